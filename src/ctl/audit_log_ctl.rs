@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+
+use crate::{
+    pojo::audit_log_pojo::*,
+    ctl::extract::AppQuery,
+    util::{paged_struct::PageData, result_struct::RespResult},
+    AppState, ResultJson,
+};
+
+pub struct AuditLogCtl();
+
+impl AuditLogCtl {
+    /// Retrieves a paginated list of audit log records, newest first
+    /// 
+    /// # Arguments
+    /// * `condition` - Query parameters filtering by actor, entity, action, org and time range
+    /// * `state` - Application state containing database connection
+    /// 
+    /// # Returns
+    /// A paginated list of recorded mutations with their before/after snapshots
+    pub async fn page(
+        AppQuery(condition): AppQuery<AuditLogCondition>,
+        State(state): State<Arc<AppState>>,
+    ) -> ResultJson<PageData<AuditLogVo>> {
+        let audit_logs = state.svcs().audit_log.page(condition)
+            .await?;
+    
+        Ok(Json(RespResult::ok(audit_logs)))
+    }
+}
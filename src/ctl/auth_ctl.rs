@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::{header::USER_AGENT, HeaderMap},
+    Json,
+};
+
+use crate::{
+    ctl::{extract::ValidJson, principal::CurrentUser},
+    pojo::auth_pojo::*,
+    util::result_struct::RespResult,
+    AppState, ResultJson,
+};
+
+pub struct AuthCtl();
+
+impl AuthCtl {
+    /// Authenticates a user by username and password
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `headers` - Request headers, the user agent is recorded with the session
+    /// * `login_dto` - Username, password and optional device of the account
+    ///
+    /// # Returns
+    /// A signed RS256 access token and a refresh token together with the user's code, org and roles
+    pub async fn login(
+        State(state): State<Arc<AppState>>,
+        headers: HeaderMap,
+        ValidJson(login_dto): ValidJson<LoginDto>,
+    ) -> ResultJson<LoginVo> {
+        let login_vo = state.svcs().auth
+            .login(login_dto, Self::user_agent(&headers))
+            .await?;
+        Ok(Json(RespResult::ok(login_vo)))
+    }
+
+    /// Rotates a refresh token into a new access/refresh token pair
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `headers` - Request headers, the user agent is recorded with the session
+    /// * `refresh_dto` - The refresh token returned by the last login or refresh
+    ///
+    /// # Returns
+    /// A new access token and refresh token, the presented refresh token becomes invalid
+    pub async fn refresh(
+        State(state): State<Arc<AppState>>,
+        headers: HeaderMap,
+        ValidJson(refresh_dto): ValidJson<RefreshDto>,
+    ) -> ResultJson<LoginVo> {
+        let login_vo = state.svcs().auth
+            .refresh(refresh_dto, Self::user_agent(&headers))
+            .await?;
+        Ok(Json(RespResult::ok(login_vo)))
+    }
+
+    /// Ends the session of the calling access token
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `current_user` - Caller identified by the bearer token
+    ///
+    /// # Returns
+    /// Whether the session was ended, its access and refresh tokens stop working
+    pub async fn logout(
+        State(state): State<Arc<AppState>>,
+        CurrentUser(claims): CurrentUser,
+    ) -> ResultJson<bool> {
+        state.svcs().auth
+            .logout(&claims)
+            .await?;
+        Ok(Json(RespResult::ok(true)))
+    }
+
+    /// Lists the active sessions of the calling user
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `current_user` - Caller identified by the bearer token
+    ///
+    /// # Returns
+    /// The live sessions with device and user agent, the caller's own session is flagged as current
+    pub async fn sessions(
+        State(state): State<Arc<AppState>>,
+        CurrentUser(claims): CurrentUser,
+    ) -> ResultJson<Vec<SessionVo>> {
+        let sessions = state.svcs().auth
+            .list_sessions(&claims)
+            .await?;
+        Ok(Json(RespResult::ok(sessions)))
+    }
+
+    /// Changes the password of an account
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `change_password_dto` - Username, current password and new password
+    ///
+    /// # Returns
+    /// Whether the password was changed
+    pub async fn change_password(
+        State(state): State<Arc<AppState>>,
+        ValidJson(change_password_dto): ValidJson<ChangePasswordDto>,
+    ) -> ResultJson<bool> {
+        state.svcs().auth
+            .change_password(change_password_dto)
+            .await?;
+        Ok(Json(RespResult::ok(true)))
+    }
+
+    /// Returns the PEM encoded public key used to verify issued access tokens
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing the signing keys
+    ///
+    /// # Returns
+    /// The RSA public key in PEM format
+    pub async fn public_key(State(state): State<Arc<AppState>>) -> ResultJson<String> {
+        Ok(Json(RespResult::ok(state.svcs().auth.public_key_pem())))
+    }
+
+    fn user_agent(headers: &HeaderMap) -> Option<String> {
+        headers
+            .get(USER_AGENT)
+            .and_then(|user_agent| user_agent.to_str().ok())
+            .map(|user_agent| user_agent.chars().take(255).collect())
+    }
+}
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+
+use crate::{
+    ctl::{
+        extract::{AppPath, AppQuery, ValidJson},
+        principal::{CurrentUser, OptionalUser},
+    },
+    pojo::{authz_pojo::*, permission_pojo::PermissionVo},
+    util::{
+        exception::AppError,
+        result_struct::RespResult,
+        tenant,
+        tree::TreeNode,
+    },
+    AppState, ResultJson,
+};
+
+pub struct AuthzCtl();
+
+impl AuthzCtl {
+    /// Resolves the effective roles and permissions of a user
+    ///
+    /// # Arguments
+    /// * `user_code` - The `user_info.user_code` of the user
+    /// * `state` - Application state containing database connection
+    ///
+    /// # Returns
+    /// The user's roles from all binding tables and the permissions they grant, with their origin
+    pub async fn user_permissions(
+        AppPath(user_code): AppPath<String>,
+        State(state): State<Arc<AppState>>,
+    ) -> ResultJson<EffectivePermissionsVo> {
+        let permissions = state.svcs().authz
+            .effective_permissions(&user_code)
+            .await?;
+        Ok(Json(RespResult::ok(permissions)))
+    }
+
+    /// Resolves the data scope of a user, the rows of the business tables the user may see
+    ///
+    /// # Arguments
+    /// * `user_code` - The `user_info.user_code` of the user
+    /// * `condition` - Query parameters selecting the org, by default the caller's organization or else the user's own
+    /// * `state` - Application state containing database connection
+    ///
+    /// # Returns
+    /// The widest data scope of the user's roles with the org and the department codes it covers
+    pub async fn user_data_scope(
+        AppPath(user_code): AppPath<String>,
+        AppQuery(condition): AppQuery<DataScopeCondition>,
+        State(state): State<Arc<AppState>>,
+    ) -> ResultJson<DataScopeVo> {
+        let data_scope = state.svcs().authz
+            .data_scope(&user_code, Self::tenant_org_code(condition.org_code)?)
+            .await?;
+        Ok(Json(RespResult::ok(data_scope)))
+    }
+
+    /// Retrieves the menu tree of the calling user
+    ///
+    /// # Arguments
+    /// * `condition` - Query parameters selecting the application
+    /// * `state` - Application state containing database connection
+    /// * `user` - Caller identified by the bearer token
+    ///
+    /// # Returns
+    /// The permission nodes granted to the caller together with their ancestors, ordered by `sort`
+    pub async fn my_menus(
+        AppQuery(condition): AppQuery<MenuCondition>,
+        State(state): State<Arc<AppState>>,
+        CurrentUser(claims): CurrentUser,
+    ) -> ResultJson<Vec<TreeNode<PermissionVo>>> {
+        let user_code = claims.user_code.unwrap_or_default();
+        let menus = state.svcs().authz
+            .menus(&user_code, claims.is_admin, condition.application)
+            .await?;
+        Ok(Json(RespResult::ok(menus)))
+    }
+
+    /// Checks whether a user holds a permission
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `bearer` - User of the `Authorization` header, used when the body names no subject
+    /// * `check_dto` - User code or token, permission code, optional application and org
+    ///
+    /// # Returns
+    /// Allow/deny together with the roles granting the permission
+    pub async fn check(
+        State(state): State<Arc<AppState>>,
+        bearer: OptionalUser,
+        ValidJson(check_dto): ValidJson<PermissionCheckDto>,
+    ) -> ResultJson<PermissionCheckVo> {
+        let user_code = Self::subject_user_code(&state, &bearer, &check_dto.subject).await?;
+        let result = state.svcs().authz
+            .check(&user_code, Self::tenant_org_code(check_dto.subject.org_code)?, vec![check_dto.check])
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::Internal(anyhow::anyhow!("permission check returned no result")))?;
+        Ok(Json(RespResult::ok(result)))
+    }
+
+    /// Checks several permissions of one user at once
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `bearer` - User of the `Authorization` header, used when the body names no subject
+    /// * `batch_dto` - User code or token, optional org and the permissions to check
+    ///
+    /// # Returns
+    /// One allow/deny result per requested permission, in request order
+    pub async fn check_batch(
+        State(state): State<Arc<AppState>>,
+        bearer: OptionalUser,
+        ValidJson(batch_dto): ValidJson<PermissionCheckBatchDto>,
+    ) -> ResultJson<Vec<PermissionCheckVo>> {
+        let user_code = Self::subject_user_code(&state, &bearer, &batch_dto.subject).await?;
+        let results = state.svcs().authz
+            .check(&user_code, Self::tenant_org_code(batch_dto.subject.org_code)?, batch_dto.checks)
+            .await?;
+        Ok(Json(RespResult::ok(results)))
+    }
+
+    /// Org a check runs in: the requested one, which must be the caller's organization, or else that organization
+    fn tenant_org_code(org_code: Option<String>) -> Result<Option<String>, AppError> {
+        tenant::check(org_code.as_deref())?;
+        Ok(org_code.filter(|org_code| !org_code.is_empty()).or_else(tenant::current))
+    }
+
+    /// User code named by the body, or carried by the body token or the bearer token
+    async fn subject_user_code(
+        state: &AppState,
+        bearer: &OptionalUser,
+        subject: &CheckSubjectDto,
+    ) -> Result<String, AppError> {
+        if let Some(user_code) = &subject.user_code {
+            return Ok(user_code.clone());
+        }
+        let claims = match (&subject.token, &bearer.0) {
+            (Some(token), _) => state.svcs().auth
+                .verify_token(token)
+                .await?,
+            (None, Some(claims)) => claims.clone(),
+            (None, None) => {
+                return Err(AppError::validation("缺少 userCode 或 token！"))
+            }
+        };
+        claims
+            .user_code
+            .ok_or_else(|| AppError::NotFound("令牌未关联用户编码！".to_string()))
+    }
+}
@@ -0,0 +1,202 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use axum::{
+    extract::State,
+    routing::{get, post, put},
+    Json, Router,
+};
+
+use crate::{
+    ctl::{
+        extract::{AppJson, AppPath, AppQuery, ValidJson, ValidPatch},
+        principal::OptionalUser,
+    },
+    svc::crud_svc::CrudHooks,
+    util::{desensitize::SensitiveProjection, exception::AppError, paged_struct::PageData, result_struct::RespResult},
+    AppState, ResultJson,
+};
+
+/// Handlers shared by every CRUD resource
+pub struct CrudCtl<R>(PhantomData<R>);
+
+impl<R: CrudHooks> CrudCtl<R> {
+    /// Retrieves a list of records based on the provided search conditions
+    ///
+    /// # Arguments
+    /// * `condition` - Query parameters containing search criteria
+    /// * `projection` - Whether the sensitive columns are requested
+    /// * `state` - Application state containing database connection
+    /// * `user` - Caller identified by the bearer token, privileged callers may set `includeSensitive`
+    ///
+    /// # Returns
+    /// A list of records matching the search criteria
+    pub async fn list(
+        AppQuery(condition): AppQuery<R::Condition>,
+        AppQuery(projection): AppQuery<SensitiveProjection>,
+        State(state): State<Arc<AppState>>,
+        user: OptionalUser,
+    ) -> ResultJson<Vec<R::Vo>> {
+        let include_sensitive = Self::include_sensitive(&user, &projection)?;
+        let mut records = R::svc(state.svcs()).list(condition)
+            .await?;
+        if !include_sensitive {
+            records.iter_mut().for_each(R::desensitize);
+        }
+
+        Ok(Json(RespResult::ok(records)))
+    }
+
+    /// Retrieves a paginated list of records based on the provided search conditions
+    ///
+    /// # Arguments
+    /// * `condition` - Query parameters containing search criteria and pagination info
+    /// * `projection` - Whether the sensitive columns are requested
+    /// * `state` - Application state containing database connection
+    /// * `user` - Caller identified by the bearer token, privileged callers may set `includeSensitive`
+    ///
+    /// # Returns
+    /// A paginated list of records matching the search criteria
+    pub async fn page(
+        AppQuery(condition): AppQuery<R::Condition>,
+        AppQuery(projection): AppQuery<SensitiveProjection>,
+        State(state): State<Arc<AppState>>,
+        user: OptionalUser,
+    ) -> ResultJson<PageData<R::Vo>> {
+        let include_sensitive = Self::include_sensitive(&user, &projection)?;
+        let mut records = R::svc(state.svcs()).page(condition)
+            .await?;
+        if !include_sensitive {
+            records.page_data.iter_mut().for_each(R::desensitize);
+        }
+
+        Ok(Json(RespResult::ok(records)))
+    }
+
+    /// Creates a new record
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `dto` - Data transfer object containing the new record's information, checked against its `Validate` rules
+    ///
+    /// # Returns
+    /// The ID of the newly created record
+    pub async fn save(
+        State(state): State<Arc<AppState>>,
+        ValidJson(dto): ValidJson<R::Dto>,
+    ) -> ResultJson<i64> {
+        let rec_id = R::svc(state.svcs()).save(dto)
+            .await?;
+        Ok(Json(RespResult::ok(rec_id)))
+    }
+
+    /// Retrieves a single record by its ID
+    ///
+    /// # Arguments
+    /// * `rec_id` - The ID of the record to retrieve
+    /// * `state` - Application state containing database connection
+    /// * `projection` - Whether the sensitive columns are requested
+    /// * `user` - Caller identified by the bearer token, privileged callers may set `includeSensitive`
+    ///
+    /// # Returns
+    /// The record if found, None otherwise
+    pub async fn get_by_id(
+        AppPath(rec_id): AppPath<i64>,
+        State(state): State<Arc<AppState>>,
+        AppQuery(projection): AppQuery<SensitiveProjection>,
+        user: OptionalUser,
+    ) -> ResultJson<Option<R::Vo>> {
+        let include_sensitive = Self::include_sensitive(&user, &projection)?;
+        let mut record = R::svc(state.svcs()).get_by_id(rec_id)
+            .await?;
+        if !include_sensitive {
+            record.iter_mut().for_each(R::desensitize);
+        }
+
+        Ok(Json(RespResult::ok(record)))
+    }
+
+    /// Updates an existing record
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `dto` - Data transfer object containing the updated information, the fields it carries are checked against its `Validate` rules
+    ///
+    /// # Returns
+    /// The number of records updated
+    pub async fn update_by_id(
+        State(state): State<Arc<AppState>>,
+        ValidPatch(dto): ValidPatch<R::Dto>,
+    ) -> ResultJson<u64> {
+        let result = R::svc(state.svcs()).update_by_id(dto)
+            .await?;
+        Ok(Json(RespResult::ok(result)))
+    }
+
+    /// Soft deletes multiple records by their IDs (marks them as deleted)
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `dto` - Data transfer object containing the IDs to delete
+    ///
+    /// # Returns
+    /// The number of records marked as deleted
+    pub async fn delete_by_ids(
+        State(state): State<Arc<AppState>>,
+        AppJson(dto): AppJson<R::Dto>,
+    ) -> ResultJson<u64> {
+        let result = R::svc(state.svcs()).delete_by_ids(dto)
+            .await?;
+        Ok(Json(RespResult::ok(result)))
+    }
+
+    /// Permanently removes multiple records by their IDs
+    ///
+    /// # Arguments
+    /// * `state` - Application state containing database connection
+    /// * `dto` - Data transfer object containing the IDs to remove
+    ///
+    /// # Returns
+    /// The number of records permanently removed
+    pub async fn remove_by_ids(
+        State(state): State<Arc<AppState>>,
+        AppJson(dto): AppJson<R::Dto>,
+    ) -> ResultJson<u64> {
+        let result = R::svc(state.svcs()).remove_by_ids(dto)
+            .await?;
+        Ok(Json(RespResult::ok(result)))
+    }
+
+    /// Whether the records may be returned as stored, resources without
+    /// sensitive columns never need the privilege
+    fn include_sensitive(
+        user: &OptionalUser,
+        projection: &SensitiveProjection,
+    ) -> Result<bool, AppError> {
+        if !R::SENSITIVE {
+            return Ok(true);
+        }
+        user.allow_sensitive(projection.include_sensitive)
+    }
+}
+
+/// Registration of the routes served by `CrudCtl`
+pub trait CrudRoutes {
+    /// Routes `base` (save, update, remove), `base/delByIds`, `base/list`,
+    /// `base/page` and `base/:id` to the handlers of resource `R`
+    fn crud<R: CrudHooks>(self, base: &str) -> Self;
+}
+
+impl CrudRoutes for Router<Arc<AppState>> {
+    fn crud<R: CrudHooks>(self, base: &str) -> Self {
+        self.route(
+            base,
+            post(CrudCtl::<R>::save)
+                .put(CrudCtl::<R>::update_by_id)
+                .delete(CrudCtl::<R>::remove_by_ids)
+        )
+        .route(&format!("{}/delByIds", base), put(CrudCtl::<R>::delete_by_ids))
+        .route(&format!("{}/list", base), get(CrudCtl::<R>::list))
+        .route(&format!("{}/page", base), get(CrudCtl::<R>::page))
+        .route(&format!("{}/:id", base), get(CrudCtl::<R>::get_by_id))
+    }
+}
@@ -0,0 +1,99 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, FromRequestParts, Path, Query, Request},
+    http::request::Parts,
+    Json,
+};
+use serde::de::DeserializeOwned;
+
+use crate::util::{
+    exception::AppError,
+    validation::{self, Validate},
+};
+
+/// `Json` body whose rejection is answered as a validation `AppError`
+pub struct AppJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for AppJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        Ok(AppJson(value))
+    }
+}
+
+/// `AppJson` body which passes every rule of its `Validate` impl, required fields included
+pub struct ValidJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let AppJson(value) = AppJson::<T>::from_request(req, state).await?;
+        validation::check(&value, false)?;
+        Ok(ValidJson(value))
+    }
+}
+
+/// `AppJson` body of an update, the fields it carries pass the rules of its `Validate` impl
+pub struct ValidPatch<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidPatch<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let AppJson(value) = AppJson::<T>::from_request(req, state).await?;
+        validation::check(&value, true)?;
+        Ok(ValidPatch(value))
+    }
+}
+
+/// `Query` parameters whose rejection is answered as a validation `AppError`
+pub struct AppQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for AppQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        Ok(AppQuery(value))
+    }
+}
+
+/// `Path` parameters whose rejection is answered as a validation `AppError`
+pub struct AppPath<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for AppPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+        Ok(AppPath(value))
+    }
+}
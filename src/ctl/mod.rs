@@ -1,33 +1,21 @@
+pub mod audit_log_ctl;
+pub mod auth_ctl;
+pub mod authz_ctl;
+pub mod crud_ctl;
+pub mod extract;
+pub mod principal;
+pub mod tree_ctl;
 pub mod user_ctl;
-pub mod organization_ctl;
 pub mod role_ctl;
 pub mod permission_ctl;
-pub mod department_ctl;
-pub mod position_ctl;
-pub mod group_ctl;
-pub mod user_info_ctl;
-pub mod user_wechat_info_ctl;
-pub mod system_config_ctl;
-pub mod user_role_ref_ctl;
-pub mod user_group_ref_ctl;
-pub mod group_role_ref_ctl;
-pub mod department_role_ref_ctl;
-pub mod position_role_ref_ctl;
-pub mod organization_role_ref_ctl;
+pub mod organization_ctl;
 
+pub use audit_log_ctl::AuditLogCtl;
+pub use auth_ctl::AuthCtl;
+pub use authz_ctl::AuthzCtl;
+pub use crud_ctl::{CrudCtl, CrudRoutes};
+pub use tree_ctl::TreeCtl;
 pub use user_ctl::UserCtl;
-pub use organization_ctl::OrganizationCtl;
 pub use role_ctl::RoleCtl;
 pub use permission_ctl::PermissionCtl;
-pub use department_ctl::DepartmentCtl;
-pub use position_ctl::PositionCtl;
-pub use group_ctl::GroupCtl;
-pub use user_info_ctl::UserInfoCtl;
-pub use user_wechat_info_ctl::UserWechatInfoCtl;
-pub use system_config_ctl::SystemConfigCtl;
-pub use user_role_ref_ctl::UserRoleRefCtl;
-pub use user_group_ref_ctl::UserGroupRefCtl;
-pub use group_role_ref_ctl::GroupRoleRefCtl;
-pub use department_role_ref_ctl::DepartmentRoleRefCtl;
-pub use position_role_ref_ctl::PositionRoleRefCtl;
-pub use organization_role_ref_ctl::OrganizationRoleRefCtl;
+pub use organization_ctl::OrganizationCtl;
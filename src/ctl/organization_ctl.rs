@@ -1,151 +1,88 @@
 use std::sync::Arc;
 
-use axum::{
-    extract::{Path, Query, State},
-    Json,
-};
+use axum::{extract::State, Json};
 
 use crate::{
-    pojo::organization_pojo::*,
-    svc::organization_svc::OrganizationSvc,
-    util::{exception::internal_err, paged_struct::PageData, result_struct::RespResult},
+    pojo::{department_pojo::DepartmentTreeVo, organization_pojo::*},
+    ctl::extract::{AppPath, AppQuery},
+    util::{result_struct::RespResult, tree::TreeNode},
     AppState, ResultJson,
 };
 
+/// Organization routes beyond the CRUD ones served by `CrudCtl`
 pub struct OrganizationCtl();
 
 impl OrganizationCtl {
-    /// Returns a simple greeting message for the root endpoint
-    pub async fn root() -> &'static str {
-        "Hello, World!"
-    }
-    
-    /// Retrieves a list of organizations based on the provided search conditions
-    /// 
+    /// Retrieves the organization hierarchy built from `parent_code`
+    ///
     /// # Arguments
-    /// * `condition` - Query parameters containing search criteria
+    /// * `condition` - Query parameters restricting the organizations by type and status
     /// * `state` - Application state containing database connection
-    /// 
+    ///
     /// # Returns
-    /// A list of organization records matching the search criteria
-    pub async fn list(
-        Query(condition): Query<OrganizationCondition>,
+    /// The root organizations, each carrying its subsidiaries
+    pub async fn tree(
+        AppQuery(condition): AppQuery<OrganizationTreeCondition>,
         State(state): State<Arc<AppState>>,
-    ) -> ResultJson<Vec<OrganizationVo>> {
-        let organizations = OrganizationSvc::get_instance(&state).list(condition)
-            .await
-            .map_err(internal_err)?;
-    
+    ) -> ResultJson<Vec<TreeNode<OrganizationVo>>> {
+        let organizations = state.svcs().organization.tree(condition)
+            .await?;
+
         Ok(Json(RespResult::ok(organizations)))
     }
-    
-    /// Retrieves a paginated list of organizations based on the provided search conditions
-    /// 
+
+    /// Retrieves every organization below an organization
+    ///
     /// # Arguments
-    /// * `condition` - Query parameters containing search criteria and pagination info
+    /// * `code` - Code of the organization
     /// * `state` - Application state containing database connection
-    /// 
+    ///
     /// # Returns
-    /// A paginated list of organization records matching the search criteria
-    pub async fn page(
-        Query(condition): Query<OrganizationCondition>,
+    /// The descendants ordered by `path`, each parent before its children
+    pub async fn descendants(
+        AppPath(code): AppPath<String>,
         State(state): State<Arc<AppState>>,
-    ) -> ResultJson<PageData<OrganizationVo>> {
-        let organizations = OrganizationSvc::get_instance(&state).page(condition)
-            .await
-            .map_err(internal_err)?;
-    
+    ) -> ResultJson<Vec<OrganizationVo>> {
+        let organizations = state.svcs().organization.descendants(&code)
+            .await?;
+
         Ok(Json(RespResult::ok(organizations)))
     }
-    
-    /// Creates a new organization record
-    /// 
-    /// # Arguments
-    /// * `organization_dto` - Organization data transfer object containing the new organization's information
-    /// * `state` - Application state containing database connection
-    /// 
-    /// # Returns
-    /// The ID of the newly created organization
-    pub async fn save(
-        State(state): State<Arc<AppState>>,
-        Json(organization_dto): Json<OrganizationDto>,
-    ) -> ResultJson<i64> {
-        let organization_id = OrganizationSvc::get_instance(&state).save(organization_dto)
-            .await
-            .map_err(internal_err)?;
-        Ok(Json(RespResult::ok(organization_id)))
-    }
-    
-    /// Retrieves a single organization by its ID
-    /// 
-    /// # Arguments
-    /// * `organization_id` - The ID of the organization to retrieve
-    /// * `state` - Application state containing database connection
-    /// 
-    /// # Returns
-    /// The organization record if found, None otherwise
-    pub async fn get_by_id(
-        Path(organization_id): Path<i64>,
-        State(state): State<Arc<AppState>>,
-    ) -> ResultJson<Option<OrganizationVo>> {
-        let organization = OrganizationSvc::get_instance(&state).get_by_id(organization_id)
-            .await
-            .map_err(internal_err)?;
-    
-        Ok(Json(RespResult::ok(organization)))
-    }
-    
-    /// Updates an existing organization record
-    /// 
-    /// # Arguments
-    /// * `organization_dto` - Organization data transfer object containing the updated information
-    /// * `state` - Application state containing database connection
-    /// 
-    /// # Returns
-    /// The number of records updated
-    pub async fn update_by_id(
-        State(state): State<Arc<AppState>>,
-        Json(organization_dto): Json<OrganizationDto>,
-    ) -> ResultJson<u64> {
-        let result = OrganizationSvc::get_instance(&state).update_by_id(organization_dto)
-            .await
-            .map_err(internal_err)?;
-        Ok(Json(RespResult::ok(result)))
-    }
-    
-    /// Soft deletes multiple organizations by their IDs (marks them as deleted)
-    /// 
+
+    /// Retrieves the chain of organizations above an organization
+    ///
     /// # Arguments
-    /// * `organization_dto` - Organization data transfer object containing the IDs to delete
+    /// * `code` - Code of the organization
     /// * `state` - Application state containing database connection
-    /// 
+    ///
     /// # Returns
-    /// The number of records marked as deleted
-    pub async fn delete_by_ids(
+    /// The ancestors from the root organization down to the direct parent
+    pub async fn ancestors(
+        AppPath(code): AppPath<String>,
         State(state): State<Arc<AppState>>,
-        Json(organization_dto): Json<OrganizationDto>,
-    ) -> ResultJson<u64> {
-        let result = OrganizationSvc::get_instance(&state).delete_by_ids(organization_dto)
-            .await
-            .map_err(internal_err)?;
-        Ok(Json(RespResult::ok(result)))
+    ) -> ResultJson<Vec<OrganizationVo>> {
+        let organizations = state.svcs().organization.ancestors(&code)
+            .await?;
+
+        Ok(Json(RespResult::ok(organizations)))
     }
-    
-    /// Permanently removes multiple organizations by their IDs
-    /// 
+
+    /// Retrieves the department hierarchy of an organization with its positions and headcounts
+    ///
     /// # Arguments
-    /// * `organization_dto` - Organization data transfer object containing the IDs to remove
+    /// * `org_code` - Code of the organization
     /// * `state` - Application state containing database connection
-    /// 
+    ///
     /// # Returns
-    /// The number of records permanently removed
-    pub async fn remove_by_ids(
+    /// The top-level departments, each carrying its positions, its direct and recursive
+    /// headcounts and its sub-departments
+    pub async fn department_tree(
+        AppPath(org_code): AppPath<String>,
         State(state): State<Arc<AppState>>,
-        Json(organization_dto): Json<OrganizationDto>,
-    ) -> ResultJson<u64> {
-        let result = OrganizationSvc::get_instance(&state).remove_by_ids(organization_dto)
-            .await
-            .map_err(internal_err)?;
-        Ok(Json(RespResult::ok(result)))
+    ) -> ResultJson<Vec<TreeNode<DepartmentTreeVo>>> {
+        let departments = state.svcs().department.tree(&org_code)
+            .await?;
+
+        Ok(Json(RespResult::ok(departments)))
     }
-} 
\ No newline at end of file
+}
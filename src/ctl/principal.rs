@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap},
+};
+
+use crate::{
+    pojo::auth_pojo::Claims,
+    util::exception::AppError,
+    AppState,
+};
+
+/// Claims of the bearer token sent with the request, if any
+pub struct OptionalUser(pub Option<Claims>);
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for OptionalUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        // already verified by the authorization middleware
+        if let Some(claims) = parts.extensions.get::<Claims>() {
+            return Ok(OptionalUser(Some(claims.clone())));
+        }
+        let Some(token) = bearer_token(&parts.headers) else {
+            return Ok(OptionalUser(None));
+        };
+        let claims = state.svcs().auth
+            .verify_token(token)
+            .await?;
+        Ok(OptionalUser(Some(claims)))
+    }
+}
+
+/// Claims of the bearer token sent with the request, rejected with 401 when absent
+pub struct CurrentUser(pub Claims);
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for CurrentUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        match OptionalUser::from_request_parts(parts, state).await? {
+            OptionalUser(Some(claims)) => Ok(CurrentUser(claims)),
+            OptionalUser(None) => Err(AppError::Unauthorized("请先登录！".to_string())),
+        }
+    }
+}
+
+impl CurrentUser {
+    /// Rejects callers which are not administrators
+    pub fn require_admin(&self) -> Result<(), AppError> {
+        if self.0.is_admin {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden("无权执行该操作！".to_string()))
+        }
+    }
+}
+
+impl OptionalUser {
+    /// Whether the sensitive projection may be returned to this caller.
+    /// Requesting it without the privilege is rejected instead of silently ignored.
+    pub fn allow_sensitive(&self, include_sensitive: Option<bool>) -> Result<bool, AppError> {
+        if !include_sensitive.unwrap_or(false) {
+            return Ok(false);
+        }
+        match &self.0 {
+            Some(claims) if claims.is_admin => Ok(true),
+            Some(_) => Err(AppError::Forbidden("无权查看敏感信息！".to_string())),
+            None => Err(AppError::Unauthorized("请先登录！".to_string())),
+        }
+    }
+}
+
+/// Token of an `Authorization: Bearer ...` header
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
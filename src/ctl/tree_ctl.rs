@@ -0,0 +1,39 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use axum::{extract::State, Json};
+
+use crate::{
+    ctl::extract::{AppPath, ValidJson},
+    mapper::tree_mapper::{TreeMapperTrait, TreeResource},
+    pojo::tree_pojo::MoveDto,
+    svc::crud_svc::CrudHooks,
+    util::result_struct::RespResult,
+    AppState, ResultJson,
+};
+
+/// Handlers shared by every resource whose rows form a tree
+pub struct TreeCtl<R>(PhantomData<R>);
+
+impl<R: CrudHooks + TreeResource> TreeCtl<R>
+where
+    R::Mapper: TreeMapperTrait<R>,
+{
+    /// Moves a node with its whole subtree below a new parent
+    ///
+    /// # Arguments
+    /// * `code` - Code of the node to move
+    /// * `state` - Application state containing database connection
+    /// * `move_dto` - Code of the new parent, none for a root node, and the `recSign` of the node
+    ///
+    /// # Returns
+    /// The number of nodes whose path was rewritten, the node included
+    pub async fn move_node(
+        AppPath(code): AppPath<String>,
+        State(state): State<Arc<AppState>>,
+        ValidJson(move_dto): ValidJson<MoveDto>,
+    ) -> ResultJson<u64> {
+        let moved = R::svc(state.svcs()).move_node(&code, move_dto)
+            .await?;
+        Ok(Json(RespResult::ok(moved)))
+    }
+}
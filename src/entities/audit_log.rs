@@ -0,0 +1,27 @@
+//! `audit_log` entity, maintained by hand together with its migration
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub actor_id: i64,
+    pub actor_code: Option<String>,
+    pub ip: Option<String>,
+    pub entity: String,
+    pub action: String,
+    pub rec_ids: Option<Json>,
+    pub before_data: Option<Json>,
+    pub after_data: Option<Json>,
+    pub diff_data: Option<Json>,
+    pub org_code: Option<String>,
+    pub create_time: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
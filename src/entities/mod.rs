@@ -2,6 +2,7 @@
 
 pub mod prelude;
 
+pub mod audit_log;
 pub mod department;
 pub mod department_role_ref;
 pub mod group;
@@ -11,8 +12,10 @@ pub mod organization_role_ref;
 pub mod permission;
 pub mod position;
 pub mod position_role_ref;
+pub mod refresh_token;
 pub mod role;
 pub mod system_config;
+pub mod token_deny_list;
 pub mod user_group_ref;
 pub mod user_info;
 pub mod user_role_ref;
@@ -11,7 +11,7 @@ pub struct Model {
     pub org_code: String,
     pub role_code: String,
     #[sea_orm(default_value = 0, nullable)]
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     pub create_time: Option<DateTime>,
     pub update_time: Option<DateTime>,
     #[sea_orm(default_value = 0, nullable)]
@@ -24,7 +24,7 @@ pub struct Model {
     pub extra: Option<Json>,
     pub remark: Option<String>,
     #[sea_orm(default_value = 0, nullable)]
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     pub create_time: Option<DateTime>,
     pub update_time: Option<DateTime>,
     #[sea_orm(default_value = 0, nullable)]
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.12
 
+pub use super::audit_log::Entity as AuditLog;
 pub use super::department::Entity as Department;
 pub use super::department_role_ref::Entity as DepartmentRoleRef;
 pub use super::group::Entity as Group;
@@ -9,8 +10,10 @@ pub use super::organization_role_ref::Entity as OrganizationRoleRef;
 pub use super::permission::Entity as Permission;
 pub use super::position::Entity as Position;
 pub use super::position_role_ref::Entity as PositionRoleRef;
+pub use super::refresh_token::Entity as RefreshToken;
 pub use super::role::Entity as Role;
 pub use super::system_config::Entity as SystemConfig;
+pub use super::token_deny_list::Entity as TokenDenyList;
 pub use super::user_group_ref::Entity as UserGroupRef;
 pub use super::user_info::Entity as UserInfo;
 pub use super::user_role_ref::Entity as UserRoleRef;
@@ -0,0 +1,27 @@
+//! `refresh_token` entity, maintained by hand together with its migration
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "refresh_token")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    pub family_id: String,
+    pub device: Option<String>,
+    pub user_agent: Option<String>,
+    pub expire_time: DateTime,
+    pub used_time: Option<DateTime>,
+    pub revoke_time: Option<DateTime>,
+    pub create_time: Option<DateTime>,
+    pub update_time: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -17,10 +17,11 @@ pub struct Model {
     pub org_code: Option<String>,
     pub extra: Option<Json>,
     pub remark: Option<String>,
-    pub role_type: i8,
+    pub role_type: i16,
     pub path: Option<String>,
+    pub data_scope: Option<String>,
     #[sea_orm(default_value = 0, nullable)]
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     pub create_time: Option<DateTime>,
     pub update_time: Option<DateTime>,
     #[sea_orm(default_value = 0, nullable)]
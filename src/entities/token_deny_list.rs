@@ -0,0 +1,21 @@
+//! `token_deny_list` entity, maintained by hand together with its migration
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "token_deny_list")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub token_id: String,
+    pub user_id: i64,
+    pub reason: Option<String>,
+    pub expire_time: DateTime,
+    pub create_time: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -14,15 +14,15 @@ pub struct Model {
     pub real_name: Option<String>,
     pub password: Option<String>,
     #[sea_orm(default_value = 0, nullable)]
-    pub status: Option<i8>,
+    pub status: Option<i16>,
     #[sea_orm(default_value = 0, nullable)]
-    pub r#type: Option<i8>,
+    pub r#type: Option<i16>,
     pub email: Option<String>,
     pub area_code: Option<String>,
     pub phone: Option<String>,
     pub remark: Option<String>,
     #[sea_orm(default_value = 0, nullable)]
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     pub head_pic: Option<String>,
     pub create_time: Option<DateTime>,
     pub update_time: Option<DateTime>,
@@ -31,7 +31,7 @@ pub struct Model {
     #[sea_orm(default_value = 0, nullable)]
     pub update_by: Option<i64>,
     #[sea_orm(default_value = 0, nullable)]
-    pub is_admin: Option<i8>,
+    pub is_admin: Option<i16>,
     pub open_id: Option<String>,
     pub last_login_time: Option<DateTime>,
     pub wechat_open_id: Option<String>,
@@ -14,7 +14,7 @@ pub struct Model {
     pub mini_open_id: Option<String>,
     pub nickname: Option<String>,
     pub language: Option<String>,
-    pub subscribe: Option<i8>,
+    pub subscribe: Option<i16>,
     pub head_img_url: Option<String>,
     pub subscribe_time: Option<i64>,
     pub remark: Option<String>,
@@ -26,7 +26,7 @@ pub struct Model {
     pub app_id: Option<String>,
     pub app_type: Option<String>,
     #[sea_orm(default_value = 0, nullable)]
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     pub create_time: Option<DateTime>,
     pub update_time: Option<DateTime>,
     #[sea_orm(default_value = 0, nullable)]
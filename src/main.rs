@@ -1,7 +1,7 @@
-use std::error::Error;
+use std::{error::Error, net::SocketAddr};
 
 
-use auth_center::route::build_app_route;
+use auth_center::{connect_db, migration::MigrateCommand, route::build_app_route};
 use tracing::{info, instrument};
 
 #[tokio::main]
@@ -13,10 +13,19 @@ async fn main() -> Result<(), Box<dyn Error>> {
     .with_test_writer()
     .init();
 
+    // `auth_center migrate <up [n] | down [n] | status>` manages the schema instead of serving
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("migrate") {
+        let command = MigrateCommand::parse(&args[1..])?;
+        let db = connect_db().await?;
+        command.run(&db).await?;
+        return Ok(());
+    }
+
     let app = build_app_route().await.unwrap();
     let listener = tokio::net::TcpListener::bind("0.0.0.0:18080").await.unwrap();
     info!("listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await.unwrap();
 
     Ok(())
 }
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use sea_orm::sea_query::Cond;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, Iterable, PaginatorTrait,
+    PrimaryKeyToColumn, QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+};
+use serde_json::{json, Value};
+
+use crate::entities::{prelude::*, *};
+use crate::pojo::audit_log_pojo::*;
+use crate::util::{audit, tenant};
+use crate::util::paged_struct::{PageData, PageInfo, Pageable};
+
+/// Trait defining the interface for audit-log database operations
+#[async_trait::async_trait]
+pub trait AuditLogMapperTrait: Send + Sync {
+    async fn page(&self, condition: AuditLogCondition) -> Result<PageData<AuditLogVo>, DbErr>;
+    async fn save(&self, audit_log_actmod: audit_log::ActiveModel) -> Result<i64, DbErr>;
+}
+
+/// Implementation of AuditLogMapperTrait
+pub struct AuditLogMapper {
+    db: DatabaseConnection,
+}
+
+impl AuditLogMapper {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+
+    /// Current rows of `E` with the given primary keys as JSON objects, read through `db`
+    /// which is the transaction of the mutation when there is one
+    pub async fn snapshot<E: EntityTrait, C: ConnectionTrait>(&self, db: &C, rec_ids: &[i64]) -> Result<Vec<Value>, DbErr> {
+        let Some(primary_key) = E::PrimaryKey::iter().next() else {
+            return Ok(vec![]);
+        };
+        if rec_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        E::find()
+            .filter(primary_key.into_column().is_in(rec_ids.to_vec()))
+            .into_json()
+            .all(db)
+            .await
+    }
+
+    /// Records a mutation of `E` done by the current principal through `db`. `before` is
+    /// the snapshot taken ahead of the mutation, the after image is read back here.
+    pub async fn record<E: EntityTrait, C: ConnectionTrait>(
+        &self,
+        db: &C,
+        action: AuditAction,
+        rec_ids: Vec<i64>,
+        before: Vec<Value>,
+    ) -> Result<i64, DbErr> {
+        let after = match action {
+            AuditAction::Remove => vec![],
+            _ => self.snapshot::<E, C>(db, &rec_ids).await?,
+        };
+        let diff = audit::diff_rows(&before, &after);
+        let context = audit::context();
+        let org_code = after
+            .iter()
+            .chain(before.iter())
+            .find_map(|row| row.get("org_code")?.as_str().map(str::to_string))
+            .or(context.org_code);
+        let as_json = |rows: Vec<Value>| (!rows.is_empty()).then(|| audit::redact(Value::Array(rows)));
+
+        let audit_log_actmod = audit_log::ActiveModel {
+            actor_id: Set(context.principal_id),
+            actor_code: Set(context.user_code),
+            ip: Set(context.ip),
+            entity: Set(E::default().table_name().to_string()),
+            action: Set(action.as_str().to_string()),
+            rec_ids: Set(Some(json!(rec_ids))),
+            before_data: Set(as_json(before)),
+            after_data: Set(as_json(after)),
+            diff_data: Set(Some(audit::redact(diff))),
+            org_code: Set(org_code),
+            create_time: Set(Some(audit::now())),
+            ..Default::default()
+        };
+        Ok(AuditLog::insert(audit_log_actmod).exec(db).await?.last_insert_id)
+    }
+
+    fn build_query_wrapper(&self, condition: &AuditLogCondition) -> Condition {
+        let mut query_wrapper = Cond::all();
+        if let Some(actor_id) = &condition.actor_id {
+            query_wrapper = query_wrapper.add(audit_log::Column::ActorId.eq(*actor_id));
+        };
+        if let Some(actor_code) = &condition.actor_code {
+            query_wrapper = query_wrapper.add(audit_log::Column::ActorCode.eq(actor_code));
+        };
+        if let Some(entity) = &condition.entity {
+            query_wrapper = query_wrapper.add(audit_log::Column::Entity.eq(entity));
+        };
+        if let Some(action) = &condition.action {
+            query_wrapper = query_wrapper.add(audit_log::Column::Action.eq(action));
+        };
+        if let Some(org_code) = &condition.org_code {
+            query_wrapper = query_wrapper.add(audit_log::Column::OrgCode.eq(org_code));
+        };
+        if let Some(org_code) = tenant::current() {
+            query_wrapper = query_wrapper.add(audit_log::Column::OrgCode.eq(org_code));
+        };
+        if let Some(start_time) = &condition.start_time {
+            query_wrapper = query_wrapper.add(audit_log::Column::CreateTime.gte(*start_time));
+        };
+        if let Some(end_time) = &condition.end_time {
+            query_wrapper = query_wrapper.add(audit_log::Column::CreateTime.lt(*end_time));
+        };
+        query_wrapper
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogMapperTrait for AuditLogMapper {
+    async fn page(&self, condition: AuditLogCondition) -> Result<PageData<AuditLogVo>, DbErr> {
+        let audit_logs = AuditLog::find()
+            .filter(self.build_query_wrapper(&condition))
+            .order_by_desc(audit_log::Column::Id)
+            .apply_if(condition.get_size(), QuerySelect::limit)
+            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
+            .into_model::<AuditLogVo>()
+            .all(&self.db)
+            .await?;
+        let total = AuditLog::find()
+            .filter(self.build_query_wrapper(&condition))
+            .count(&self.db)
+            .await?;
+        let page_info = PageInfo::from(
+            condition.get_page().unwrap_or(1),
+            condition.get_size().unwrap_or(20),
+            total,
+        );
+        Ok(PageData::new(page_info, audit_logs))
+    }
+
+    async fn save(&self, audit_log_actmod: audit_log::ActiveModel) -> Result<i64, DbErr> {
+        let inserted_result = AuditLog::insert(audit_log_actmod)
+            .exec(&self.db)
+            .await?;
+        Ok(inserted_result.last_insert_id)
+    }
+}
@@ -0,0 +1,227 @@
+use anyhow::Result;
+use sea_orm::sea_query::Cond;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect};
+
+use crate::entities::{prelude::*, *};
+use crate::mapper::tree_mapper::path_starts_with;
+use crate::pojo::authz_pojo::GrantSourceType;
+
+/// `permission.node_type` of nodes mapping an API route to the permission guarding it
+pub const PERMISSION_NODE_TYPE_API: &str = "api";
+
+/// A role reaching a user through one of the five role binding tables
+#[derive(Debug, Clone)]
+pub struct RoleBinding {
+    pub role_code: String,
+    pub source_type: GrantSourceType,
+    pub source_code: String,
+}
+
+/// Trait defining the read-only queries behind permission resolution
+#[async_trait::async_trait]
+pub trait AuthzMapperTrait: Send + Sync {
+    async fn get_user_info_by_user_code(&self, user_code: &str) -> Result<Option<user_info::Model>, DbErr>;
+    async fn get_user_info_in_org(&self, user_code: &str, org_code: &str) -> Result<Option<user_info::Model>, DbErr>;
+    async fn list_role_bindings(&self, user_info: &user_info::Model) -> Result<Vec<RoleBinding>, DbErr>;
+    async fn list_roles_by_codes(&self, role_codes: Vec<String>) -> Result<Vec<role::Model>, DbErr>;
+    async fn list_permissions_by_keys(&self, ids: Vec<i64>, keys: Vec<String>) -> Result<Vec<permission::Model>, DbErr>;
+    async fn list_api_permissions(&self, link_urls: Vec<String>, org_code: &Option<String>) -> Result<Vec<permission::Model>, DbErr>;
+    async fn get_department(&self, org_code: &str, code: &str) -> Result<Option<department::Model>, DbErr>;
+    async fn list_department_codes_below(&self, org_code: &str, path: &str) -> Result<Vec<String>, DbErr>;
+}
+
+/// Implementation of AuthzMapperTrait
+pub struct AuthzMapper {
+    db: DatabaseConnection,
+}
+
+impl AuthzMapper {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+
+    /// Ref rows without org apply everywhere, others only inside the user's org
+    fn org_wrapper<C: ColumnTrait>(column: C, org_code: &Option<String>) -> Condition {
+        let mut org_wrapper = Cond::any().add(column.is_null());
+        if let Some(org_code) = org_code {
+            org_wrapper = org_wrapper.add(column.eq(org_code));
+        };
+        org_wrapper
+    }
+
+    fn bindings(rows: Vec<(String, String)>, source_type: GrantSourceType) -> Vec<RoleBinding> {
+        rows.into_iter()
+            .map(|(source_code, role_code)| RoleBinding {
+                role_code,
+                source_type,
+                source_code,
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthzMapperTrait for AuthzMapper {
+    async fn get_user_info_by_user_code(&self, user_code: &str) -> Result<Option<user_info::Model>, DbErr> {
+        UserInfo::find()
+            .filter(user_info::Column::UserCode.eq(user_code))
+            .filter(user_info::Column::IsDel.eq(0))
+            .one(&self.db)
+            .await
+    }
+
+    /// The user info row of the person `user_code` in `org_code`, none when the person does not work there
+    async fn get_user_info_in_org(&self, user_code: &str, org_code: &str) -> Result<Option<user_info::Model>, DbErr> {
+        UserInfo::find()
+            .filter(user_info::Column::UserCode.eq(user_code))
+            .filter(user_info::Column::OrgCode.eq(org_code))
+            .filter(user_info::Column::IsDel.eq(0))
+            .one(&self.db)
+            .await
+    }
+
+    async fn list_role_bindings(&self, user_info: &user_info::Model) -> Result<Vec<RoleBinding>, DbErr> {
+        let db = &self.db;
+        let mut role_bindings = vec![];
+        let Some(user_code) = &user_info.user_code else {
+            return Ok(role_bindings);
+        };
+
+        let user_roles = UserRoleRef::find()
+            .select_only()
+            .columns([user_role_ref::Column::UserCode, user_role_ref::Column::RoleCode])
+            .filter(user_role_ref::Column::IsDel.eq(0))
+            .filter(user_role_ref::Column::UserCode.eq(user_code))
+            .filter(Self::org_wrapper(user_role_ref::Column::OrgCode, &user_info.org_code))
+            .into_tuple::<(String, String)>()
+            .all(db)
+            .await?;
+        role_bindings.extend(Self::bindings(user_roles, GrantSourceType::User));
+
+        let group_codes = UserGroupRef::find()
+            .select_only()
+            .column(user_group_ref::Column::GroupCode)
+            .filter(user_group_ref::Column::IsDel.eq(0))
+            .filter(user_group_ref::Column::UserCode.eq(user_code))
+            .filter(Self::org_wrapper(user_group_ref::Column::OrgCode, &user_info.org_code))
+            .into_tuple::<String>()
+            .all(db)
+            .await?;
+        if !group_codes.is_empty() {
+            let group_roles = GroupRoleRef::find()
+                .select_only()
+                .columns([group_role_ref::Column::GroupCode, group_role_ref::Column::RoleCode])
+                .filter(group_role_ref::Column::IsDel.eq(0))
+                .filter(group_role_ref::Column::GroupCode.is_in(group_codes))
+                .filter(Self::org_wrapper(group_role_ref::Column::OrgCode, &user_info.org_code))
+                .into_tuple::<(String, String)>()
+                .all(db)
+                .await?;
+            role_bindings.extend(Self::bindings(group_roles, GrantSourceType::Group));
+        }
+
+        if let Some(department_code) = &user_info.department_code {
+            let department_roles = DepartmentRoleRef::find()
+                .select_only()
+                .columns([department_role_ref::Column::DepartmentCode, department_role_ref::Column::RoleCode])
+                .filter(department_role_ref::Column::IsDel.eq(0))
+                .filter(department_role_ref::Column::DepartmentCode.eq(department_code))
+                .filter(Self::org_wrapper(department_role_ref::Column::OrgCode, &user_info.org_code))
+                .into_tuple::<(String, String)>()
+                .all(db)
+                .await?;
+            role_bindings.extend(Self::bindings(department_roles, GrantSourceType::Department));
+        };
+
+        if let Some(position_code) = &user_info.position_code {
+            let position_roles = PositionRoleRef::find()
+                .select_only()
+                .columns([position_role_ref::Column::PositionCode, position_role_ref::Column::RoleCode])
+                .filter(position_role_ref::Column::IsDel.eq(0))
+                .filter(position_role_ref::Column::PositionCode.eq(position_code))
+                .filter(Self::org_wrapper(position_role_ref::Column::OrgCode, &user_info.org_code))
+                .into_tuple::<(String, String)>()
+                .all(db)
+                .await?;
+            role_bindings.extend(Self::bindings(position_roles, GrantSourceType::Position));
+        };
+
+        if let Some(org_code) = &user_info.org_code {
+            let organization_roles = OrganizationRoleRef::find()
+                .select_only()
+                .columns([organization_role_ref::Column::OrgCode, organization_role_ref::Column::RoleCode])
+                .filter(organization_role_ref::Column::IsDel.eq(0))
+                .filter(organization_role_ref::Column::OrgCode.eq(org_code))
+                .into_tuple::<(String, String)>()
+                .all(db)
+                .await?;
+            role_bindings.extend(Self::bindings(organization_roles, GrantSourceType::Organization));
+        };
+
+        Ok(role_bindings)
+    }
+
+    async fn list_roles_by_codes(&self, role_codes: Vec<String>) -> Result<Vec<role::Model>, DbErr> {
+        if role_codes.is_empty() {
+            return Ok(vec![]);
+        }
+        Role::find()
+            .filter(role::Column::IsDel.eq(0))
+            .filter(role::Column::Code.is_in(role_codes))
+            .all(&self.db)
+            .await
+    }
+
+    /// Permissions referenced by id, or by `code`/`uuid`
+    async fn list_permissions_by_keys(&self, ids: Vec<i64>, keys: Vec<String>) -> Result<Vec<permission::Model>, DbErr> {
+        if ids.is_empty() && keys.is_empty() {
+            return Ok(vec![]);
+        }
+        Permission::find()
+            .filter(permission::Column::IsDel.eq(0))
+            .filter(
+                Cond::any()
+                    .add(permission::Column::Id.is_in(ids))
+                    .add(permission::Column::Code.is_in(keys.clone()))
+                    .add(permission::Column::Uuid.is_in(keys)),
+            )
+            .all(&self.db)
+            .await
+    }
+
+    /// `api` permission nodes whose `link_url` names one of the given routes, those without org
+    /// apply to every org, the others only inside their own
+    async fn list_api_permissions(&self, link_urls: Vec<String>, org_code: &Option<String>) -> Result<Vec<permission::Model>, DbErr> {
+        Permission::find()
+            .filter(permission::Column::IsDel.eq(0))
+            .filter(permission::Column::NodeType.eq(PERMISSION_NODE_TYPE_API))
+            .filter(permission::Column::LinkUrl.is_in(link_urls))
+            .filter(Self::org_wrapper(permission::Column::OrgCode, org_code))
+            .all(&self.db)
+            .await
+    }
+
+    async fn get_department(&self, org_code: &str, code: &str) -> Result<Option<department::Model>, DbErr> {
+        Department::find()
+            .filter(department::Column::OrgCode.eq(org_code))
+            .filter(department::Column::Code.eq(code))
+            .filter(department::Column::IsDel.eq(0))
+            .one(&self.db)
+            .await
+    }
+
+    /// Codes of the department whose materialized path is `path` and of every department below it
+    async fn list_department_codes_below(&self, org_code: &str, path: &str) -> Result<Vec<String>, DbErr> {
+        Department::find()
+            .select_only()
+            .column(department::Column::Code)
+            .filter(department::Column::OrgCode.eq(org_code))
+            .filter(path_starts_with(department::Column::Path, path))
+            .filter(department::Column::Code.is_not_null())
+            .filter(department::Column::IsDel.eq(0))
+            .into_tuple::<String>()
+            .all(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,384 @@
+use anyhow::Result;
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::{IntoValueTuple, ValueTuple, ValueType};
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, ColumnType, Condition, DatabaseConnection, DbErr, EntityName,
+    EntityTrait, FromQueryResult, IdenStatic, IntoActiveModel, Iterable, ModelTrait, PaginatorTrait,
+    PrimaryKeyToColumn, PrimaryKeyTrait, QueryFilter, QuerySelect, QueryTrait, TransactionTrait,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::mapper::audit_log_mapper::AuditLogMapper;
+use crate::pojo::audit_log_pojo::AuditAction;
+use crate::util::paged_struct::{PageData, PageInfo, Pageable};
+use crate::util::{audit, rec_sign, tenant, validation::Validate, IntoJsonValue};
+
+/// A table served through the generic CRUD layers: its entity, the pojos exchanged
+/// with the client and the filters a `Condition` translates to
+pub trait CrudResource: Send + Sync + Sized + 'static {
+    type Entity: EntityTrait<Model = Self::Model, ActiveModel = Self::ActiveModel>;
+    type Model: ModelTrait<Entity = Self::Entity>
+        + FromQueryResult
+        + IntoActiveModel<Self::ActiveModel>
+        + DeserializeOwned
+        + Send
+        + Sync;
+    type ActiveModel: ActiveModelTrait<Entity = Self::Entity> + ActiveModelBehavior + Send + Sync;
+    type Vo: FromQueryResult + Serialize + Send + Sync;
+    type Condition: Pageable + DeserializeOwned + Send + Sync;
+    type Dto: IntoJsonValue + Validate + Debug + DeserializeOwned + Send + Sync;
+
+    /// Whether the table uses its `rec_sign` column as an optimistic lock
+    const VERSIONED: bool = false;
+
+    /// Columns of a unique index whose nullable columns let rows repeat in the database,
+    /// e.g. bindings without an organization; a save or update repeating them is refused
+    const UNIQUE_KEY: &'static [&'static str] = &[];
+
+    /// Filters of a list/page query built from the caller's condition, soft deleted rows excluded
+    fn build_query_wrapper(condition: &Self::Condition) -> Condition;
+
+    /// Rows belonging to organization `org_code`, the tenant a request is confined to: by default
+    /// the rows whose `org_code` column matches, `None` for a table shared by all tenants
+    fn tenant_condition(org_code: &str) -> Option<Condition> {
+        Column::<Self>::from_str("org_code")
+            .ok()
+            .map(|column| Condition::all().add(column.eq(org_code)))
+    }
+}
+
+/// Trait defining the interface shared by all CRUD resources
+#[async_trait::async_trait]
+pub trait CrudMapperTrait<R: CrudResource>: Send + Sync {
+    async fn list(&self, condition: R::Condition) -> Result<Vec<R::Vo>, DbErr>;
+    async fn page(&self, condition: R::Condition) -> Result<PageData<R::Vo>, DbErr>;
+    async fn get_by_id(&self, rec_id: i64) -> Result<Option<R::Vo>, DbErr>;
+    async fn save(&self, dto: R::Dto) -> Result<i64, DbErr>;
+    async fn update_by_id(&self, dto: R::Dto) -> Result<u64, DbErr>;
+    async fn delete_by_ids(&self, dto: R::Dto) -> Result<u64, DbErr>;
+    async fn remove_by_ids(&self, dto: R::Dto) -> Result<u64, DbErr>;
+    /// The ids among `rec_ids` of existing rows the current request may touch
+    async fn tenant_rec_ids(&self, rec_ids: Vec<i64>) -> Result<Vec<i64>, DbErr>;
+}
+
+/// Implementation of CrudMapperTrait for any `CrudResource`
+pub struct CrudMapper<R: CrudResource> {
+    pub(crate) db: DatabaseConnection,
+    pub(crate) audit_log: Arc<AuditLogMapper>,
+    resource: PhantomData<R>,
+}
+
+pub(crate) type Column<R> = <<R as CrudResource>::Entity as EntityTrait>::Column;
+
+/// Builds an `ActiveModel` setting only the columns present in `json`
+///
+/// `from_json` deserializes a whole `Model`, so the columns a partial dto leaves out are first
+/// filled with a placeholder of their type and then marked `NotSet` again.
+pub(crate) fn active_model_from_json<A>(json: Value) -> Result<A, DbErr>
+where
+    A: ActiveModelTrait,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A> + DeserializeOwned,
+{
+    let Value::Object(mut obj) = json else {
+        return A::from_json(json);
+    };
+    let mut missing = Vec::new();
+    for col in <A::Entity as EntityTrait>::Column::iter() {
+        let key = col.as_str();
+        if obj.contains_key(key) {
+            continue;
+        }
+        let def = col.def();
+        let placeholder = if def.is_null() {
+            Value::Null
+        } else {
+            match def.get_column_type() {
+                ColumnType::TinyInteger | ColumnType::SmallInteger | ColumnType::Integer | ColumnType::BigInteger => {
+                    Value::from(0)
+                }
+                ColumnType::Char(_) | ColumnType::String(_) | ColumnType::Text => Value::from(""),
+                ColumnType::DateTime | ColumnType::Timestamp => Value::from("1970-01-01T00:00:00"),
+                ColumnType::Date => Value::from("1970-01-01"),
+                _ => Value::Null,
+            }
+        };
+        obj.insert(key.to_string(), placeholder);
+        missing.push(col);
+    }
+    let mut actmod = A::from_json(Value::Object(obj))?;
+    for col in missing {
+        actmod.not_set(col);
+    }
+    Ok(actmod)
+}
+
+impl<R: CrudResource> CrudMapper<R> {
+    pub fn new(db: DatabaseConnection, audit_log: Arc<AuditLogMapper>) -> Self {
+        Self { db, audit_log, resource: PhantomData }
+    }
+
+    /// A mapper on `db` recording into `audit_log`, ready to be shared by the services
+    pub fn shared(db: &DatabaseConnection, audit_log: &Arc<AuditLogMapper>) -> Arc<Self> {
+        Arc::new(Self::new(db.clone(), audit_log.clone()))
+    }
+
+    /// `rec_id` of an update dto
+    pub fn rec_id(dto: &R::Dto) -> Option<i64> {
+        dto.into_json_with_snake_key().get("rec_id")?.as_i64()
+    }
+
+    /// `rec_ids` of a delete dto, empty if there are none
+    pub fn rec_ids(dto: &R::Dto) -> Vec<i64> {
+        dto.into_json_with_snake_key()
+            .get("rec_ids")
+            .and_then(Value::as_array)
+            .map(|ids| ids.iter().filter_map(Value::as_i64).collect())
+            .unwrap_or_default()
+    }
+
+    /// `rec_sign` the caller read before sending an update dto
+    pub fn rec_sign(dto: &R::Dto) -> Option<String> {
+        dto.into_json_with_snake_key().get("rec_sign")?.as_str().map(str::to_string)
+    }
+
+    /// `org_code` a dto writes, if any
+    pub fn org_code(dto: &R::Dto) -> Option<String> {
+        dto.into_json_with_snake_key().get("org_code")?.as_str().map(str::to_string)
+    }
+
+    fn table_name() -> String {
+        R::Entity::default().table_name().to_string()
+    }
+
+    pub(crate) fn column(name: &str) -> Option<Column<R>> {
+        Column::<R>::from_str(name).ok()
+    }
+
+    pub(crate) fn id_column() -> Result<Column<R>, DbErr> {
+        <R::Entity as EntityTrait>::PrimaryKey::iter()
+            .next()
+            .map(PrimaryKeyToColumn::into_column)
+            .ok_or_else(|| DbErr::Custom(format!("{} has no primary key", Self::table_name())))
+    }
+
+    /// Rows the current request may touch, every row outside of a tenant
+    pub(crate) fn tenant_filter() -> Condition {
+        tenant::current()
+            .and_then(|org_code| R::tenant_condition(&org_code))
+            .unwrap_or_else(Condition::all)
+    }
+
+    /// Rows holding the `UNIQUE_KEY` values `value_of` gives, a missing or null value matching null
+    fn same_unique_key(value_of: impl Fn(Column<R>) -> Option<sea_orm::Value>) -> Option<Condition> {
+        if R::UNIQUE_KEY.is_empty() {
+            return None;
+        }
+        let mut condition = Condition::all();
+        for name in R::UNIQUE_KEY {
+            let column = Self::column(name)?;
+            condition = condition.add(match value_of(column) {
+                Some(value) if value != value.as_null() => column.eq(value),
+                _ => column.is_null(),
+            });
+        }
+        Some(condition)
+    }
+
+    fn convert_page_data(condition: &R::Condition, list: Vec<R::Vo>, total: u64) -> PageData<R::Vo> {
+        let page_info = PageInfo::from(
+            condition.get_page().unwrap_or(1),
+            condition.get_size().unwrap_or(20),
+            total,
+        );
+        PageData::new(page_info, list)
+    }
+}
+
+/// Every mutation commits in one transaction with the `audit_log` row recording it
+#[async_trait::async_trait]
+impl<R: CrudResource> CrudMapperTrait<R> for CrudMapper<R> {
+    async fn list(&self, condition: R::Condition) -> Result<Vec<R::Vo>, DbErr> {
+        R::Entity::find()
+            .filter(R::build_query_wrapper(&condition))
+            .filter(Self::tenant_filter())
+            .apply_if(condition.get_size(), QuerySelect::limit)
+            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
+            .into_model::<R::Vo>()
+            .all(&self.db)
+            .await
+    }
+
+    async fn page(&self, condition: R::Condition) -> Result<PageData<R::Vo>, DbErr> {
+        let list = R::Entity::find()
+            .filter(R::build_query_wrapper(&condition))
+            .filter(Self::tenant_filter())
+            .apply_if(condition.get_size(), QuerySelect::limit)
+            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
+            .into_model::<R::Vo>()
+            .all(&self.db)
+            .await?;
+        let total = R::Entity::find()
+            .filter(R::build_query_wrapper(&condition))
+            .filter(Self::tenant_filter())
+            .count(&self.db)
+            .await?;
+        Ok(Self::convert_page_data(&condition, list, total))
+    }
+
+    async fn get_by_id(&self, rec_id: i64) -> Result<Option<R::Vo>, DbErr> {
+        R::Entity::find()
+            .filter(Self::id_column()?.eq(rec_id))
+            .filter(Self::tenant_filter())
+            .into_model::<R::Vo>()
+            .one(&self.db)
+            .await
+    }
+
+    async fn save(&self, dto: R::Dto) -> Result<i64, DbErr> {
+        let dto_json = dto.into_json_with_snake_key();
+        info!("{}_json is {:?}", Self::table_name(), dto_json);
+        let mut actmod = active_model_from_json::<R::ActiveModel>(dto_json)?;
+        // a key the database does not generate, e.g. the id WeChat assigned, is the caller's `rec_id`
+        if !<<R::Entity as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::auto_increment() {
+            if let Some(rec_id) = Self::rec_id(&dto) {
+                actmod.set(Self::id_column()?, rec_id.into());
+            }
+        }
+        audit::stamp_insert(&mut actmod);
+        // a row created within a tenant belongs to it
+        if let (Some(org_code_column), Some(org_code)) = (Self::column("org_code"), tenant::current()) {
+            actmod.set(org_code_column, org_code.into());
+        }
+        if R::VERSIONED {
+            if let Some(rec_sign_column) = Self::column("rec_sign") {
+                actmod.set(rec_sign_column, rec_sign::generate().into());
+            }
+        }
+        let txn = self.db.begin().await?;
+        if let Some(same_key) = Self::same_unique_key(|column| actmod.get(column).into_value()) {
+            if R::Entity::find().filter(same_key).count(&txn).await? > 0 {
+                return Err(DbErr::RecordNotInserted);
+            }
+        }
+        let inserted_result = R::Entity::insert(actmod).exec(&txn).await?;
+        let rec_id = match inserted_result.last_insert_id.into_value_tuple() {
+            ValueTuple::One(value) => <i64 as ValueType>::try_from(value)
+                .map_err(|err| DbErr::Custom(format!("{} id is not an integer: {}", Self::table_name(), err)))?,
+            _ => return Err(DbErr::Custom(format!("{} has a composite primary key", Self::table_name()))),
+        };
+        self.audit_log.record::<R::Entity, _>(&txn, AuditAction::Save, vec![rec_id], vec![]).await?;
+        txn.commit().await?;
+        Ok(rec_id)
+    }
+
+    async fn update_by_id(&self, dto: R::Dto) -> Result<u64, DbErr> {
+        info!("{}_json is {:?}", Self::table_name(), dto);
+        let Some(rec_id) = Self::rec_id(&dto) else {
+            return Ok(0);
+        };
+        let mut actmod = active_model_from_json::<R::ActiveModel>(dto.into_json_with_snake_key())?;
+        audit::stamp_update(&mut actmod);
+        let mut update = R::Entity::update_many()
+            .filter(Self::id_column()?.eq(rec_id))
+            .filter(Self::tenant_filter());
+        if R::VERSIONED {
+            if let Some(rec_sign_column) = Self::column("rec_sign") {
+                actmod.set(rec_sign_column, rec_sign::generate().into());
+                let expected = Self::rec_sign(&dto).unwrap_or_default();
+                update = update.filter(rec_sign::matches(rec_sign_column, &expected));
+            }
+        }
+        let txn = self.db.begin().await?;
+        if !R::UNIQUE_KEY.is_empty() {
+            let id_column = Self::id_column()?;
+            let current = R::Entity::find().filter(id_column.eq(rec_id)).one(&txn).await?;
+            let same_key = current.and_then(|current| {
+                Self::same_unique_key(|column| actmod.get(column).into_value().or_else(|| Some(current.get(column))))
+            });
+            if let Some(same_key) = same_key {
+                if R::Entity::find().filter(same_key).filter(id_column.ne(rec_id)).count(&txn).await? > 0 {
+                    return Err(DbErr::RecordNotUpdated);
+                }
+            }
+        }
+        let before = self.audit_log.snapshot::<R::Entity, _>(&txn, &[rec_id]).await?;
+        let update_result = update.set(actmod).exec(&txn).await?;
+        if update_result.rows_affected > 0 {
+            self.audit_log.record::<R::Entity, _>(&txn, AuditAction::Update, vec![rec_id], before).await?;
+        }
+        txn.commit().await?;
+        Ok(update_result.rows_affected)
+    }
+
+    async fn delete_by_ids(&self, dto: R::Dto) -> Result<u64, DbErr> {
+        info!("{}_json is {:?}", Self::table_name(), dto);
+        let rec_ids = self.tenant_rec_ids(Self::rec_ids(&dto)).await?;
+        if rec_ids.is_empty() {
+            return Ok(0);
+        }
+        let is_del_column = Self::column("is_del")
+            .ok_or_else(|| DbErr::Custom(format!("{} has no is_del column", Self::table_name())))?;
+        let mut update = R::Entity::update_many()
+            .col_expr(is_del_column, Expr::value(-1))
+            .filter(Self::id_column()?.is_in(rec_ids.clone()));
+        if let Some(update_by_column) = Self::column("update_by") {
+            update = update.col_expr(update_by_column, Expr::value(audit::principal_id()));
+        }
+        if let Some(update_time_column) = Self::column("update_time") {
+            update = update.col_expr(update_time_column, Expr::value(audit::now()));
+        }
+        if R::VERSIONED {
+            if let Some(rec_sign_column) = Self::column("rec_sign") {
+                update = update.col_expr(rec_sign_column, Expr::value(rec_sign::generate()));
+            }
+        }
+        let txn = self.db.begin().await?;
+        let before = self.audit_log.snapshot::<R::Entity, _>(&txn, &rec_ids).await?;
+        let update_result = update.exec(&txn).await?;
+        if update_result.rows_affected > 0 {
+            self.audit_log.record::<R::Entity, _>(&txn, AuditAction::Delete, rec_ids, before).await?;
+        }
+        txn.commit().await?;
+        Ok(update_result.rows_affected)
+    }
+
+    async fn remove_by_ids(&self, dto: R::Dto) -> Result<u64, DbErr> {
+        info!("{}_json is {:?}", Self::table_name(), dto);
+        let rec_ids = self.tenant_rec_ids(Self::rec_ids(&dto)).await?;
+        if rec_ids.is_empty() {
+            return Ok(0);
+        }
+        let txn = self.db.begin().await?;
+        let before = self.audit_log.snapshot::<R::Entity, _>(&txn, &rec_ids).await?;
+        let delete_result = R::Entity::delete_many()
+            .filter(Self::id_column()?.is_in(rec_ids.clone()))
+            .exec(&txn)
+            .await?;
+        if delete_result.rows_affected > 0 {
+            self.audit_log.record::<R::Entity, _>(&txn, AuditAction::Remove, rec_ids, before).await?;
+        }
+        txn.commit().await?;
+        Ok(delete_result.rows_affected)
+    }
+
+    async fn tenant_rec_ids(&self, rec_ids: Vec<i64>) -> Result<Vec<i64>, DbErr> {
+        if rec_ids.is_empty() {
+            return Ok(rec_ids);
+        }
+        let id_column = Self::id_column()?;
+        R::Entity::find()
+            .select_only()
+            .column(id_column)
+            .filter(id_column.is_in(rec_ids))
+            .filter(Self::tenant_filter())
+            .into_tuple::<i64>()
+            .all(&self.db)
+            .await
+    }
+}
@@ -1,48 +1,26 @@
-use anyhow::Result;
-use sea_orm::prelude::Expr;
 use sea_orm::sea_query::Cond;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect,
-    QueryTrait,
-};
-use std::sync::Arc;
-use tracing::info;
-use once_cell::sync::OnceCell;
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
 
 use crate::entities::{prelude::*, *};
-use crate::util::paged_struct::{PageData, PageInfo, Pageable};
-use crate::util::IntoJsonValue;
-use crate::{pojo::department_pojo::*, AppState};
-use sea_orm::Condition;
+use crate::mapper::crud_mapper::{CrudMapper, CrudMapperTrait, CrudResource};
+use crate::mapper::tree_mapper::{TreeMapperTrait, TreeResource};
+use crate::pojo::department_pojo::*;
+use crate::pojo::position_pojo::PositionVo;
 
-/// Trait defining the interface for department-related database operations
-#[async_trait::async_trait]
-pub trait DepartmentMapperTrait {
-    async fn list(&self, condition: DepartmentCondition) -> Result<Vec<DepartmentVo>, DbErr>;
-    async fn page(&self, condition: DepartmentCondition) -> Result<PageData<DepartmentVo>, DbErr>;
-    async fn get_by_id(&self, rec_id: i64) -> Result<Option<DepartmentVo>, DbErr>;
-    async fn save(&self, department_dto: DepartmentDto) -> Result<i64, DbErr>;
-    async fn update_by_id(&self, department_dto: DepartmentDto) -> Result<u64, DbErr>;
-    async fn delete_by_ids(&self, department_dto: DepartmentDto) -> Result<u64, DbErr>;
-    async fn remove_by_ids(&self, department_dto: DepartmentDto) -> Result<u64, DbErr>;
-}
-
-/// Implementation of DepartmentMapperTrait
-pub struct DepartmentMapper {
-    state: Arc<AppState>,
-}
+/// The department table as a CRUD resource
+pub struct DepartmentResource;
 
-impl DepartmentMapper {
-    pub fn new(state: Arc<AppState>) -> Self {
-        Self { state }
-    }
+impl CrudResource for DepartmentResource {
+    type Entity = Department;
+    type Model = department::Model;
+    type ActiveModel = department::ActiveModel;
+    type Vo = DepartmentVo;
+    type Condition = DepartmentCondition;
+    type Dto = DepartmentDto;
 
-    pub fn get_instance(state: Arc<AppState>) -> &'static DepartmentMapper {
-        static INSTANCE: OnceCell<DepartmentMapper> = OnceCell::new();
-        INSTANCE.get_or_init(|| DepartmentMapper::new(state))
-    }
+    const VERSIONED: bool = true;
 
-    fn build_query_wrapper(&self, condition: &DepartmentCondition) -> Condition {
+    fn build_query_wrapper(condition: &DepartmentCondition) -> Condition {
         let mut query_wrapper = Cond::all().add(department::Column::IsDel.eq(0));
         if let Some(ids) = &condition.ids {
             query_wrapper = query_wrapper.add(department::Column::Id.is_in(ids.clone()));
@@ -61,98 +39,62 @@ impl DepartmentMapper {
         };
         query_wrapper
     }
-
-    async fn convert_page_data(
-        &self,
-        condition: &DepartmentCondition,
-        list: Vec<DepartmentVo>,
-        total: u64,
-    ) -> Result<PageData<DepartmentVo>, DbErr> {
-        let page_info = PageInfo::from(
-            condition.get_page().unwrap(),
-            condition.get_size().unwrap(),
-            total,
-        );
-        let page_data = PageData::new(page_info, list);
-        Ok(page_data)
-    }
 }
 
-#[async_trait::async_trait]
-impl DepartmentMapperTrait for DepartmentMapper {
-    async fn list(&self, condition: DepartmentCondition) -> Result<Vec<DepartmentVo>, DbErr> {
-        let department = Department::find()
-            .filter(self.build_query_wrapper(&condition))
-            .apply_if(condition.get_size(), QuerySelect::limit)
-            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
-            .into_model::<DepartmentVo>()
-            .all(&self.state.mysql_pool)
-            .await?;
+/// Mapper of the department table, see `CrudMapperTrait` for the CRUD operations
+pub type DepartmentMapper = CrudMapper<DepartmentResource>;
 
-        Ok(department)
-    }
+impl TreeResource for DepartmentResource {
+    const CODE: department::Column = department::Column::Code;
+    const PARENT_CODE: department::Column = department::Column::ParentCode;
+    const PATH: department::Column = department::Column::Path;
+    // a department hangs below a department of its own organization only
+    const SCOPE: Option<department::Column> = Some(department::Column::OrgCode);
+    const LABEL: &'static str = "部门";
+}
 
-    async fn page(&self, condition: DepartmentCondition) -> Result<PageData<DepartmentVo>, DbErr> {
-        let department = Department::find()
-            .filter(self.build_query_wrapper(&condition))
-            .apply_if(condition.get_size(), QuerySelect::limit)
-            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
-            .into_model::<DepartmentVo>()
-            .all(&self.state.mysql_pool)
-            .await?;
-        let total = Department::find()
-            .filter(self.build_query_wrapper(&condition))
-            .count(&self.state.mysql_pool)
-            .await?;
-        self.convert_page_data(&condition, department, total).await
-    }
+/// Trait defining the department queries beyond CRUD
+#[async_trait::async_trait]
+pub trait DepartmentMapperTrait: CrudMapperTrait<DepartmentResource> + TreeMapperTrait<DepartmentResource> {
+    async fn list_by_org(&self, org_code: &str) -> Result<Vec<DepartmentVo>, DbErr>;
+    async fn list_positions_by_org(&self, org_code: &str) -> Result<Vec<PositionVo>, DbErr>;
+    async fn count_members_by_department(&self, org_code: &str) -> Result<Vec<(String, i64)>, DbErr>;
+}
 
-    async fn get_by_id(&self, rec_id: i64) -> Result<Option<DepartmentVo>, DbErr> {
-        let department_opt = Department::find_by_id(rec_id)
+#[async_trait::async_trait]
+impl DepartmentMapperTrait for DepartmentMapper {
+    async fn list_by_org(&self, org_code: &str) -> Result<Vec<DepartmentVo>, DbErr> {
+        Department::find()
+            .filter(department::Column::OrgCode.eq(org_code))
+            .filter(department::Column::IsDel.eq(0))
+            .order_by_asc(department::Column::Id)
             .into_model::<DepartmentVo>()
-            .one(&self.state.mysql_pool)
-            .await?;
-        Ok(department_opt)
-    }
-
-    async fn save(&self, department_dto: DepartmentDto) -> Result<i64, DbErr> {
-        let department_dtoc = department_dto.into_json_with_snake_key();
-        info!("department_json is {:?}", department_dtoc);
-        let mut department_actmod = department::ActiveModel::from_json(department_dtoc)?;
-        department_actmod.set(department::Column::CreateBy, sea_orm::Value::BigInt(Some(0)));
-        department_actmod.set(department::Column::UpdateBy, sea_orm::Value::BigInt(Some(0)));
-        let inserted_result = Department::insert(department_actmod).exec(&self.state.mysql_pool).await?;
-        Ok(inserted_result.last_insert_id)
+            .all(&self.db)
+            .await
     }
 
-    async fn update_by_id(&self, department_dto: DepartmentDto) -> Result<u64, DbErr> {
-        let department_dto_json = department_dto.into_json_with_snake_key();
-        info!("department_json is {:?}", department_dto);
-        let department_actmod = department::ActiveModel::from_json(department_dto_json)?;
-        let update_result = Department::update_many()
-            .set(department_actmod)
-            .filter(department::Column::Id.eq(department_dto.rec_id))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
+    async fn list_positions_by_org(&self, org_code: &str) -> Result<Vec<PositionVo>, DbErr> {
+        Position::find()
+            .filter(position::Column::OrgCode.eq(org_code))
+            .filter(position::Column::IsDel.eq(0))
+            .order_by_asc(position::Column::Id)
+            .into_model::<PositionVo>()
+            .all(&self.db)
+            .await
     }
 
-    async fn delete_by_ids(&self, department_dto: DepartmentDto) -> Result<u64, DbErr> {
-        info!("department_json is {:?}", department_dto);
-        let update_result = Department::update_many()
-            .col_expr(department::Column::IsDel, Expr::value(-1))
-            .filter(department::Column::Id.is_in(department_dto.rec_ids.unwrap()))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
+    /// `(department_code, headcount)` of every department of the organization having members
+    async fn count_members_by_department(&self, org_code: &str) -> Result<Vec<(String, i64)>, DbErr> {
+        UserInfo::find()
+            .select_only()
+            .column(user_info::Column::DepartmentCode)
+            .column_as(user_info::Column::Id.count(), "headcount")
+            .filter(user_info::Column::OrgCode.eq(org_code))
+            .filter(user_info::Column::DepartmentCode.is_not_null())
+            .filter(user_info::Column::IsDel.eq(0))
+            .group_by(user_info::Column::DepartmentCode)
+            .into_tuple::<(String, i64)>()
+            .all(&self.db)
+            .await
     }
-
-    async fn remove_by_ids(&self, department_dto: DepartmentDto) -> Result<u64, DbErr> {
-        info!("department_json is {:?}", department_dto);
-        let update_result = Department::delete_many()
-            .filter(department::Column::Id.is_in(department_dto.rec_ids.unwrap()))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
-    }
-} 
\ No newline at end of file
+}
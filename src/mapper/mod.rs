@@ -1,3 +1,5 @@
+pub mod crud_mapper;
+pub mod tree_mapper;
 pub mod organization_mapper;
 pub mod role_mapper;
 pub mod permission_mapper;
@@ -14,6 +16,12 @@ pub mod position_role_ref_mapper;
 pub mod department_role_ref_mapper;
 pub mod group_role_ref_mapper;
 pub mod organization_role_ref_mapper;
+pub mod refresh_token_mapper;
+pub mod authz_mapper;
+pub mod token_deny_list_mapper;
+pub mod audit_log_mapper;
+pub use crud_mapper::*;
+pub use tree_mapper::*;
 pub use organization_mapper::*;
 pub use role_mapper::*;
 pub use permission_mapper::*;
@@ -30,3 +38,64 @@ pub use position_role_ref_mapper::*;
 pub use department_role_ref_mapper::*;
 pub use group_role_ref_mapper::*;
 pub use organization_role_ref_mapper::*;
+pub use refresh_token_mapper::*;
+pub use authz_mapper::*;
+pub use token_deny_list_mapper::*;
+pub use audit_log_mapper::*;
+
+use std::sync::Arc;
+
+use sea_orm::DatabaseConnection;
+
+/// Every mapper of the application, built once per `AppState` on top of its connection.
+/// The services only see the traits, a test may swap any of them for a fake.
+pub struct Mappers {
+    pub audit_log: Arc<dyn AuditLogMapperTrait>,
+    pub authz: Arc<dyn AuthzMapperTrait>,
+    pub refresh_token: Arc<dyn RefreshTokenMapperTrait>,
+    pub token_deny_list: Arc<dyn TokenDenyListMapperTrait>,
+    pub organization: Arc<dyn OrganizationMapperTrait>,
+    pub role: Arc<dyn RoleMapperTrait>,
+    pub permission: Arc<dyn PermissionMapperTrait>,
+    pub position: Arc<dyn CrudMapperTrait<PositionResource>>,
+    pub department: Arc<dyn DepartmentMapperTrait>,
+    pub group: Arc<dyn CrudMapperTrait<GroupResource>>,
+    pub system_config: Arc<dyn CrudMapperTrait<SystemConfigResource>>,
+    pub user_wechat_info: Arc<dyn CrudMapperTrait<UserWechatInfoResource>>,
+    pub user: Arc<dyn UserMapperTrait>,
+    pub user_info: Arc<dyn UserInfoMapperTrait>,
+    pub user_role_ref: Arc<dyn CrudMapperTrait<UserRoleRefResource>>,
+    pub user_group_ref: Arc<dyn CrudMapperTrait<UserGroupRefResource>>,
+    pub position_role_ref: Arc<dyn CrudMapperTrait<PositionRoleRefResource>>,
+    pub department_role_ref: Arc<dyn CrudMapperTrait<DepartmentRoleRefResource>>,
+    pub group_role_ref: Arc<dyn CrudMapperTrait<GroupRoleRefResource>>,
+    pub organization_role_ref: Arc<dyn CrudMapperTrait<OrganizationRoleRefResource>>,
+}
+
+impl Mappers {
+    pub fn new(db: &DatabaseConnection) -> Self {
+        let audit_log = Arc::new(AuditLogMapper::new(db.clone()));
+        Self {
+            authz: Arc::new(AuthzMapper::new(db.clone())),
+            refresh_token: Arc::new(RefreshTokenMapper::new(db.clone())),
+            token_deny_list: Arc::new(TokenDenyListMapper::new(db.clone())),
+            organization: OrganizationMapper::shared(db, &audit_log),
+            role: RoleMapper::shared(db, &audit_log),
+            permission: PermissionMapper::shared(db, &audit_log),
+            position: PositionMapper::shared(db, &audit_log),
+            department: DepartmentMapper::shared(db, &audit_log),
+            group: GroupMapper::shared(db, &audit_log),
+            system_config: SystemConfigMapper::shared(db, &audit_log),
+            user_wechat_info: UserWechatInfoMapper::shared(db, &audit_log),
+            user: UserMapper::shared(db, &audit_log),
+            user_info: UserInfoMapper::shared(db, &audit_log),
+            user_role_ref: UserRoleRefMapper::shared(db, &audit_log),
+            user_group_ref: UserGroupRefMapper::shared(db, &audit_log),
+            position_role_ref: PositionRoleRefMapper::shared(db, &audit_log),
+            department_role_ref: DepartmentRoleRefMapper::shared(db, &audit_log),
+            group_role_ref: GroupRoleRefMapper::shared(db, &audit_log),
+            organization_role_ref: OrganizationRoleRefMapper::shared(db, &audit_log),
+            audit_log,
+        }
+    }
+}
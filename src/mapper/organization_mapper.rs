@@ -1,48 +1,25 @@
-use anyhow::Result;
-use sea_orm::prelude::Expr;
 use sea_orm::sea_query::Cond;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect,
-    QueryTrait,
-};
-use std::sync::Arc;
-use tracing::info;
-use once_cell::sync::OnceCell;
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder};
 
 use crate::entities::{prelude::*, *};
-use crate::util::paged_struct::{PageData, PageInfo, Pageable};
-use crate::util::IntoJsonValue;
-use crate::{pojo::organization_pojo::*, AppState};
-use sea_orm::Condition;
+use crate::mapper::crud_mapper::{CrudMapper, CrudMapperTrait, CrudResource};
+use crate::mapper::tree_mapper::{path_contains_code, path_starts_with, TreeMapperTrait, TreeResource};
+use crate::pojo::organization_pojo::*;
 
-/// Trait defining the interface for organization-related database operations
-#[async_trait::async_trait]
-pub trait OrganizationMapperTrait {
-    async fn list(&self, condition: OrganizationCondition) -> Result<Vec<OrganizationVo>, DbErr>;
-    async fn page(&self, condition: OrganizationCondition) -> Result<PageData<OrganizationVo>, DbErr>;
-    async fn get_by_id(&self, rec_id: i64) -> Result<Option<OrganizationVo>, DbErr>;
-    async fn save(&self, organization_dto: OrganizationDto) -> Result<i64, DbErr>;
-    async fn update_by_id(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr>;
-    async fn delete_by_ids(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr>;
-    async fn remove_by_ids(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr>;
-}
-
-/// Implementation of OrganizationMapperTrait
-pub struct OrganizationMapper {
-    state: Arc<AppState>,
-}
+/// The organization table as a CRUD resource
+pub struct OrganizationResource;
 
-impl OrganizationMapper {
-    pub fn new(state: Arc<AppState>) -> Self {
-        Self { state }
-    }
+impl CrudResource for OrganizationResource {
+    type Entity = Organization;
+    type Model = organization::Model;
+    type ActiveModel = organization::ActiveModel;
+    type Vo = OrganizationVo;
+    type Condition = OrganizationCondition;
+    type Dto = OrganizationDto;
 
-    pub fn get_instance(state: Arc<AppState>) -> &'static OrganizationMapper {
-        static INSTANCE: OnceCell<OrganizationMapper> = OnceCell::new();
-        INSTANCE.get_or_init(|| OrganizationMapper::new(state))
-    }
+    const VERSIONED: bool = true;
 
-    fn build_query_wrapper(&self, condition: &OrganizationCondition) -> Condition {
+    fn build_query_wrapper(condition: &OrganizationCondition) -> Condition {
         let mut query_wrapper = Cond::all().add(organization::Column::IsDel.eq(0));
         if let Some(ids) = &condition.ids {
             query_wrapper = query_wrapper.add(organization::Column::Id.is_in(ids.clone()));
@@ -77,97 +54,70 @@ impl OrganizationMapper {
         query_wrapper
     }
 
-    async fn convert_page_data(
-        &self,
-        condition: &OrganizationCondition,
-        list: Vec<OrganizationVo>,
-        total: u64,
-    ) -> Result<PageData<OrganizationVo>, DbErr> {
-        let page_info = PageInfo::from(
-            condition.get_page().unwrap(),
-            condition.get_size().unwrap(),
-            total,
-        );
-        let page_data = PageData::new(page_info, list);
-        Ok(page_data)
+    /// The organization itself and its subsidiaries, the organizations are the tenants
+    fn tenant_condition(org_code: &str) -> Option<Condition> {
+        Some(Cond::all().add(path_contains_code(organization::Column::Path, org_code)))
     }
 }
 
+/// Mapper of the organization table, see `CrudMapperTrait` for the CRUD operations
+pub type OrganizationMapper = CrudMapper<OrganizationResource>;
+
+impl TreeResource for OrganizationResource {
+    const CODE: organization::Column = organization::Column::Code;
+    const PARENT_CODE: organization::Column = organization::Column::ParentCode;
+    const PATH: organization::Column = organization::Column::Path;
+    const LABEL: &'static str = "组织";
+    const TENANT_ROOTED: bool = true;
+}
+
+/// Trait defining the organization hierarchy queries beyond CRUD
+#[async_trait::async_trait]
+pub trait OrganizationMapperTrait: CrudMapperTrait<OrganizationResource> + TreeMapperTrait<OrganizationResource> {
+    async fn list_by_codes(&self, codes: Vec<String>) -> Result<Vec<OrganizationVo>, DbErr>;
+    async fn list_descendants(&self, path: &str) -> Result<Vec<OrganizationVo>, DbErr>;
+    async fn list_tree_nodes(&self, condition: OrganizationTreeCondition) -> Result<Vec<OrganizationVo>, DbErr>;
+}
+
 #[async_trait::async_trait]
 impl OrganizationMapperTrait for OrganizationMapper {
-    async fn list(&self, condition: OrganizationCondition) -> Result<Vec<OrganizationVo>, DbErr> {
-        let organization = Organization::find()
-            .filter(self.build_query_wrapper(&condition))
-            .apply_if(condition.get_size(), QuerySelect::limit)
-            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
+    async fn list_by_codes(&self, codes: Vec<String>) -> Result<Vec<OrganizationVo>, DbErr> {
+        Organization::find()
+            .filter(organization::Column::Code.is_in(codes))
+            .filter(organization::Column::IsDel.eq(0))
+            .filter(Self::tenant_filter())
             .into_model::<OrganizationVo>()
-            .all(&self.state.mysql_pool)
-            .await?;
-
-        Ok(organization)
+            .all(&self.db)
+            .await
     }
 
-    async fn page(&self, condition: OrganizationCondition) -> Result<PageData<OrganizationVo>, DbErr> {
-        let organization = Organization::find()
-            .filter(self.build_query_wrapper(&condition))
-            .apply_if(condition.get_size(), QuerySelect::limit)
-            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
+    /// Organizations strictly below the one whose materialized path is `path`, parents before their children
+    async fn list_descendants(&self, path: &str) -> Result<Vec<OrganizationVo>, DbErr> {
+        Organization::find()
+            .filter(path_starts_with(organization::Column::Path, path))
+            .filter(organization::Column::Path.ne(path))
+            .filter(organization::Column::IsDel.eq(0))
+            .filter(Self::tenant_filter())
+            .order_by_asc(organization::Column::Path)
             .into_model::<OrganizationVo>()
-            .all(&self.state.mysql_pool)
-            .await?;
-        let total = Organization::find()
-            .filter(self.build_query_wrapper(&condition))
-            .count(&self.state.mysql_pool)
-            .await?;
-        self.convert_page_data(&condition, organization, total).await
+            .all(&self.db)
+            .await
     }
 
-    async fn get_by_id(&self, rec_id: i64) -> Result<Option<OrganizationVo>, DbErr> {
-        let organization_opt = Organization::find_by_id(rec_id)
+    async fn list_tree_nodes(&self, condition: OrganizationTreeCondition) -> Result<Vec<OrganizationVo>, DbErr> {
+        let mut query_wrapper = Cond::all().add(organization::Column::IsDel.eq(0));
+        if let Some(r#type) = &condition.r#type {
+            query_wrapper = query_wrapper.add(organization::Column::Type.eq(r#type));
+        };
+        if let Some(status) = &condition.status {
+            query_wrapper = query_wrapper.add(organization::Column::Status.eq(status));
+        };
+        Organization::find()
+            .filter(query_wrapper)
+            .filter(Self::tenant_filter())
+            .order_by_asc(organization::Column::Id)
             .into_model::<OrganizationVo>()
-            .one(&self.state.mysql_pool)
-            .await?;
-        Ok(organization_opt)
-    }
-
-    async fn save(&self, organization_dto: OrganizationDto) -> Result<i64, DbErr> {
-        let organization_dtoc = organization_dto.into_json_with_snake_key();
-        info!("organization_json is {:?}", organization_dtoc);
-        let mut organization_actmod = organization::ActiveModel::from_json(organization_dtoc)?;
-        organization_actmod.set(organization::Column::CreateBy, sea_orm::Value::BigInt(Some(0)));
-        organization_actmod.set(organization::Column::UpdateBy, sea_orm::Value::BigInt(Some(0)));
-        let inserted_result = Organization::insert(organization_actmod).exec(&self.state.mysql_pool).await?;
-        Ok(inserted_result.last_insert_id)
+            .all(&self.db)
+            .await
     }
-
-    async fn update_by_id(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr> {
-        let organization_dto_json = organization_dto.into_json_with_snake_key();
-        info!("organization_json is {:?}", organization_dto);
-        let organization_actmod = organization::ActiveModel::from_json(organization_dto_json)?;
-        let update_result = Organization::update_many()
-            .set(organization_actmod)
-            .filter(organization::Column::Id.eq(organization_dto.rec_id))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
-    }
-
-    async fn delete_by_ids(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr> {
-        info!("organization_json is {:?}", organization_dto);
-        let update_result = Organization::update_many()
-            .col_expr(organization::Column::IsDel, Expr::value(-1))
-            .filter(organization::Column::Id.is_in(organization_dto.rec_ids.unwrap()))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
-    }
-
-    async fn remove_by_ids(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr> {
-        info!("organization_json is {:?}", organization_dto);
-        let update_result = Organization::delete_many()
-            .filter(organization::Column::Id.is_in(organization_dto.rec_ids.unwrap()))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
-    }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use sea_orm::prelude::Expr;
+use sea_orm::{ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::entities::{prelude::*, *};
+
+/// Trait defining the interface for refresh token-related database operations
+#[async_trait::async_trait]
+pub trait RefreshTokenMapperTrait: Send + Sync {
+    async fn save(&self, refresh_token: refresh_token::ActiveModel) -> Result<i64, DbErr>;
+    async fn get_by_token_hash(&self, token_hash: &str) -> Result<Option<refresh_token::Model>, DbErr>;
+    async fn mark_used(&self, rec_id: i64) -> Result<u64, DbErr>;
+    async fn revoke_family(&self, family_id: &str) -> Result<u64, DbErr>;
+    async fn list_active(&self, user_id: i64) -> Result<Vec<refresh_token::Model>, DbErr>;
+}
+
+/// Implementation of RefreshTokenMapperTrait
+pub struct RefreshTokenMapper {
+    db: DatabaseConnection,
+}
+
+impl RefreshTokenMapper {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+
+    fn now() -> NaiveDateTime {
+        chrono::Local::now().naive_local()
+    }
+}
+
+#[async_trait::async_trait]
+impl RefreshTokenMapperTrait for RefreshTokenMapper {
+    async fn save(&self, mut refresh_token: refresh_token::ActiveModel) -> Result<i64, DbErr> {
+        refresh_token.create_time = Set(Some(Self::now()));
+        refresh_token.update_time = Set(Some(Self::now()));
+        let inserted_result = RefreshToken::insert(refresh_token)
+            .exec(&self.db)
+            .await?;
+        Ok(inserted_result.last_insert_id)
+    }
+
+    async fn get_by_token_hash(&self, token_hash: &str) -> Result<Option<refresh_token::Model>, DbErr> {
+        RefreshToken::find()
+            .filter(refresh_token::Column::TokenHash.eq(token_hash))
+            .one(&self.db)
+            .await
+    }
+
+    /// Marks a token as used, only succeeds (returns 1) for the first caller
+    async fn mark_used(&self, rec_id: i64) -> Result<u64, DbErr> {
+        let update_result = RefreshToken::update_many()
+            .col_expr(refresh_token::Column::UsedTime, Expr::value(Self::now()))
+            .col_expr(refresh_token::Column::UpdateTime, Expr::value(Self::now()))
+            .filter(refresh_token::Column::Id.eq(rec_id))
+            .filter(refresh_token::Column::UsedTime.is_null())
+            .filter(refresh_token::Column::RevokeTime.is_null())
+            .exec(&self.db)
+            .await?;
+        Ok(update_result.rows_affected)
+    }
+
+    async fn revoke_family(&self, family_id: &str) -> Result<u64, DbErr> {
+        let update_result = RefreshToken::update_many()
+            .col_expr(refresh_token::Column::RevokeTime, Expr::value(Self::now()))
+            .col_expr(refresh_token::Column::UpdateTime, Expr::value(Self::now()))
+            .filter(refresh_token::Column::FamilyId.eq(family_id))
+            .filter(refresh_token::Column::RevokeTime.is_null())
+            .exec(&self.db)
+            .await?;
+        Ok(update_result.rows_affected)
+    }
+
+    /// Current (not yet rotated) refresh token of every live session of a user
+    async fn list_active(&self, user_id: i64) -> Result<Vec<refresh_token::Model>, DbErr> {
+        RefreshToken::find()
+            .filter(refresh_token::Column::UserId.eq(user_id))
+            .filter(refresh_token::Column::UsedTime.is_null())
+            .filter(refresh_token::Column::RevokeTime.is_null())
+            .filter(refresh_token::Column::ExpireTime.gt(Self::now()))
+            .order_by_desc(refresh_token::Column::CreateTime)
+            .all(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use sea_orm::{ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter};
+
+use crate::entities::{prelude::*, *};
+
+/// Trait defining the interface for token deny-list database operations
+#[async_trait::async_trait]
+pub trait TokenDenyListMapperTrait: Send + Sync {
+    async fn save(&self, token_id: &str, user_id: i64, reason: &str, expire_time: NaiveDateTime) -> Result<i64, DbErr>;
+    async fn is_denied(&self, token_ids: Vec<String>) -> Result<bool, DbErr>;
+    async fn delete_expired(&self) -> Result<u64, DbErr>;
+}
+
+/// Implementation of TokenDenyListMapperTrait
+pub struct TokenDenyListMapper {
+    db: DatabaseConnection,
+}
+
+impl TokenDenyListMapper {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+}
+
+#[async_trait::async_trait]
+impl TokenDenyListMapperTrait for TokenDenyListMapper {
+    async fn save(&self, token_id: &str, user_id: i64, reason: &str, expire_time: NaiveDateTime) -> Result<i64, DbErr> {
+        let deny_actmod = token_deny_list::ActiveModel {
+            token_id: Set(token_id.to_string()),
+            user_id: Set(user_id),
+            reason: Set(Some(reason.to_string())),
+            expire_time: Set(expire_time),
+            create_time: Set(Some(chrono::Local::now().naive_local())),
+            ..Default::default()
+        };
+        let inserted_result = TokenDenyList::insert(deny_actmod)
+            .exec(&self.db)
+            .await?;
+        Ok(inserted_result.last_insert_id)
+    }
+
+    /// Whether any of the given token ids (`jti` or session id) is denied
+    async fn is_denied(&self, token_ids: Vec<String>) -> Result<bool, DbErr> {
+        let denied = TokenDenyList::find()
+            .filter(token_deny_list::Column::TokenId.is_in(token_ids))
+            .filter(token_deny_list::Column::ExpireTime.gt(chrono::Local::now().naive_local()))
+            .count(&self.db)
+            .await?;
+        Ok(denied > 0)
+    }
+
+    async fn delete_expired(&self) -> Result<u64, DbErr> {
+        let delete_result = TokenDenyList::delete_many()
+            .filter(token_deny_list::Column::ExpireTime.lte(chrono::Local::now().naive_local()))
+            .exec(&self.db)
+            .await?;
+        Ok(delete_result.rows_affected)
+    }
+}
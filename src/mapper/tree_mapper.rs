@@ -0,0 +1,207 @@
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::{LikeExpr, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, Condition, DbErr, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QuerySelect, TransactionTrait,
+    UpdateMany, Value,
+};
+
+use crate::mapper::crud_mapper::{Column, CrudMapper, CrudResource};
+use crate::pojo::audit_log_pojo::AuditAction;
+use crate::util::{audit, rec_sign, tree};
+
+/// A CRUD resource whose rows form a hierarchy: `parent_code` names the parent
+/// row and `path` materializes the codes from the root down to the row itself
+pub trait TreeResource: CrudResource {
+    const CODE: Column<Self>;
+    const PARENT_CODE: Column<Self>;
+    const PATH: Column<Self>;
+    /// Column a node must share with its parent, e.g. the `org_code` of a department
+    const SCOPE: Option<Column<Self>> = None;
+    /// Name of a node in messages, e.g. `部门`
+    const LABEL: &'static str;
+    /// Whether the nodes are the tenants themselves, a tenant then keeps its nodes below its own
+    const TENANT_ROOTED: bool = false;
+}
+
+/// The columns of a tree row the hierarchy operations work with
+#[derive(Debug, Clone)]
+pub struct TreeNodeRow {
+    pub id: i64,
+    pub code: String,
+    pub parent_code: Option<String>,
+    /// Stored path, or the path of a root node for rows created before paths were maintained
+    pub path: String,
+    pub scope: Option<String>,
+}
+
+/// Trait defining the hierarchy operations shared by all `TreeResource`s
+#[async_trait::async_trait]
+pub trait TreeMapperTrait<R: TreeResource>: Send + Sync {
+    /// The node `code`, for a resource with a `SCOPE` column the one of `scope` when given
+    async fn get_node(&self, code: &str, scope: Option<&str>) -> Result<Option<TreeNodeRow>, DbErr>;
+    async fn get_node_by_id(&self, rec_id: i64) -> Result<Option<TreeNodeRow>, DbErr>;
+    async fn count_children(&self, code: &str) -> Result<u64, DbErr>;
+    async fn move_subtree(&self, node: &TreeNodeRow, parent_code: Option<String>, path: String, rec_sign: &str) -> Result<u64, DbErr>;
+}
+
+/// Escape character of the `LIKE` patterns built from paths
+const LIKE_ESCAPE: char = '\\';
+
+/// `text` matched literally inside a `LIKE` pattern, `_` and `%` of a code being no wildcards
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '_' | '%') || c == LIKE_ESCAPE {
+            escaped.push(LIKE_ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Rows whose materialized path starts with `path`: the node of `path` and every node below it
+pub fn path_starts_with<C: ColumnTrait>(column: C, path: &str) -> SimpleExpr {
+    column.like(LikeExpr::new(format!("{}%", escape_like(path))).escape(LIKE_ESCAPE))
+}
+
+/// Rows whose materialized path passes through the node `code`: the node and every node below it
+pub fn path_contains_code<C: ColumnTrait>(column: C, code: &str) -> SimpleExpr {
+    column.like(LikeExpr::new(format!("%{}%", escape_like(&tree::child_path(None, code)))).escape(LIKE_ESCAPE))
+}
+
+fn string_value(value: Value) -> Option<String> {
+    match value {
+        Value::String(Some(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+impl<R: TreeResource> CrudMapper<R> {
+    fn tree_node(model: R::Model) -> Result<TreeNodeRow, DbErr> {
+        let id = match model.get(Self::id_column()?) {
+            Value::BigInt(Some(id)) => id,
+            Value::Int(Some(id)) => id.into(),
+            value => return Err(DbErr::Custom(format!("unexpected id {:?}", value))),
+        };
+        let code = string_value(model.get(R::CODE)).unwrap_or_default();
+        let path = string_value(model.get(R::PATH))
+            .filter(|path| !path.is_empty())
+            .unwrap_or_else(|| tree::child_path(None, &code));
+        Ok(TreeNodeRow {
+            id,
+            parent_code: string_value(model.get(R::PARENT_CODE)).filter(|code| !code.is_empty()),
+            scope: R::SCOPE.and_then(|scope| string_value(model.get(scope))),
+            code,
+            path,
+        })
+    }
+
+    fn not_deleted() -> Result<Condition, DbErr> {
+        let is_del = Self::column("is_del").ok_or_else(|| DbErr::Custom("tree table without is_del".to_string()))?;
+        Ok(Condition::all().add(is_del.eq(0)))
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: TreeResource> TreeMapperTrait<R> for CrudMapper<R> {
+    async fn get_node(&self, code: &str, scope: Option<&str>) -> Result<Option<TreeNodeRow>, DbErr> {
+        let mut query = R::Entity::find()
+            .filter(R::CODE.eq(code))
+            .filter(Self::not_deleted()?)
+            .filter(Self::tenant_filter());
+        if let (Some(scope_column), Some(scope)) = (R::SCOPE, scope) {
+            query = query.filter(scope_column.eq(scope));
+        }
+        query
+            .one(&self.db)
+            .await?
+            .map(Self::tree_node)
+            .transpose()
+    }
+
+    async fn get_node_by_id(&self, rec_id: i64) -> Result<Option<TreeNodeRow>, DbErr> {
+        R::Entity::find()
+            .filter(Self::id_column()?.eq(rec_id))
+            .filter(Self::not_deleted()?)
+            .filter(Self::tenant_filter())
+            .one(&self.db)
+            .await?
+            .map(Self::tree_node)
+            .transpose()
+    }
+
+    async fn count_children(&self, code: &str) -> Result<u64, DbErr> {
+        R::Entity::find()
+            .filter(R::PARENT_CODE.eq(code))
+            .filter(Self::not_deleted()?)
+            .filter(Self::tenant_filter())
+            .count(&self.db)
+            .await
+    }
+
+    /// Re-parents `node` under `parent_code` at `path` and rewrites the path of every
+    /// node below it, all in one transaction which reads and locks the subtree first. Nothing changes, and 0 is returned, unless
+    /// `node` still carries `rec_sign`; otherwise the count of rewritten rows is returned.
+    async fn move_subtree(&self, node: &TreeNodeRow, parent_code: Option<String>, path: String, rec_sign: &str) -> Result<u64, DbErr> {
+        let id_column = Self::id_column()?;
+        let rec_sign_column = Self::column("rec_sign");
+        let stamp = |mut update: UpdateMany<R::Entity>| {
+            if let Some(update_by) = Self::column("update_by") {
+                update = update.col_expr(update_by, Expr::value(audit::principal_id()));
+            }
+            if let Some(update_time) = Self::column("update_time") {
+                update = update.col_expr(update_time, Expr::value(audit::now()));
+            }
+            if let (true, Some(rec_sign_column)) = (R::VERSIONED, rec_sign_column) {
+                update = update.col_expr(rec_sign_column, Expr::value(rec_sign::generate()));
+            }
+            update
+        };
+
+        let txn = self.db.begin().await?;
+        let mut descendants = R::Entity::find()
+            .filter(path_starts_with(R::PATH, &node.path))
+            .filter(R::PATH.ne(&node.path))
+            .filter(Self::not_deleted()?)
+            .filter(Self::tenant_filter());
+        if let Some(scope) = R::SCOPE {
+            // paths only repeat in other scopes, e.g. the departments of another organization
+            descendants = descendants.filter(scope.eq(node.scope.clone()));
+        }
+        // no row locks on SQLite, whose writers are serialized anyway
+        let descendants = descendants
+            .lock_exclusive()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(Self::tree_node)
+            .collect::<Result<Vec<_>, _>>()?;
+        let rec_ids: Vec<i64> = std::iter::once(node.id).chain(descendants.iter().map(|row| row.id)).collect();
+        let before = self.audit_log.snapshot::<R::Entity, _>(&txn, &rec_ids).await?;
+
+        let mut update = stamp(R::Entity::update_many())
+            .col_expr(R::PARENT_CODE, Expr::value(parent_code))
+            .col_expr(R::PATH, Expr::value(path.clone()))
+            .filter(id_column.eq(node.id));
+        if let (true, Some(rec_sign_column)) = (R::VERSIONED, rec_sign_column) {
+            update = update.filter(rec_sign::matches(rec_sign_column, rec_sign));
+        }
+        if update.exec(&txn).await?.rows_affected == 0 {
+            // the node changed in the meantime, leave its subtree alone
+            txn.rollback().await?;
+            return Ok(0);
+        }
+        for descendant in &descendants {
+            let descendant_path = format!("{}{}", path, &descendant.path[node.path.len()..]);
+            stamp(R::Entity::update_many())
+                .col_expr(R::PATH, Expr::value(descendant_path))
+                .filter(id_column.eq(descendant.id))
+                .exec(&txn)
+                .await?;
+        }
+        let moved = rec_ids.len() as u64;
+        self.audit_log.record::<R::Entity, _>(&txn, AuditAction::Move, rec_ids, before).await?;
+        txn.commit().await?;
+        Ok(moved)
+    }
+}
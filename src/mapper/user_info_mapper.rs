@@ -1,48 +1,26 @@
-use anyhow::Result;
 use sea_orm::prelude::Expr;
 use sea_orm::sea_query::Cond;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect,
-    QueryTrait,
-};
-use std::sync::Arc;
-use tracing::info;
-use once_cell::sync::OnceCell;
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QuerySelect};
 
 use crate::entities::{prelude::*, *};
-use crate::util::paged_struct::{PageData, PageInfo, Pageable};
-use crate::util::IntoJsonValue;
-use crate::{pojo::user_info_pojo::*, AppState};
-use sea_orm::Condition;
+use crate::mapper::crud_mapper::{CrudMapper, CrudMapperTrait, CrudResource};
+use crate::util::{audit, rec_sign};
+use crate::pojo::user_info_pojo::*;
 
-/// Trait defining the interface for user info-related database operations
-#[async_trait::async_trait]
-pub trait UserInfoMapperTrait {
-    async fn list(&self, condition: UserInfoCondition) -> Result<Vec<UserInfoVo>, DbErr>;
-    async fn page(&self, condition: UserInfoCondition) -> Result<PageData<UserInfoVo>, DbErr>;
-    async fn get_by_id(&self, rec_id: i64) -> Result<Option<UserInfoVo>, DbErr>;
-    async fn save(&self, user_info_dto: UserInfoDto) -> Result<i64, DbErr>;
-    async fn update_by_id(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr>;
-    async fn delete_by_ids(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr>;
-    async fn remove_by_ids(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr>;
-}
-
-/// Implementation of UserInfoMapperTrait
-pub struct UserInfoMapper {
-    state: Arc<AppState>,
-}
+/// The user info table as a CRUD resource
+pub struct UserInfoResource;
 
-impl UserInfoMapper {
-    pub fn new(state: Arc<AppState>) -> Self {
-        Self { state }
-    }
+impl CrudResource for UserInfoResource {
+    type Entity = UserInfo;
+    type Model = user_info::Model;
+    type ActiveModel = user_info::ActiveModel;
+    type Vo = UserInfoVo;
+    type Condition = UserInfoCondition;
+    type Dto = UserInfoDto;
 
-    pub fn get_instance(state: Arc<AppState>) -> &'static UserInfoMapper {
-        static INSTANCE: OnceCell<UserInfoMapper> = OnceCell::new();
-        INSTANCE.get_or_init(|| UserInfoMapper::new(state))
-    }
+    const VERSIONED: bool = true;
 
-    fn build_query_wrapper(&self, condition: &UserInfoCondition) -> Condition {
+    fn build_query_wrapper(condition: &UserInfoCondition) -> Condition {
         let mut query_wrapper = Cond::all().add(user_info::Column::IsDel.eq(0));
         if let Some(ids) = &condition.ids {
             query_wrapper = query_wrapper.add(user_info::Column::Id.is_in(ids.clone()));
@@ -107,102 +85,60 @@ impl UserInfoMapper {
         if let Some(position_code) = &condition.position_code {
             query_wrapper = query_wrapper.add(user_info::Column::PositionCode.eq(position_code));
         };
-
-
+        if let Some(data_scope) = &condition.data_scope {
+            query_wrapper = query_wrapper.add(data_scope.condition(
+                user_info::Column::OrgCode,
+                user_info::Column::DepartmentCode,
+                user_info::Column::UserCode,
+            ));
+        };
         query_wrapper
     }
+}
 
-    async fn convert_page_data(
-        &self,
-        condition: &UserInfoCondition,
-        list: Vec<UserInfoVo>,
-        total: u64,
-    ) -> Result<PageData<UserInfoVo>, DbErr> {
-        let page_info = PageInfo::from(
-            condition.get_page().unwrap(),
-            condition.get_size().unwrap(),
-            total,
-        );
-        let page_data = PageData::new(page_info, list);
-        Ok(page_data)
-    }
+/// Mapper of the user info table, see `CrudMapperTrait` for the CRUD operations
+pub type UserInfoMapper = CrudMapper<UserInfoResource>;
+
+/// Trait defining the user info-specific database operations beyond CRUD
+#[async_trait::async_trait]
+pub trait UserInfoMapperTrait: CrudMapperTrait<UserInfoResource> {
+    async fn get_by_username(&self, username: &str) -> Result<Option<user_info::Model>, DbErr>;
+    async fn update_password(&self, rec_id: i64, password_hash: &str) -> Result<u64, DbErr>;
+    async fn list_org_codes(&self, user_code: &str) -> Result<Vec<String>, DbErr>;
 }
 
 #[async_trait::async_trait]
 impl UserInfoMapperTrait for UserInfoMapper {
-    async fn list(&self, condition: UserInfoCondition) -> Result<Vec<UserInfoVo>, DbErr> {
-        let user_info = UserInfo::find()
-            .filter(self.build_query_wrapper(&condition))
-            .apply_if(condition.get_size(), QuerySelect::limit)
-            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
-            .into_model::<UserInfoVo>()
-            .all(&self.state.mysql_pool)
-            .await?;
-
-        Ok(user_info)
-    }
-
-    async fn page(&self, condition: UserInfoCondition) -> Result<PageData<UserInfoVo>, DbErr> {
-        let user_info = UserInfo::find()
-            .filter(self.build_query_wrapper(&condition))
-            .apply_if(condition.get_size(), QuerySelect::limit)
-            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
-            .into_model::<UserInfoVo>()
-            .all(&self.state.mysql_pool)
-            .await?;
-        let total = UserInfo::find()
-            .filter(self.build_query_wrapper(&condition))
-            .count(&self.state.mysql_pool)
-            .await?;
-        self.convert_page_data(&condition, user_info, total).await
-    }
-
-    async fn get_by_id(&self, rec_id: i64) -> Result<Option<UserInfoVo>, DbErr> {
-        let user_info_opt = UserInfo::find_by_id(rec_id)
-            .into_model::<UserInfoVo>()
-            .one(&self.state.mysql_pool)
-            .await?;
-        Ok(user_info_opt)
-    }
-
-    async fn save(&self, user_info_dto: UserInfoDto) -> Result<i64, DbErr> {
-        let user_info_dtoc = user_info_dto.into_json_with_snake_key();
-        info!("user_info_json is {:?}", user_info_dtoc);
-        let mut user_info_actmod = user_info::ActiveModel::from_json(user_info_dtoc)?;
-        user_info_actmod.set(user_info::Column::CreateBy, sea_orm::Value::BigInt(Some(0)));
-        user_info_actmod.set(user_info::Column::UpdateBy, sea_orm::Value::BigInt(Some(0)));
-        let inserted_result = UserInfo::insert(user_info_actmod).exec(&self.state.mysql_pool).await?;
-        Ok(inserted_result.last_insert_id)
+    async fn get_by_username(&self, username: &str) -> Result<Option<user_info::Model>, DbErr> {
+        UserInfo::find()
+            .filter(user_info::Column::Username.eq(username))
+            .filter(user_info::Column::IsDel.eq(0))
+            .one(&self.db)
+            .await
     }
 
-    async fn update_by_id(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr> {
-        let user_info_dto_json = user_info_dto.into_json_with_snake_key();
-        info!("user_info_json is {:?}", user_info_dto);
-        let user_info_actmod = user_info::ActiveModel::from_json(user_info_dto_json)?;
+    async fn update_password(&self, rec_id: i64, password_hash: &str) -> Result<u64, DbErr> {
         let update_result = UserInfo::update_many()
-            .set(user_info_actmod)
-            .filter(user_info::Column::Id.eq(user_info_dto.rec_id))
-            .exec(&self.state.mysql_pool)
+            .col_expr(user_info::Column::Password, Expr::value(password_hash))
+            .col_expr(user_info::Column::RecSign, Expr::value(rec_sign::generate()))
+            .col_expr(user_info::Column::UpdateBy, Expr::value(audit::principal_id()))
+            .col_expr(user_info::Column::UpdateTime, Expr::value(audit::now()))
+            .filter(user_info::Column::Id.eq(rec_id))
+            .exec(&self.db)
             .await?;
         Ok(update_result.rows_affected)
     }
 
-    async fn delete_by_ids(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr> {
-        info!("user_info_json is {:?}", user_info_dto);
-        let update_result = UserInfo::update_many()
-            .col_expr(user_info::Column::IsDel, Expr::value(-1))
-            .filter(user_info::Column::Id.is_in(user_info_dto.rec_ids.unwrap()))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
-    }
-
-    async fn remove_by_ids(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr> {
-        info!("user_info_json is {:?}", user_info_dto);
-        let update_result = UserInfo::delete_many()
-            .filter(user_info::Column::Id.is_in(user_info_dto.rec_ids.unwrap()))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
+    /// Organizations the person `user_code` works for, one per user info row
+    async fn list_org_codes(&self, user_code: &str) -> Result<Vec<String>, DbErr> {
+        UserInfo::find()
+            .select_only()
+            .column(user_info::Column::OrgCode)
+            .filter(user_info::Column::UserCode.eq(user_code))
+            .filter(user_info::Column::OrgCode.is_not_null())
+            .filter(user_info::Column::IsDel.eq(0))
+            .into_tuple::<String>()
+            .all(&self.db)
+            .await
     }
-} 
\ No newline at end of file
+}
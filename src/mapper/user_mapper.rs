@@ -1,48 +1,24 @@
-use anyhow::Result;
 use sea_orm::prelude::Expr;
 use sea_orm::sea_query::Cond;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect,
-    QueryTrait,
-};
-use std::sync::Arc;
-use tracing::info;
-use once_cell::sync::OnceCell;
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QuerySelect, QueryTrait};
 
 use crate::entities::{prelude::*, *};
-use crate::util::paged_struct::{PageData, PageInfo, Pageable};
-use crate::util::IntoJsonValue;
-use crate::{pojo::user_pojo::*, AppState};
-use sea_orm::Condition;
+use crate::mapper::crud_mapper::{CrudMapper, CrudMapperTrait, CrudResource};
+use crate::util::audit;
+use crate::pojo::user_pojo::*;
 
-/// Trait defining the interface for user-related database operations
-#[async_trait::async_trait]
-pub trait UserMapperTrait {
-    async fn list(&self, condition: UserCondition) -> Result<Vec<UserVo>, DbErr>;
-    async fn page(&self, condition: UserCondition) -> Result<PageData<UserVo>, DbErr>;
-    async fn get_by_id(&self, rec_id: i64) -> Result<Option<UserVo>, DbErr>;
-    async fn save(&self, user_dto: UserDto) -> Result<i64, DbErr>;
-    async fn update_by_id(&self, user_dto: UserDto) -> Result<u64, DbErr>;
-    async fn delete_by_ids(&self, user_dto: UserDto) -> Result<u64, DbErr>;
-    async fn remove_by_ids(&self, user_dto: UserDto) -> Result<u64, DbErr>;
-}
-
-/// Implementation of UserMapperTrait
-pub struct UserMapper {
-    state: Arc<AppState>,
-}
-
-impl UserMapper {
-    pub fn new(state: Arc<AppState>) -> Self {
-        Self { state }
-    }
+/// The user table as a CRUD resource
+pub struct UserResource;
 
-    pub fn get_instance(state: Arc<AppState>) -> &'static UserMapper {
-        static INSTANCE: OnceCell<UserMapper> = OnceCell::new();
-        INSTANCE.get_or_init(|| UserMapper::new(state))
-    }
+impl CrudResource for UserResource {
+    type Entity = User;
+    type Model = user::Model;
+    type ActiveModel = user::ActiveModel;
+    type Vo = UserVo;
+    type Condition = UserCondition;
+    type Dto = UserDto;
 
-    fn build_query_wrapper(&self, condition: &UserCondition) -> Condition {
+    fn build_query_wrapper(condition: &UserCondition) -> Condition {
         let mut query_wrapper = Cond::all().add(user::Column::IsDel.eq(0));
         if let Some(ids) = &condition.ids {
             query_wrapper = query_wrapper.add(user::Column::Id.is_in(ids.clone()));
@@ -56,96 +32,63 @@ impl UserMapper {
         query_wrapper
     }
 
-    async fn convert_page_data(
-        &self,
-        condition: &UserCondition,
-        list: Vec<UserVo>,
-        total: u64,
-    ) -> Result<PageData<UserVo>, DbErr> {
-        let page_info = PageInfo::from(
-            condition.get_page().unwrap(),
-            condition.get_size().unwrap(),
-            total,
-        );
-        let page_data = PageData::new(page_info, list);
-        Ok(page_data)
+    /// Accounts of the people working for the organization, an account has no `org_code` of its own
+    fn tenant_condition(org_code: &str) -> Option<Condition> {
+        let usernames = UserInfo::find()
+            .select_only()
+            .column(user_info::Column::Username)
+            .filter(user_info::Column::OrgCode.eq(org_code))
+            .filter(user_info::Column::IsDel.eq(0))
+            .into_query();
+        Some(Cond::all().add(user::Column::UserName.in_subquery(usernames)))
     }
 }
 
-#[async_trait::async_trait]
-impl UserMapperTrait for UserMapper {
-    async fn list(&self, condition: UserCondition) -> Result<Vec<UserVo>, DbErr> {
-        let user = User::find()
-            .filter(self.build_query_wrapper(&condition))
-            .apply_if(condition.get_size(), QuerySelect::limit)
-            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
-            .into_model::<UserVo>()
-            .all(&self.state.mysql_pool)
-            .await?;
-
-        Ok(user)
-    }
+/// Mapper of the user table, see `CrudMapperTrait` for the CRUD operations
+pub type UserMapper = CrudMapper<UserResource>;
 
-    async fn page(&self, condition: UserCondition) -> Result<PageData<UserVo>, DbErr> {
-        let user = User::find()
-            .filter(self.build_query_wrapper(&condition))
-            .apply_if(condition.get_size(), QuerySelect::limit)
-            .apply_if(condition.get_offset(), QuerySelect::offset::<u64>)
-            .into_model::<UserVo>()
-            .all(&self.state.mysql_pool)
-            .await?;
-        let total = User::find()
-            .filter(self.build_query_wrapper(&condition))
-            .count(&self.state.mysql_pool)
-            .await?;
-        self.convert_page_data(&condition, user, total).await
-    }
+/// Trait defining the user-specific database operations beyond CRUD
+#[async_trait::async_trait]
+pub trait UserMapperTrait: CrudMapperTrait<UserResource> {
+    async fn get_by_user_name(&self, user_name: &str) -> Result<Option<user::Model>, DbErr>;
+    async fn get_account_by_id(&self, rec_id: i64) -> Result<Option<user::Model>, DbErr>;
+    async fn update_last_login_time(&self, rec_id: i64) -> Result<u64, DbErr>;
+    async fn update_password(&self, rec_id: i64, password_hash: &str) -> Result<u64, DbErr>;
+}
 
-    async fn get_by_id(&self, rec_id: i64) -> Result<Option<UserVo>, DbErr> {
-        let user_opt = User::find_by_id(rec_id)
-            .into_model::<UserVo>()
-            .one(&self.state.mysql_pool)
-            .await?;
-        Ok(user_opt)
+#[async_trait::async_trait]
+impl UserMapperTrait for UserMapper {
+    async fn get_by_user_name(&self, user_name: &str) -> Result<Option<user::Model>, DbErr> {
+        User::find()
+            .filter(user::Column::UserName.eq(user_name))
+            .filter(user::Column::IsDel.eq(0))
+            .one(&self.db)
+            .await
     }
 
-    async fn save(&self, user_dto: UserDto) -> Result<i64, DbErr> {
-        let user_dtoc = user_dto.into_json_with_snake_key();
-        info!("user_json is {:?}", user_dtoc);
-        let mut user_actmod = user::ActiveModel::from_json(user_dtoc)?;
-        user_actmod.set(user::Column::CreateBy, sea_orm::Value::BigInt(Some(0)));
-        user_actmod.set(user::Column::UpdateBy, sea_orm::Value::BigInt(Some(0)));
-        let inserted_result = User::insert(user_actmod).exec(&self.state.mysql_pool).await?;
-        Ok(inserted_result.last_insert_id)
+    async fn get_account_by_id(&self, rec_id: i64) -> Result<Option<user::Model>, DbErr> {
+        User::find_by_id(rec_id)
+            .filter(user::Column::IsDel.eq(0))
+            .one(&self.db)
+            .await
     }
 
-    async fn update_by_id(&self, user_dto: UserDto) -> Result<u64, DbErr> {
-        let user_dto_json = user_dto.into_json_with_snake_key();
-        info!("user_json is {:?}", user_dto);
-        let user_actmod = user::ActiveModel::from_json(user_dto_json)?;
+    async fn update_last_login_time(&self, rec_id: i64) -> Result<u64, DbErr> {
         let update_result = User::update_many()
-            .set(user_actmod)
-            .filter(user::Column::Id.eq(user_dto.rec_id))
-            .exec(&self.state.mysql_pool)
+            .col_expr(user::Column::LastLoginTime, Expr::value(chrono::Local::now().naive_local()))
+            .filter(user::Column::Id.eq(rec_id))
+            .exec(&self.db)
             .await?;
         Ok(update_result.rows_affected)
     }
 
-    async fn delete_by_ids(&self, user_dto: UserDto) -> Result<u64, DbErr> {
-        info!("user_json is {:?}", user_dto);
+    async fn update_password(&self, rec_id: i64, password_hash: &str) -> Result<u64, DbErr> {
         let update_result = User::update_many()
-            .col_expr(user::Column::IsDel, Expr::value(-1))
-            .filter(user::Column::Id.is_in(user_dto.rec_ids.unwrap()))
-            .exec(&self.state.mysql_pool)
-            .await?;
-        Ok(update_result.rows_affected)
-    }
-
-    async fn remove_by_ids(&self, user_dto: UserDto) -> Result<u64, DbErr> {
-        info!("user_json is {:?}", user_dto);
-        let update_result = User::delete_many()
-            .filter(user::Column::Id.is_in(user_dto.rec_ids.unwrap()))
-            .exec(&self.state.mysql_pool)
+            .col_expr(user::Column::Password, Expr::value(password_hash))
+            .col_expr(user::Column::UpdateBy, Expr::value(audit::principal_id()))
+            .col_expr(user::Column::UpdateTime, Expr::value(audit::now()))
+            .filter(user::Column::Id.eq(rec_id))
+            .exec(&self.db)
             .await?;
         Ok(update_result.rows_affected)
     }
@@ -0,0 +1,170 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use once_cell::sync::Lazy;
+
+use crate::{
+    ctl::principal::bearer_token,
+    util::{
+        audit::{self, AuditContext},
+        exception::AppError,
+        tenant,
+    },
+    AppState,
+};
+
+/// Routes reachable without a token
+const PUBLIC_ROUTES: [&str; 4] = ["/", "/auth/login", "/auth/refresh", "/auth/publicKey"];
+
+/// Routes open to every authenticated user, they only act on the caller's own data
+const SELF_SERVICE_PREFIXES: [&str; 2] = ["/auth/", "/authz/me/"];
+
+/// Routes posting a query body, they only read like a `GET`
+const QUERY_ROUTES: [&str; 2] = ["/authz/check", "/authz/check-batch"];
+
+/// Reverse proxies whose forwarding headers are believed, the comma separated
+/// addresses of `TRUSTED_PROXIES`; none by default
+static TRUSTED_PROXIES: Lazy<Vec<IpAddr>> = Lazy::new(|| {
+    std::env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect()
+});
+
+/// Header selecting which of the caller's organizations a request acts on
+pub const ORG_CODE_HEADER: &str = "x-org-code";
+
+/// What a route requires from its caller before the handler runs
+#[derive(Debug, PartialEq, Eq)]
+pub enum Access {
+    Public,
+    Authenticated,
+    Permission(String),
+}
+
+/// Default access rule of a route: `{resource}:read` for reads, `{resource}:delete`
+/// for soft and hard deletes and `{resource}:write` for every other mutation,
+/// `resource` being the first path segment, e.g. `userRoleRef:write`
+pub fn default_access(method: &Method, route: &str) -> Access {
+    if PUBLIC_ROUTES.contains(&route) {
+        return Access::Public;
+    }
+    if SELF_SERVICE_PREFIXES.iter().any(|prefix| route.starts_with(prefix)) {
+        return Access::Authenticated;
+    }
+    let mut segments = route.trim_start_matches('/').split('/');
+    let resource = segments.next().unwrap_or_default();
+    let action = if *method == Method::GET || QUERY_ROUTES.contains(&route) {
+        "read"
+    } else if *method == Method::DELETE || route.ends_with("/delByIds") {
+        "delete"
+    } else {
+        "write"
+    };
+    Access::Permission(format!("{}:{}", resource, action))
+}
+
+/// Validates the bearer token, puts its `Claims` into the request extensions and enforces
+/// the permission guarding the matched route. The default rule of [`default_access`] can be
+/// re-mapped through `api` permission nodes without a redeploy, administrators pass every check.
+/// A guarded route first resolves the caller's organization, see `util::tenant`, the permission is
+/// evaluated on the roles held there and the handler then runs confined to it.
+pub async fn authorize(
+    State(state): State<Arc<AppState>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let method = req.method().clone();
+    let default_code = match default_access(&method, &route) {
+        Access::Public => return Ok(next.run(req).await),
+        Access::Authenticated => None,
+        Access::Permission(code) => Some(code),
+    };
+    // self-service routes only act on the caller's own data, every other one on an organization's
+    let tenant_scoped = default_code.is_some();
+
+    let token = bearer_token(req.headers())
+        .ok_or_else(|| AppError::Unauthorized("请先登录！".to_string()))?;
+    let claims = state.svcs().auth
+        .verify_token(token)
+        .await?;
+
+    let org_code = if tenant_scoped {
+        let requested = req.headers().get(ORG_CODE_HEADER).and_then(|value| value.to_str().ok());
+        state.svcs().auth
+            .tenant(&claims, requested.map(str::trim).filter(|org_code| !org_code.is_empty()))
+            .await?
+    } else {
+        None
+    };
+
+    if !claims.is_admin {
+        let authz_svc = &state.svcs().authz;
+        let required = authz_svc
+            .route_permission(method.as_str(), &route, org_code.clone(), default_code)
+            .await?;
+        if let Some(permission_code) = required {
+            // the roles held in the organization the request acts on, not in the home organization
+            let granted = match &claims.user_code {
+                Some(user_code) => authz_svc
+                    .is_granted(user_code, org_code.clone(), &permission_code)
+                    .await?,
+                None => false,
+            };
+            if !granted {
+                return Err(AppError::Forbidden(format!("缺少权限 {}！", permission_code)));
+            }
+        }
+    }
+
+    let context = AuditContext {
+        principal_id: claims.uid,
+        user_code: claims.user_code.clone(),
+        org_code: org_code.clone().or_else(|| claims.org_code.clone()),
+        ip: client_ip(&req),
+        is_admin: claims.is_admin,
+    };
+    req.extensions_mut().insert(claims);
+    Ok(audit::scope(context, tenant::scope(org_code, next.run(req))).await)
+}
+
+/// Address of the client: the peer address, or for a peer among `TRUSTED_PROXIES` the
+/// nearest address of `X-Forwarded-For` (else `X-Real-IP`) not itself a trusted proxy
+fn client_ip(req: &Request) -> Option<String> {
+    let peer = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| addr.ip())?;
+    if !TRUSTED_PROXIES.contains(&peer) {
+        return Some(peer.to_string());
+    }
+    let headers = req.headers();
+    let forwarded = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            // every proxy appends the address it received the request from
+            value
+                .rsplit(',')
+                .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+                .find(|ip| !TRUSTED_PROXIES.contains(ip))
+        })
+        .or_else(|| {
+            headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+        });
+    Some(forwarded.unwrap_or(peer).to_string())
+}
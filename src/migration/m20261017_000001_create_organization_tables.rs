@@ -0,0 +1,192 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+use super::{audit_columns, code, code_null, create_index, id, path};
+
+/// The organisation structure: organizations, their departments, positions and user groups
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(Organization::Table)
+                        .if_not_exists()
+                        .col(id(Organization::Id))
+                        .col(code_null(Organization::Code))
+                        .col(code_null(Organization::ParentCode))
+                        .col(string_null(Organization::Name))
+                        .col(string_len_null(Organization::Type, 32))
+                        .col(string_len_null(Organization::Contacts, 64))
+                        .col(string_len_null(Organization::Cellphone, 32))
+                        .col(string_null(Organization::Email))
+                        .col(string_len_null(Organization::Uscc, 32))
+                        .col(string_len_null(Organization::BusinessLicense, 512))
+                        .col(string_len_null(Organization::IdCardFront, 512))
+                        .col(string_len_null(Organization::IdCardBack, 512))
+                        .col(date_null(Organization::InvalidDate))
+                        .col(string_len_null(Organization::Status, 32))
+                        .col(json_null(Organization::Extra))
+                        .col(string_len_null(Organization::Remark, 512))
+                        .col(path(Organization::Path)),
+                    true,
+                )
+                .take(),
+            )
+            .await?;
+        create_index(manager, Organization::Table, &[Organization::Code]).await?;
+        create_index(manager, Organization::Table, &[Organization::ParentCode]).await?;
+
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(Department::Table)
+                        .if_not_exists()
+                        .col(id(Department::Id))
+                        .col(code_null(Department::Code))
+                        .col(code_null(Department::ParentCode))
+                        .col(string(Department::Name))
+                        .col(string_len_null(Department::Description, 512))
+                        .col(code(Department::OrgCode))
+                        .col(json_null(Department::Extra))
+                        .col(string_len_null(Department::Remark, 512))
+                        .col(path(Department::Path)),
+                    true,
+                )
+                .take(),
+            )
+            .await?;
+        create_index(manager, Department::Table, &[Department::Code]).await?;
+        create_index(manager, Department::Table, &[Department::ParentCode]).await?;
+        create_index(manager, Department::Table, &[Department::OrgCode]).await?;
+
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(Position::Table)
+                        .if_not_exists()
+                        .col(id(Position::Id))
+                        .col(code_null(Position::Code))
+                        .col(code_null(Position::ParentCode))
+                        .col(string(Position::Name))
+                        .col(string_len_null(Position::Description, 512))
+                        .col(code_null(Position::DepartmentCode))
+                        .col(code_null(Position::OrgCode))
+                        .col(json_null(Position::Extra))
+                        .col(string_len_null(Position::Remark, 512))
+                        .col(path(Position::Path)),
+                    false,
+                )
+                .take(),
+            )
+            .await?;
+        create_index(manager, Position::Table, &[Position::Code]).await?;
+        create_index(manager, Position::Table, &[Position::DepartmentCode]).await?;
+        create_index(manager, Position::Table, &[Position::OrgCode]).await?;
+
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(Group::Table)
+                        .if_not_exists()
+                        .col(id(Group::Id))
+                        .col(code_null(Group::Code))
+                        .col(code_null(Group::ParentCode))
+                        .col(string(Group::Name))
+                        .col(string_len(Group::Type, 32))
+                        .col(string_len_null(Group::Description, 512))
+                        .col(code(Group::Application))
+                        .col(code_null(Group::OrgCode))
+                        .col(json_null(Group::Extra))
+                        .col(string_len_null(Group::Remark, 512))
+                        .col(path(Group::Path)),
+                    false,
+                )
+                .take(),
+            )
+            .await?;
+        create_index(manager, Group::Table, &[Group::Code]).await?;
+        create_index(manager, Group::Table, &[Group::OrgCode]).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(Group::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(Position::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(Department::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(Organization::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Organization {
+    Table,
+    Id,
+    Code,
+    ParentCode,
+    Name,
+    Type,
+    Contacts,
+    Cellphone,
+    Email,
+    Uscc,
+    BusinessLicense,
+    IdCardFront,
+    IdCardBack,
+    InvalidDate,
+    Status,
+    Extra,
+    Remark,
+    Path,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Department {
+    Table,
+    Id,
+    Code,
+    ParentCode,
+    Name,
+    Description,
+    OrgCode,
+    Extra,
+    Remark,
+    Path,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Position {
+    Table,
+    Id,
+    Code,
+    ParentCode,
+    Name,
+    Description,
+    DepartmentCode,
+    OrgCode,
+    Extra,
+    Remark,
+    Path,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Group {
+    Table,
+    Id,
+    Code,
+    ParentCode,
+    Name,
+    Type,
+    Description,
+    Application,
+    OrgCode,
+    Extra,
+    Remark,
+    Path,
+}
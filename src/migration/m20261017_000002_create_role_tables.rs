@@ -0,0 +1,116 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+use super::{audit_columns, code, code_null, create_index, create_unique_index, id, path};
+
+/// Roles and the permission tree they grant
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(Role::Table)
+                        .if_not_exists()
+                        .col(id(Role::Id))
+                        .col(code_null(Role::Code))
+                        .col(code_null(Role::ParentCode))
+                        .col(string(Role::Name))
+                        .col(string_len_null(Role::Description, 512))
+                        .col(code_null(Role::Application))
+                        .col(json_null(Role::Permissions))
+                        .col(code_null(Role::OrgCode))
+                        .col(json_null(Role::Extra))
+                        .col(string_len_null(Role::Remark, 512))
+                        .col(tiny_integer(Role::RoleType).default(0))
+                        .col(path(Role::Path)),
+                    true,
+                )
+                .take(),
+            )
+            .await?;
+        create_index(manager, Role::Table, &[Role::Code]).await?;
+        create_index(manager, Role::Table, &[Role::ParentCode]).await?;
+        create_index(manager, Role::Table, &[Role::OrgCode]).await?;
+
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(Permission::Table)
+                        .if_not_exists()
+                        .col(id(Permission::Id))
+                        .col(code(Permission::Uuid))
+                        .col(code_null(Permission::ParentUuid))
+                        .col(string_len_null(Permission::Code, 128))
+                        .col(string(Permission::Name))
+                        .col(string_len_null(Permission::Description, 512))
+                        .col(code_null(Permission::Application))
+                        .col(integer_null(Permission::Sort))
+                        .col(string_len_null(Permission::NodeType, 32))
+                        .col(string_len_null(Permission::LinkUrl, 512))
+                        .col(string_len_null(Permission::IcoUrl, 512))
+                        .col(integer_null(Permission::Level))
+                        .col(path(Permission::Path))
+                        .col(code_null(Permission::OrgCode))
+                        .col(json_null(Permission::Extra))
+                        .col(string_len_null(Permission::Remark, 512)),
+                    false,
+                )
+                .take(),
+            )
+            .await?;
+        create_unique_index(manager, Permission::Table, &[Permission::Uuid]).await?;
+        create_index(manager, Permission::Table, &[Permission::ParentUuid]).await?;
+        create_index(manager, Permission::Table, &[Permission::Code]).await?;
+        create_index(manager, Permission::Table, &[Permission::OrgCode]).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(Permission::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(Role::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
+enum Role {
+    Table,
+    Id,
+    Code,
+    ParentCode,
+    Name,
+    Description,
+    Application,
+    Permissions,
+    OrgCode,
+    Extra,
+    Remark,
+    RoleType,
+    Path,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Permission {
+    Table,
+    Id,
+    Uuid,
+    ParentUuid,
+    Code,
+    Name,
+    Description,
+    Application,
+    Sort,
+    NodeType,
+    LinkUrl,
+    IcoUrl,
+    Level,
+    Path,
+    OrgCode,
+    Extra,
+    Remark,
+}
@@ -0,0 +1,206 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+use super::{audit_columns, code_null, create_index, create_unique_index, id, path};
+
+/// Login accounts, the people behind them and their WeChat profiles
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(User::Table)
+                        .if_not_exists()
+                        .col(id(User::Id))
+                        .col(string_len(User::UserName, 64))
+                        .col(string_len_null(User::RealName, 64))
+                        .col(string_null(User::Password))
+                        .col(tiny_integer_null(User::Status).default(0))
+                        .col(tiny_integer_null(User::Type).default(0))
+                        .col(string_null(User::Email))
+                        .col(string_len_null(User::AreaCode, 16))
+                        .col(string_len_null(User::Phone, 32))
+                        .col(string_len_null(User::Remark, 512))
+                        .col(string_len_null(User::HeadPic, 512))
+                        .col(tiny_integer_null(User::IsAdmin).default(0))
+                        .col(string_len_null(User::OpenId, 128))
+                        .col(date_time_null(User::LastLoginTime))
+                        .col(string_len_null(User::WechatOpenId, 128))
+                        .col(string_len_null(User::WechatUnionId, 128)),
+                    false,
+                )
+                .take(),
+            )
+            .await?;
+        create_index(manager, User::Table, &[User::UserName]).await?;
+
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(UserInfo::Table)
+                        .if_not_exists()
+                        .col(id(UserInfo::Id))
+                        .col(code_null(UserInfo::UserCode))
+                        .col(string_len_null(UserInfo::Username, 64))
+                        .col(string_len_null(UserInfo::LoginStatus, 32))
+                        .col(string_len_null(UserInfo::EnglishName, 64))
+                        .col(string_len(UserInfo::RealName, 64))
+                        .col(string_len_null(UserInfo::NickName, 64))
+                        .col(string_len_null(UserInfo::Cellphone, 32))
+                        .col(string_len_null(UserInfo::Gender, 16))
+                        .col(string_len_null(UserInfo::Portrait, 512))
+                        .col(string_len(UserInfo::UserType, 32))
+                        .col(date_null(UserInfo::Birthday))
+                        .col(string_null(UserInfo::Password))
+                        .col(string_len_null(UserInfo::IdCardType, 32))
+                        .col(string_len_null(UserInfo::IdCardNo, 64))
+                        .col(string_null(UserInfo::Email))
+                        .col(string_len_null(UserInfo::Qq, 32))
+                        .col(string_len_null(UserInfo::WxUnionId, 128))
+                        .col(string_len_null(UserInfo::WxOpenId, 128))
+                        .col(string_len_null(UserInfo::WxMiniOpenId, 128))
+                        .col(string_len_null(UserInfo::Address, 512))
+                        .col(code_null(UserInfo::ParentCode))
+                        .col(path(UserInfo::Path))
+                        .col(date_null(UserInfo::EffectiveDate))
+                        .col(date_null(UserInfo::InvalidDate))
+                        .col(string_len_null(UserInfo::Status, 32))
+                        .col(code_null(UserInfo::OrgCode))
+                        .col(code_null(UserInfo::DepartmentCode))
+                        .col(code_null(UserInfo::PositionCode))
+                        .col(json_null(UserInfo::Extra))
+                        .col(string_len_null(UserInfo::Remark, 512)),
+                    true,
+                )
+                .take(),
+            )
+            .await?;
+        create_index(manager, UserInfo::Table, &[UserInfo::UserCode]).await?;
+        create_index(manager, UserInfo::Table, &[UserInfo::Username]).await?;
+        create_index(manager, UserInfo::Table, &[UserInfo::OrgCode]).await?;
+        create_index(manager, UserInfo::Table, &[UserInfo::DepartmentCode]).await?;
+        create_index(manager, UserInfo::Table, &[UserInfo::PositionCode]).await?;
+
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(UserWechatInfo::Table)
+                        .if_not_exists()
+                        .col(big_integer(UserWechatInfo::Id).primary_key())
+                        .col(string_len_null(UserWechatInfo::UnionId, 128))
+                        .col(string_len_null(UserWechatInfo::WechatOpenId, 128))
+                        .col(string_len_null(UserWechatInfo::MiniOpenId, 128))
+                        .col(string_len_null(UserWechatInfo::Nickname, 128))
+                        .col(string_len_null(UserWechatInfo::Language, 32))
+                        .col(tiny_integer_null(UserWechatInfo::Subscribe))
+                        .col(string_len_null(UserWechatInfo::HeadImgUrl, 512))
+                        .col(big_integer_null(UserWechatInfo::SubscribeTime))
+                        .col(string_len_null(UserWechatInfo::Remark, 512))
+                        .col(integer_null(UserWechatInfo::GroupId))
+                        .col(json_null(UserWechatInfo::TagIds))
+                        .col(string_len_null(UserWechatInfo::SubscribeScene, 64))
+                        .col(string_len_null(UserWechatInfo::QrScene, 64))
+                        .col(string_len_null(UserWechatInfo::QrSceneStr, 128))
+                        .col(string_len_null(UserWechatInfo::AppId, 64))
+                        .col(string_len_null(UserWechatInfo::AppType, 32)),
+                    false,
+                )
+                .take(),
+            )
+            .await?;
+        create_unique_index(manager, UserWechatInfo::Table, &[UserWechatInfo::UnionId]).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(UserWechatInfo::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(UserInfo::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(User::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
+enum User {
+    Table,
+    Id,
+    UserName,
+    RealName,
+    Password,
+    Status,
+    Type,
+    Email,
+    AreaCode,
+    Phone,
+    Remark,
+    HeadPic,
+    IsAdmin,
+    OpenId,
+    LastLoginTime,
+    WechatOpenId,
+    WechatUnionId,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum UserInfo {
+    Table,
+    Id,
+    UserCode,
+    Username,
+    LoginStatus,
+    EnglishName,
+    RealName,
+    NickName,
+    Cellphone,
+    Gender,
+    Portrait,
+    UserType,
+    Birthday,
+    Password,
+    IdCardType,
+    IdCardNo,
+    Email,
+    Qq,
+    WxUnionId,
+    WxOpenId,
+    WxMiniOpenId,
+    Address,
+    ParentCode,
+    Path,
+    EffectiveDate,
+    InvalidDate,
+    Status,
+    OrgCode,
+    DepartmentCode,
+    PositionCode,
+    Extra,
+    Remark,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum UserWechatInfo {
+    Table,
+    Id,
+    UnionId,
+    WechatOpenId,
+    MiniOpenId,
+    Nickname,
+    Language,
+    Subscribe,
+    HeadImgUrl,
+    SubscribeTime,
+    Remark,
+    GroupId,
+    TagIds,
+    SubscribeScene,
+    QrScene,
+    QrSceneStr,
+    AppId,
+    AppType,
+}
@@ -0,0 +1,76 @@
+use sea_orm_migration::prelude::*;
+
+use super::{audit_columns, code, code_null, create_index, create_unique_index, id};
+
+/// Role grants and group memberships, each unique per subject, target and organization
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Every ref table with the code of the subject and of the target it links
+const REF_TABLES: [(RefTable, RefColumn, RefColumn, bool); 6] = [
+    (RefTable::UserRoleRef, RefColumn::UserCode, RefColumn::RoleCode, true),
+    (RefTable::UserGroupRef, RefColumn::UserCode, RefColumn::GroupCode, false),
+    (RefTable::GroupRoleRef, RefColumn::GroupCode, RefColumn::RoleCode, false),
+    (RefTable::DepartmentRoleRef, RefColumn::DepartmentCode, RefColumn::RoleCode, false),
+    (RefTable::PositionRoleRef, RefColumn::PositionCode, RefColumn::RoleCode, false),
+    (RefTable::OrganizationRoleRef, RefColumn::OrgCode, RefColumn::RoleCode, false),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, subject, target, versioned) in REF_TABLES {
+            let mut create = Table::create();
+            create
+                .table(table)
+                .if_not_exists()
+                .col(id(RefColumn::Id))
+                .col(code(subject))
+                .col(code(target));
+            // the organization a grant applies in, an organization's own grants need no other
+            let scoped = subject != RefColumn::OrgCode;
+            if scoped {
+                create.col(code_null(RefColumn::OrgCode));
+            }
+            manager.create_table(audit_columns(&mut create, versioned).take()).await?;
+
+            if scoped {
+                create_unique_index(manager, table, &[subject, target, RefColumn::OrgCode]).await?;
+                create_index(manager, table, &[RefColumn::OrgCode]).await?;
+            } else {
+                create_unique_index(manager, table, &[subject, target]).await?;
+            }
+            create_index(manager, table, &[target]).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, ..) in REF_TABLES.into_iter().rev() {
+            manager.drop_table(Table::drop().table(table).to_owned()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
+enum RefTable {
+    UserRoleRef,
+    UserGroupRef,
+    GroupRoleRef,
+    DepartmentRoleRef,
+    PositionRoleRef,
+    OrganizationRoleRef,
+}
+
+#[derive(DeriveIden, Clone, Copy, PartialEq, Eq)]
+enum RefColumn {
+    Id,
+    UserCode,
+    GroupCode,
+    DepartmentCode,
+    PositionCode,
+    OrgCode,
+    RoleCode,
+}
@@ -0,0 +1,161 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+use super::{audit_columns, code_null, create_index, create_time, create_unique_index, id};
+
+/// System configuration, the session tables of `AuthSvc` and the audit log
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                audit_columns(
+                    Table::create()
+                        .table(SystemConfig::Table)
+                        .if_not_exists()
+                        .col(id(SystemConfig::Id))
+                        .col(string_len_null(SystemConfig::ConfigKey, 128))
+                        .col(text_null(SystemConfig::ConfigValue))
+                        .col(string_len_null(SystemConfig::ConfigType, 32))
+                        .col(string_len_null(SystemConfig::Description, 512))
+                        .col(string_len(SystemConfig::Status, 32))
+                        .col(code_null(SystemConfig::OrgCode)),
+                    false,
+                )
+                .take(),
+            )
+            .await?;
+        create_index(manager, SystemConfig::Table, &[SystemConfig::ConfigKey]).await?;
+        create_index(manager, SystemConfig::Table, &[SystemConfig::OrgCode]).await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RefreshToken::Table)
+                    .if_not_exists()
+                    .col(id(RefreshToken::Id))
+                    .col(big_integer(RefreshToken::UserId))
+                    .col(string_len(RefreshToken::TokenHash, 64))
+                    .col(string_len(RefreshToken::FamilyId, 64))
+                    .col(string_len_null(RefreshToken::Device, 64))
+                    .col(string_len_null(RefreshToken::UserAgent, 512))
+                    .col(date_time(RefreshToken::ExpireTime))
+                    .col(date_time_null(RefreshToken::UsedTime))
+                    .col(date_time_null(RefreshToken::RevokeTime))
+                    .col(create_time(RefreshToken::CreateTime))
+                    .col(date_time_null(RefreshToken::UpdateTime))
+                    .take(),
+            )
+            .await?;
+        create_unique_index(manager, RefreshToken::Table, &[RefreshToken::TokenHash]).await?;
+        create_index(manager, RefreshToken::Table, &[RefreshToken::FamilyId]).await?;
+        create_index(manager, RefreshToken::Table, &[RefreshToken::UserId]).await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TokenDenyList::Table)
+                    .if_not_exists()
+                    .col(id(TokenDenyList::Id))
+                    .col(string_len(TokenDenyList::TokenId, 64))
+                    .col(big_integer(TokenDenyList::UserId))
+                    .col(string_len_null(TokenDenyList::Reason, 64))
+                    .col(date_time(TokenDenyList::ExpireTime))
+                    .col(create_time(TokenDenyList::CreateTime))
+                    .take(),
+            )
+            .await?;
+        create_index(manager, TokenDenyList::Table, &[TokenDenyList::TokenId]).await?;
+        create_index(manager, TokenDenyList::Table, &[TokenDenyList::ExpireTime]).await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLog::Table)
+                    .if_not_exists()
+                    .col(id(AuditLog::Id))
+                    .col(big_integer(AuditLog::ActorId))
+                    .col(code_null(AuditLog::ActorCode))
+                    .col(string_len_null(AuditLog::Ip, 64))
+                    .col(string_len(AuditLog::Entity, 64))
+                    .col(string_len(AuditLog::Action, 16))
+                    .col(json_null(AuditLog::RecIds))
+                    .col(json_null(AuditLog::BeforeData))
+                    .col(json_null(AuditLog::AfterData))
+                    .col(json_null(AuditLog::DiffData))
+                    .col(code_null(AuditLog::OrgCode))
+                    .col(create_time(AuditLog::CreateTime))
+                    .take(),
+            )
+            .await?;
+        create_index(manager, AuditLog::Table, &[AuditLog::Entity]).await?;
+        create_index(manager, AuditLog::Table, &[AuditLog::ActorId]).await?;
+        create_index(manager, AuditLog::Table, &[AuditLog::CreateTime]).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(AuditLog::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(TokenDenyList::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(RefreshToken::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(SystemConfig::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum SystemConfig {
+    Table,
+    Id,
+    ConfigKey,
+    ConfigValue,
+    ConfigType,
+    Description,
+    Status,
+    OrgCode,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum RefreshToken {
+    Table,
+    Id,
+    UserId,
+    TokenHash,
+    FamilyId,
+    Device,
+    UserAgent,
+    ExpireTime,
+    UsedTime,
+    RevokeTime,
+    CreateTime,
+    UpdateTime,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum TokenDenyList {
+    Table,
+    Id,
+    TokenId,
+    UserId,
+    Reason,
+    ExpireTime,
+    CreateTime,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum AuditLog {
+    Table,
+    Id,
+    ActorId,
+    ActorCode,
+    Ip,
+    Entity,
+    Action,
+    RecIds,
+    BeforeData,
+    AfterData,
+    DiffData,
+    OrgCode,
+    CreateTime,
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// Row-level data scope of a role, see `pojo::role_pojo::DATA_SCOPES`
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Role::Table)
+                    .add_column_if_not_exists(string_len_null(Role::DataScope, 32))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(Table::alter().table(Role::Table).drop_column(Role::DataScope).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Role {
+    Table,
+    DataScope,
+}
@@ -0,0 +1,172 @@
+use sea_orm::DatabaseConnection;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+use sea_orm_migration::MigrationStatus;
+
+mod m20261017_000001_create_organization_tables;
+mod m20261017_000002_create_role_tables;
+mod m20261017_000003_create_user_tables;
+mod m20261017_000004_create_ref_tables;
+mod m20261017_000005_create_system_tables;
+mod m20261017_000006_add_role_data_scope;
+
+/// Schema of the auth center, applied in order and recorded in `seaql_migrations`
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20261017_000001_create_organization_tables::Migration),
+            Box::new(m20261017_000002_create_role_tables::Migration),
+            Box::new(m20261017_000003_create_user_tables::Migration),
+            Box::new(m20261017_000004_create_ref_tables::Migration),
+            Box::new(m20261017_000005_create_system_tables::Migration),
+            Box::new(m20261017_000006_add_role_data_scope::Migration),
+        ]
+    }
+}
+
+/// A `migrate` sub command of the binary
+#[derive(Debug, PartialEq, Eq)]
+pub enum MigrateCommand {
+    /// Applies the pending migrations, all of them or the next `n`
+    Up(Option<u32>),
+    /// Rolls back the applied migrations, the last one or the last `n`
+    Down(Option<u32>),
+    /// Lists every migration with its status
+    Status,
+}
+
+impl MigrateCommand {
+    /// Parses the arguments following `migrate`, e.g. `up`, `down 2` or `status`
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let steps = match args.get(1) {
+            Some(steps) => Some(steps.parse::<u32>().map_err(|_| format!("invalid number of steps: {}", steps))?),
+            None => None,
+        };
+        match (args.first().map(String::as_str), steps) {
+            (Some("up"), steps) => Ok(MigrateCommand::Up(steps)),
+            (Some("down"), steps) => Ok(MigrateCommand::Down(steps.or(Some(1)))),
+            (Some("status"), None) => Ok(MigrateCommand::Status),
+            _ => Err("usage: migrate <up [n] | down [n] | status>".to_string()),
+        }
+    }
+
+    /// Runs the command against `db`, `status` prints one line per migration
+    pub async fn run(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        match self {
+            MigrateCommand::Up(steps) => Migrator::up(db, *steps).await,
+            MigrateCommand::Down(steps) => Migrator::down(db, *steps).await,
+            MigrateCommand::Status => {
+                for migration in Migrator::get_migration_with_status(db).await? {
+                    let status = match migration.status() {
+                        MigrationStatus::Applied => "applied",
+                        MigrationStatus::Pending => "pending",
+                    };
+                    println!("{:<8} {}", status, migration.name());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Columns shared by the business tables: the soft delete flag, the audit columns
+/// stamped by `util::audit` and, on versioned tables, the `rec_sign` optimistic lock
+#[derive(DeriveIden)]
+enum Audit {
+    IsDel,
+    CreateTime,
+    UpdateTime,
+    CreateBy,
+    UpdateBy,
+    RecSign,
+}
+
+/// `id` as an auto increment `bigint` primary key
+fn id<T: IntoIden>(column: T) -> ColumnDef {
+    big_integer(column).auto_increment().primary_key().take()
+}
+
+/// A business code, e.g. `org_code` or `role_code`
+fn code<T: IntoIden>(column: T) -> ColumnDef {
+    string_len(column, 64)
+}
+
+/// A nullable business code
+fn code_null<T: IntoIden>(column: T) -> ColumnDef {
+    string_len_null(column, 64)
+}
+
+/// Materialised path of a tree node, the codes of its ancestors and its own
+fn path<T: IntoIden>(column: T) -> ColumnDef {
+    string_len_null(column, 1024)
+}
+
+/// Creation time, filled by the database when the row is inserted without one
+fn create_time<T: IntoIden>(column: T) -> ColumnDef {
+    date_time_null(column).default(Expr::current_timestamp()).take()
+}
+
+/// Appends the columns of [`Audit`] to `table`, `rec_sign` only if `versioned`
+fn audit_columns(table: &mut TableCreateStatement, versioned: bool) -> &mut TableCreateStatement {
+    table
+        .col(tiny_integer_null(Audit::IsDel).default(0))
+        .col(create_time(Audit::CreateTime))
+        .col(date_time_null(Audit::UpdateTime))
+        .col(big_integer_null(Audit::CreateBy).default(0))
+        .col(big_integer_null(Audit::UpdateBy).default(0));
+    if versioned {
+        table.col(string_len_null(Audit::RecSign, 64));
+    }
+    table
+}
+
+/// Creates index `idx_{table}_{columns}` over `columns` of `table`, unless a database set up
+/// before the migrations existed already has it
+async fn create_index<T, C>(manager: &SchemaManager<'_>, table: T, columns: &[C]) -> Result<(), DbErr>
+where
+    T: IntoIden + Copy + 'static,
+    C: IntoIden + Copy + 'static,
+{
+    create_named_index(manager, "idx", table, columns, false).await
+}
+
+/// Creates unique index `uk_{table}_{columns}` over `columns` of `table`, unless it exists
+async fn create_unique_index<T, C>(manager: &SchemaManager<'_>, table: T, columns: &[C]) -> Result<(), DbErr>
+where
+    T: IntoIden + Copy + 'static,
+    C: IntoIden + Copy + 'static,
+{
+    create_named_index(manager, "uk", table, columns, true).await
+}
+
+async fn create_named_index<T, C>(
+    manager: &SchemaManager<'_>,
+    prefix: &str,
+    table: T,
+    columns: &[C],
+    unique: bool,
+) -> Result<(), DbErr>
+where
+    T: IntoIden + Copy + 'static,
+    C: IntoIden + Copy + 'static,
+{
+    let table_name = table.into_iden().to_string();
+    let mut name = format!("{}_{}", prefix, table_name);
+    let mut index = Index::create();
+    index.table(table);
+    for column in columns {
+        name.push('_');
+        name.push_str(&column.into_iden().to_string());
+        index.col(*column);
+    }
+    if manager.has_index(&table_name, &name).await? {
+        return Ok(());
+    }
+    if unique {
+        index.unique();
+    }
+    manager.create_index(index.name(name).take()).await
+}
@@ -0,0 +1,71 @@
+use chrono::NaiveDateTime;
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+
+use crate::util::date_format;
+use crate::util::paged_struct::Pageable;
+
+/// Kind of mutation recorded in `audit_log.action`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Save,
+    Update,
+    Delete,
+    Remove,
+    /// A node re-parented together with its subtree
+    Move,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Save => "save",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Remove => "remove",
+            AuditAction::Move => "move",
+        }
+    }
+}
+
+#[derive(FromQueryResult, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogVo {
+    pub id: Option<i64>,
+    pub actor_id: Option<i64>,
+    pub actor_code: Option<String>,
+    pub ip: Option<String>,
+    pub entity: Option<String>,
+    pub action: Option<String>,
+    pub rec_ids: Option<serde_json::Value>,
+    pub before_data: Option<serde_json::Value>,
+    pub after_data: Option<serde_json::Value>,
+    pub diff_data: Option<serde_json::Value>,
+    pub org_code: Option<String>,
+    #[serde(with = "date_format")]
+    pub create_time: Option<chrono::DateTime<chrono::Local>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogCondition {
+    pub actor_id: Option<i64>,
+    pub actor_code: Option<String>,
+    /// Table name of the audited entity, e.g. `user_role_ref`
+    pub entity: Option<String>,
+    pub action: Option<String>,
+    pub org_code: Option<String>,
+    pub start_time: Option<NaiveDateTime>,
+    pub end_time: Option<NaiveDateTime>,
+    pub page: Option<u64>,
+    pub size: Option<u64>,
+}
+
+impl Pageable for AuditLogCondition {
+    fn get_page(&self) -> Option<u64> {
+        self.page.or(Some(1))
+    }
+    fn get_size(&self) -> Option<u64> {
+        self.size.or(Some(20))
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::pojo::user_pojo::PASSWORD_MIN_LEN;
+use crate::util::date_format;
+use crate::util::validation::{Validate, Validator};
+
+/// Claims carried by the access tokens issued by `/auth/login`
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Claims {
+    pub iss: String,
+    /// `user.id` of the authenticated account
+    pub sub: String,
+    pub uid: i64,
+    pub user_code: Option<String>,
+    pub org_code: Option<String>,
+    pub roles: Vec<String>,
+    pub is_admin: bool,
+    pub iat: i64,
+    pub exp: i64,
+    pub jti: String,
+    /// Session (refresh token family) the token was issued for
+    pub sid: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginDto {
+    pub username: String,
+    pub password: String,
+    /// Client supplied device name, e.g. `web` or `mini-program`
+    pub device: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginVo {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub refresh_token: String,
+    pub refresh_expires_in: i64,
+    pub user_code: Option<String>,
+    pub org_code: Option<String>,
+    pub roles: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordDto {
+    pub username: String,
+    pub old_password: String,
+    pub new_password: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshDto {
+    pub refresh_token: String,
+    pub device: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionVo {
+    pub session_id: String,
+    pub device: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(with = "date_format")]
+    pub last_active_time: Option<chrono::DateTime<chrono::Local>>,
+    #[serde(with = "date_format")]
+    pub expire_time: Option<chrono::DateTime<chrono::Local>>,
+    /// Whether this is the session of the calling access token
+    pub current: bool,
+}
+
+impl Validate for LoginDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("username", Some(self.username.as_str())).required("用户名").max_len(64);
+        v.field("password", Some(self.password.as_str())).required("密码");
+        v.field("device", self.device.as_deref()).max_len(64);
+    }
+}
+
+impl Validate for ChangePasswordDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("username", Some(self.username.as_str())).required("用户名").max_len(64);
+        v.field("oldPassword", Some(self.old_password.as_str())).required("原密码");
+        v.field("newPassword", Some(self.new_password.as_str()))
+            .required("新密码")
+            .min_len(PASSWORD_MIN_LEN)
+            .max_len(128);
+    }
+}
+
+impl Validate for RefreshDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("refreshToken", Some(self.refresh_token.as_str())).required("刷新令牌");
+        v.field("device", self.device.as_deref()).max_len(64);
+    }
+}
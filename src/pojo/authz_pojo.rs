@@ -0,0 +1,181 @@
+use sea_orm::{ColumnTrait, Condition};
+use serde::{Deserialize, Serialize};
+
+use crate::pojo::role_pojo::DataScope;
+use crate::util::validation::{Validate, Validator};
+
+/// Role binding table a role was granted through
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum GrantSourceType {
+    User,
+    Group,
+    Department,
+    Position,
+    Organization,
+}
+
+/// One path through which a role reaches a user, e.g. `group` + the group code
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub struct GrantSourceVo {
+    pub source_type: GrantSourceType,
+    pub source_code: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleGrantVo {
+    pub role_code: String,
+    pub role_name: Option<String>,
+    pub application: Option<String>,
+    pub sources: Vec<GrantSourceVo>,
+    /// Codes of the ancestor roles whose permissions this role inherits, nearest first
+    pub inherits: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionGrantVo {
+    pub id: i64,
+    pub uuid: String,
+    pub parent_uuid: Option<String>,
+    pub code: Option<String>,
+    pub name: String,
+    pub application: Option<String>,
+    pub node_type: Option<String>,
+    /// Codes of the effective roles granting this permission
+    pub granted_by: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectivePermissionsVo {
+    pub user_code: String,
+    pub org_code: Option<String>,
+    pub department_code: Option<String>,
+    pub position_code: Option<String>,
+    pub roles: Vec<RoleGrantVo>,
+    pub permissions: Vec<PermissionGrantVo>,
+}
+
+/// Subject of a permission check, either a user code or an access token
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckSubjectDto {
+    pub user_code: Option<String>,
+    /// Access token of the user, the `Authorization` header is used when both are absent
+    pub token: Option<String>,
+    /// Org the check applies to, defaults to the caller's organization or else the user's own org
+    pub org_code: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionCheckItem {
+    pub permission_code: String,
+    pub application: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionCheckDto {
+    #[serde(flatten)]
+    pub subject: CheckSubjectDto,
+    #[serde(flatten)]
+    pub check: PermissionCheckItem,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionCheckBatchDto {
+    #[serde(flatten)]
+    pub subject: CheckSubjectDto,
+    pub checks: Vec<PermissionCheckItem>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionCheckVo {
+    pub permission_code: String,
+    pub application: Option<String>,
+    pub allowed: bool,
+    /// Codes of the effective roles granting the permission, empty when denied
+    pub granted_by: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuCondition {
+    pub application: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DataScopeCondition {
+    /// Org the scope applies in, defaults to the user's own org
+    pub org_code: Option<String>,
+}
+
+/// Rows a user may see: the widest data scope among the user's effective roles
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataScopeVo {
+    pub user_code: String,
+    pub scope: DataScope,
+    /// Org the scope applies in, none for `all`
+    pub org_code: Option<String>,
+    /// `department.path` of the department the scope is rooted at, for the department scopes
+    pub department_paths: Vec<String>,
+    /// Codes of the departments the scope covers: the user's department, and for
+    /// `department_and_below` every department whose path starts with its path
+    pub department_codes: Vec<String>,
+}
+
+impl DataScopeVo {
+    /// Condition restricting a table to the rows of the scope, given the columns
+    /// holding the org, the department and the user of a row
+    pub fn condition<C: ColumnTrait>(&self, org_code: C, department_code: C, user_code: C) -> Condition {
+        match self.scope {
+            DataScope::All => Condition::all(),
+            DataScope::Org => Condition::all().add(org_code.eq(self.org_code.clone())),
+            DataScope::Department | DataScope::DepartmentAndBelow => Condition::all()
+                .add(org_code.eq(self.org_code.clone()))
+                .add(department_code.is_in(self.department_codes.clone())),
+            DataScope::Own => Condition::all().add(user_code.eq(self.user_code.clone())),
+        }
+    }
+}
+
+impl Validate for CheckSubjectDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("userCode", self.user_code.as_deref()).code();
+        v.field("orgCode", self.org_code.as_deref()).code();
+    }
+}
+
+impl PermissionCheckItem {
+    /// Rules of a check, its fields named below `prefix`
+    fn validate_at(&self, v: &mut Validator, prefix: &str) {
+        v.field(format!("{}permissionCode", prefix), Some(self.permission_code.as_str()))
+            .required("权限编码")
+            .max_len(128);
+        v.field(format!("{}application", prefix), self.application.as_deref()).code();
+    }
+}
+
+impl Validate for PermissionCheckDto {
+    fn validate(&self, v: &mut Validator) {
+        self.subject.validate(v);
+        self.check.validate_at(v, "");
+    }
+}
+
+impl Validate for PermissionCheckBatchDto {
+    fn validate(&self, v: &mut Validator) {
+        self.subject.validate(v);
+        for (index, check) in self.checks.iter().enumerate() {
+            check.validate_at(v, &format!("checks[{}].", index));
+        }
+    }
+}
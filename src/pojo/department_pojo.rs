@@ -3,8 +3,10 @@ use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+use crate::pojo::position_pojo::PositionVo;
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
 #[derive(FromQueryResult, Serialize)]
@@ -19,7 +21,7 @@ pub struct DepartmentVo {
     pub extra: Option<serde_json::Value>,
     pub remark: Option<String>,
     pub path: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -29,6 +31,19 @@ pub struct DepartmentVo {
     pub rec_sign: Option<String>,
 }
 
+/// A node of the department tree of an organization
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepartmentTreeVo {
+    #[serde(flatten)]
+    pub department: DepartmentVo,
+    /// Members assigned to the department itself
+    pub direct_headcount: u64,
+    /// Members of the department and of every department below it
+    pub headcount: u64,
+    pub positions: Vec<PositionVo>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DepartmentCondition {
@@ -78,6 +93,8 @@ pub struct DepartmentDto {
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub update_time: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rec_sign: Option<String>,
 }
 
 impl IntoJsonValue for DepartmentDto {
@@ -95,4 +112,17 @@ impl IntoJsonValue for DepartmentDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for DepartmentDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("code", self.code.as_deref()).required("部门编码").code();
+        v.field("parentCode", self.parent_code.as_deref()).code();
+        v.field("name", self.name.as_deref()).required("部门名称").max_len(255);
+        v.field("description", self.description.as_deref()).max_len(512);
+        v.field("orgCode", self.org_code.as_deref()).required("所属组织").code();
+        v.field("remark", self.remark.as_deref()).max_len(512);
+        v.field("path", self.path.as_deref()).max_len(1024);
+        v.ensure("parentCode", self.parent_code.is_none() || self.parent_code != self.code, "parentCode 不能与 code 相同！");
+    }
+}
@@ -5,6 +5,7 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
 #[derive(FromQueryResult, Serialize)]
@@ -14,7 +15,7 @@ pub struct DepartmentRoleRefVo {
     pub department_code: Option<String>,
     pub role_code: Option<String>,
     pub org_code: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -78,4 +79,12 @@ impl IntoJsonValue for DepartmentRoleRefDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for DepartmentRoleRefDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("departmentCode", self.department_code.as_deref()).required("部门编码").code();
+        v.field("roleCode", self.role_code.as_deref()).required("角色编码").code();
+        v.field("orgCode", self.org_code.as_deref()).code();
+    }
+}
@@ -1,3 +1,6 @@
+pub mod audit_log_pojo;
+pub mod auth_pojo;
+pub mod authz_pojo;
 pub mod department_pojo;
 pub mod department_role_ref_pojo;
 pub mod group_pojo;
@@ -9,11 +12,15 @@ pub mod position_pojo;
 pub mod position_role_ref_pojo;
 pub mod role_pojo;
 pub mod system_config_pojo;
+pub mod tree_pojo;
 pub mod user_group_ref_pojo;
 pub mod user_info_pojo;
 pub mod user_role_ref_pojo;
 pub mod user_wechat_info_pojo;
 pub mod user_pojo;
+pub use audit_log_pojo::*;
+pub use auth_pojo::*;
+pub use authz_pojo::*;
 pub use department_pojo::*;
 pub use department_role_ref_pojo::*;
 pub use group_pojo::*;
@@ -25,6 +32,7 @@ pub use position_pojo::*;
 pub use position_role_ref_pojo::*;
 pub use role_pojo::*;
 pub use system_config_pojo::*;
+pub use tree_pojo::*;
 pub use user_group_ref_pojo::*;
 pub use user_info_pojo::*;
 pub use user_role_ref_pojo::*;
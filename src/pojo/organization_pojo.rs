@@ -5,6 +5,7 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
 #[derive(FromQueryResult, Serialize)]
@@ -27,7 +28,7 @@ pub struct OrganizationVo {
     pub extra: Option<serde_json::Value>,
     pub remark: Option<String>,
     pub path: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -66,6 +67,13 @@ impl Pageable for OrganizationCondition {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationTreeCondition {
+    pub r#type: Option<String>,
+    pub status: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct OrganizationDto {
@@ -107,6 +115,8 @@ pub struct OrganizationDto {
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub update_time: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rec_sign: Option<String>,
 }
 
 impl IntoJsonValue for OrganizationDto {
@@ -124,4 +134,24 @@ impl IntoJsonValue for OrganizationDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for OrganizationDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("code", self.code.as_deref()).required("组织编码").code();
+        v.field("parentCode", self.parent_code.as_deref()).code();
+        v.field("name", self.name.as_deref()).required("组织名称").max_len(255);
+        v.field("type", self.r#type.as_deref()).max_len(32);
+        v.field("contacts", self.contacts.as_deref()).max_len(64);
+        v.field("cellphone", self.cellphone.as_deref()).cellphone();
+        v.field("email", self.email.as_deref()).max_len(255).email();
+        v.field("uscc", self.uscc.as_deref()).max_len(32);
+        v.field("businessLicense", self.business_license.as_deref()).max_len(512);
+        v.field("idCardFront", self.id_card_front.as_deref()).max_len(512);
+        v.field("idCardBack", self.id_card_back.as_deref()).max_len(512);
+        v.field("status", self.status.as_deref()).max_len(32);
+        v.field("remark", self.remark.as_deref()).max_len(512);
+        v.field("path", self.path.as_deref()).max_len(1024);
+        v.ensure("parentCode", self.parent_code.is_none() || self.parent_code != self.code, "parentCode 不能与 code 相同！");
+    }
+}
@@ -5,8 +5,12 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
+/// Values of `permission.node_type`
+pub const PERMISSION_NODE_TYPES: [&str; 3] = ["menu", "button", "api"];
+
 #[derive(FromQueryResult, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PermissionVo {
@@ -26,7 +30,7 @@ pub struct PermissionVo {
     pub org_code: Option<String>,
     pub extra: Option<serde_json::Value>,
     pub remark: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -64,6 +68,13 @@ impl Pageable for PermissionCondition {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionTreeCondition {
+    pub application: Option<String>,
+    pub org_code: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct PermissionDto {
@@ -120,4 +131,22 @@ impl IntoJsonValue for PermissionDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for PermissionDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("uuid", self.uuid.as_deref()).required("权限UUID").code();
+        v.field("parentUuid", self.parent_uuid.as_deref()).code();
+        v.field("code", self.code.as_deref()).max_len(128);
+        v.field("name", self.name.as_deref()).required("权限名称").max_len(255);
+        v.field("description", self.description.as_deref()).max_len(512);
+        v.field("application", self.application.as_deref()).code();
+        v.field("nodeType", self.node_type.as_deref()).one_of(&PERMISSION_NODE_TYPES);
+        v.field("linkUrl", self.link_url.as_deref()).max_len(512);
+        v.field("icoUrl", self.ico_url.as_deref()).max_len(512);
+        v.field("path", self.path.as_deref()).max_len(1024);
+        v.field("orgCode", self.org_code.as_deref()).code();
+        v.field("remark", self.remark.as_deref()).max_len(512);
+        v.ensure("parentUuid", self.parent_uuid.is_none() || self.parent_uuid != self.uuid, "parentUuid 不能与 uuid 相同！");
+    }
+}
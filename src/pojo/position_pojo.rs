@@ -5,6 +5,7 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
 #[derive(FromQueryResult, Serialize)]
@@ -20,7 +21,7 @@ pub struct PositionVo {
     pub extra: Option<serde_json::Value>,
     pub remark: Option<String>,
     pub path: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -98,4 +99,18 @@ impl IntoJsonValue for PositionDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for PositionDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("code", self.code.as_deref()).required("岗位编码").code();
+        v.field("parentCode", self.parent_code.as_deref()).code();
+        v.field("name", self.name.as_deref()).required("岗位名称").max_len(255);
+        v.field("description", self.description.as_deref()).max_len(512);
+        v.field("departmentCode", self.department_code.as_deref()).code();
+        v.field("orgCode", self.org_code.as_deref()).code();
+        v.field("remark", self.remark.as_deref()).max_len(512);
+        v.field("path", self.path.as_deref()).max_len(1024);
+        v.ensure("parentCode", self.parent_code.is_none() || self.parent_code != self.code, "parentCode 不能与 code 相同！");
+    }
+}
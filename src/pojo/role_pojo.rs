@@ -5,8 +5,42 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
+/// Values of `role.data_scope`, from the narrowest to the widest
+pub const DATA_SCOPES: [&str; 5] = ["self", "department", "department_and_below", "org", "all"];
+
+/// Rows of the business tables a role lets its holders see
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum DataScope {
+    /// The rows of the user only
+    #[serde(rename = "self")]
+    Own,
+    /// The rows of the user's department
+    Department,
+    /// The rows of the user's department and of every department below it
+    DepartmentAndBelow,
+    /// The rows of the user's organization, the scope of a role without `data_scope`
+    Org,
+    /// Every row
+    All,
+}
+
+impl DataScope {
+    pub fn parse(data_scope: &str) -> Option<Self> {
+        match data_scope {
+            "self" => Some(DataScope::Own),
+            "department" => Some(DataScope::Department),
+            "department_and_below" => Some(DataScope::DepartmentAndBelow),
+            "org" => Some(DataScope::Org),
+            "all" => Some(DataScope::All),
+            _ => None,
+        }
+    }
+}
+
 #[derive(FromQueryResult, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RoleVo {
@@ -20,9 +54,10 @@ pub struct RoleVo {
     pub org_code: Option<String>,
     pub extra: Option<serde_json::Value>,
     pub remark: Option<String>,
-    pub role_type: Option<i8>,
+    pub role_type: Option<i16>,
     pub path: Option<String>,
-    pub is_del: Option<i8>,
+    pub data_scope: Option<String>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -42,7 +77,7 @@ pub struct RoleCondition {
     pub name: Option<String>,
     pub application: Option<String>,
     pub org_code: Option<String>,
-    pub role_type: Option<i8>,
+    pub role_type: Option<i16>,
     pub create_time: Option<NaiveDateTime>,
     pub update_time: Option<NaiveDateTime>,
     pub page: Option<u64>,
@@ -58,6 +93,13 @@ impl Pageable for RoleCondition {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleTreeCondition {
+    pub application: Option<String>,
+    pub org_code: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct RoleDto {
@@ -84,11 +126,15 @@ pub struct RoleDto {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remark: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub role_type: Option<i8>,
+    pub role_type: Option<i16>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub update_time: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rec_sign: Option<String>,
 }
 
 impl IntoJsonValue for RoleDto {
@@ -106,4 +152,18 @@ impl IntoJsonValue for RoleDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for RoleDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("code", self.code.as_deref()).required("角色编码").code();
+        v.field("parentCode", self.parent_code.as_deref()).code();
+        v.field("name", self.name.as_deref()).required("角色名称").max_len(255);
+        v.field("description", self.description.as_deref()).max_len(512);
+        v.field("application", self.application.as_deref()).code();
+        v.field("orgCode", self.org_code.as_deref()).code();
+        v.field("remark", self.remark.as_deref()).max_len(512);
+        v.field("dataScope", self.data_scope.as_deref()).one_of(&DATA_SCOPES);
+        v.ensure("parentCode", self.parent_code.is_none() || self.parent_code != self.code, "parentCode 不能与 code 相同！");
+    }
+}
@@ -5,8 +5,12 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
+/// Values of `system_config.status`
+pub const CONFIG_STATUSES: [&str; 2] = ["enabled", "disabled"];
+
 #[derive(FromQueryResult, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SystemConfigVo {
@@ -17,7 +21,7 @@ pub struct SystemConfigVo {
     pub description: Option<String>,
     pub status: Option<String>,
     pub org_code: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -89,4 +93,14 @@ impl IntoJsonValue for SystemConfigDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for SystemConfigDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("configKey", self.config_key.as_deref()).required("配置项").max_len(128);
+        v.field("configType", self.config_type.as_deref()).max_len(32);
+        v.field("description", self.description.as_deref()).max_len(512);
+        v.field("status", self.status.as_deref()).required("配置状态").one_of(&CONFIG_STATUSES);
+        v.field("orgCode", self.org_code.as_deref()).code();
+    }
+}
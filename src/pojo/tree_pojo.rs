@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::validation::{Validate, Validator};
+
+/// New place of a node in its hierarchy
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveDto {
+    /// Code of the new parent, the node becomes a root node without one
+    pub parent_code: Option<String>,
+    /// `rec_sign` of the node as the caller read it
+    pub rec_sign: Option<String>,
+}
+
+impl Validate for MoveDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("parentCode", self.parent_code.as_deref()).code();
+    }
+}
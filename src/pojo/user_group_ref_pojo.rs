@@ -5,6 +5,7 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
 #[derive(FromQueryResult, Serialize)]
@@ -14,7 +15,7 @@ pub struct UserGroupRefVo {
     pub user_code: Option<String>,
     pub group_code: Option<String>,
     pub org_code: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -78,4 +79,12 @@ impl IntoJsonValue for UserGroupRefDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for UserGroupRefDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("userCode", self.user_code.as_deref()).required("用户编码").code();
+        v.field("groupCode", self.group_code.as_deref()).required("用户组编码").code();
+        v.field("orgCode", self.org_code.as_deref()).code();
+    }
+}
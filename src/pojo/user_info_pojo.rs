@@ -3,9 +3,15 @@ use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+use crate::pojo::authz_pojo::DataScopeVo;
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
+use crate::util::desensitize::Desensitize;
+
+/// Values of `user_info.user_type`: employees, people outside the organization and service accounts
+pub const USER_TYPES: [&str; 3] = ["staff", "external", "system"];
 
 #[derive(FromQueryResult, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -22,13 +28,16 @@ pub struct UserInfoVo {
     pub portrait: Option<String>,
     pub user_type: Option<String>,
     pub birthday: Option<chrono::NaiveDate>,
-    pub password: Option<String>,
     pub id_card_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id_card_no: Option<String>,
     pub email: Option<String>,
     pub qq: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wx_union_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wx_open_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wx_mini_open_id: Option<String>,
     pub address: Option<String>,
     pub parent_code: Option<String>,
@@ -41,7 +50,7 @@ pub struct UserInfoVo {
     pub position_code: Option<String>,
     pub extra: Option<serde_json::Value>,
     pub remark: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -51,6 +60,15 @@ pub struct UserInfoVo {
     pub rec_sign: Option<String>,
 }
 
+impl Desensitize for UserInfoVo {
+    fn desensitize(&mut self) {
+        self.id_card_no = None;
+        self.wx_union_id = None;
+        self.wx_open_id = None;
+        self.wx_mini_open_id = None;
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UserInfoCondition {
@@ -80,6 +98,9 @@ pub struct UserInfoCondition {
     pub update_time: Option<NaiveDateTime>,
     pub page: Option<u64>,
     pub size: Option<u64>,
+    /// Data scope of the caller, set by the service and never taken from the query string
+    #[serde(skip)]
+    pub data_scope: Option<DataScopeVo>,
 }
 
 impl Pageable for UserInfoCondition {
@@ -160,6 +181,8 @@ pub struct UserInfoDto {
     pub remark: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub update_time: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rec_sign: Option<String>,
 }
 
 impl IntoJsonValue for UserInfoDto {
@@ -177,4 +200,43 @@ impl IntoJsonValue for UserInfoDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for UserInfoDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("userCode", self.user_code.as_deref()).required("用户编码").code();
+        v.field("username", self.username.as_deref()).max_len(64);
+        v.field("loginStatus", self.login_status.as_deref()).max_len(32);
+        v.field("englishName", self.english_name.as_deref()).max_len(64);
+        v.field("realName", self.real_name.as_deref()).required("真实姓名").max_len(64);
+        v.field("nickName", self.nick_name.as_deref()).max_len(64);
+        v.field("cellphone", self.cellphone.as_deref()).cellphone();
+        v.field("gender", self.gender.as_deref()).max_len(16);
+        v.field("portrait", self.portrait.as_deref()).max_len(512);
+        v.field("userType", self.user_type.as_deref()).required("用户类型").one_of(&USER_TYPES);
+        v.field("idCardType", self.id_card_type.as_deref()).max_len(32);
+        v.field("idCardNo", self.id_card_no.as_deref()).max_len(64);
+        v.field("email", self.email.as_deref()).max_len(255).email();
+        v.field("qq", self.qq.as_deref()).max_len(32);
+        v.field("wxUnionId", self.wx_union_id.as_deref()).max_len(128);
+        v.field("wxOpenId", self.wx_open_id.as_deref()).max_len(128);
+        v.field("wxMiniOpenId", self.wx_mini_open_id.as_deref()).max_len(128);
+        v.field("address", self.address.as_deref()).max_len(512);
+        v.field("parentCode", self.parent_code.as_deref()).code();
+        v.field("path", self.path.as_deref()).max_len(1024);
+        v.field("status", self.status.as_deref()).max_len(32);
+        v.field("orgCode", self.org_code.as_deref()).code();
+        v.field("departmentCode", self.department_code.as_deref()).code();
+        v.field("positionCode", self.position_code.as_deref()).code();
+        v.field("remark", self.remark.as_deref()).max_len(512);
+        v.ensure("parentCode", self.parent_code.is_none() || self.parent_code != self.user_code, "parentCode 不能与 userCode 相同！");
+        v.ensure(
+            "birthday",
+            self.birthday.is_none_or(|birthday| birthday <= chrono::Local::now().date_naive()),
+            "出生日期不能晚于今天！",
+        );
+        if let (Some(effective_date), Some(invalid_date)) = (self.effective_date, self.invalid_date) {
+            v.ensure("invalidDate", invalid_date >= effective_date, "失效日期不能早于生效日期！");
+        }
+    }
+}
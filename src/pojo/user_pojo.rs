@@ -6,7 +6,18 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
+use crate::util::desensitize::Desensitize;
+
+/// `user.status` of an account which is allowed to sign in
+pub const USER_STATUS_NORMAL: i16 = 0;
+
+/// `user.type` of an account created without one
+pub const USER_TYPE_DEFAULT: i16 = 0;
+
+/// Shortest password accepted for an account
+pub const PASSWORD_MIN_LEN: usize = 6;
 
 #[derive(FromQueryResult, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,14 +25,13 @@ pub struct UserVo {
     pub id: Option<i64>,
     pub user_name: Option<String>,
     pub real_name: Option<String>,
-    pub password: Option<String>,
-    pub r#type: Option<i8>,
-    pub status: Option<i8>,
+    pub r#type: Option<i16>,
+    pub status: Option<i16>,
     pub email: Option<String>,
     pub area_code: Option<String>,
     pub phone: Option<String>,
     pub remark: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     pub head_pic: Option<String>,
     #[serde(with = "date_format")]
     pub create_time: Option<DateTimeLocal>,
@@ -29,13 +39,24 @@ pub struct UserVo {
     pub update_time: Option<DateTimeLocal>,
     pub create_by: Option<i64>,
     pub update_by: Option<i64>,
-    pub is_admin: Option<i8>,
+    pub is_admin: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub open_id: Option<String>,
     pub last_login_time: Option<DateTimeLocal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wechat_open_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wechat_union_id: Option<String>,
 }
 
+impl Desensitize for UserVo {
+    fn desensitize(&mut self) {
+        self.open_id = None;
+        self.wechat_open_id = None;
+        self.wechat_union_id = None;
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UserCondition {
@@ -43,8 +64,8 @@ pub struct UserCondition {
     pub ids: Option<Vec<i64>>,
     pub user_name: Option<String>,
     pub real_name: Option<String>,
-    pub r#type: Option<i8>,
-    pub status: Option<i8>,
+    pub r#type: Option<i16>,
+    pub status: Option<i16>,
     pub email: Option<String>,
     pub area_code: Option<String>,
     pub phone: Option<String>,
@@ -60,18 +81,10 @@ pub struct UserCondition {
 
 impl Pageable for UserCondition {
     fn get_page(&self) -> Option<u64> {
-        return if let Some(page) = self.page {
-            Some(page)
-        } else {
-            Some(1)
-        };
+        self.page.or(Some(1))
     }
     fn get_size(&self) -> Option<u64> {
-        return if let Some(size) = self.size {
-            Some(size)
-        } else {
-            Some(20)
-        };
+        self.size.or(Some(20))
     }
 }
 
@@ -86,12 +99,12 @@ pub struct UserDto {
     pub user_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub real_name: Option<String>,
-    #[serde(default = "default_resource")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub r#type: Option<i8>,
-    #[serde(default = "default_resource")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<i16>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub status: Option<i8>,
+    pub status: Option<i16>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -110,10 +123,6 @@ pub struct UserDto {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wechat_union_id: Option<String>,
 }
-// serde field default value
-fn default_resource() -> Option<i8> {
-    Some(0_i8)
-}
 
 impl IntoJsonValue for UserDto {
     /**
@@ -134,3 +143,18 @@ impl IntoJsonValue for UserDto {
         Value::Object(json_object)
     }
 }
+
+impl Validate for UserDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("userName", self.user_name.as_deref()).required("用户名").max_len(64);
+        v.field("realName", self.real_name.as_deref()).max_len(64);
+        v.field("password", self.password.as_deref()).min_len(PASSWORD_MIN_LEN).max_len(128);
+        v.field("email", self.email.as_deref()).max_len(255).email();
+        v.field("areaCode", self.area_code.as_deref()).max_len(16);
+        v.field("phone", self.phone.as_deref()).max_len(32);
+        v.field("remark", self.remark.as_deref()).max_len(512);
+        v.field("openId", self.open_id.as_deref()).max_len(128);
+        v.field("wechatOpenId", self.wechat_open_id.as_deref()).max_len(128);
+        v.field("wechatUnionId", self.wechat_union_id.as_deref()).max_len(128);
+    }
+}
@@ -5,6 +5,7 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
 
 #[derive(FromQueryResult, Serialize)]
@@ -14,13 +15,14 @@ pub struct UserRoleRefVo {
     pub user_code: Option<String>,
     pub role_code: Option<String>,
     pub org_code: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
     pub update_time: Option<chrono::DateTime<chrono::Local>>,
     pub create_by: Option<i64>,
     pub update_by: Option<i64>,
+    pub rec_sign: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -61,6 +63,8 @@ pub struct UserRoleRefDto {
     pub org_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub update_time: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rec_sign: Option<String>,
 }
 
 impl IntoJsonValue for UserRoleRefDto {
@@ -78,4 +82,12 @@ impl IntoJsonValue for UserRoleRefDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for UserRoleRefDto {
+    fn validate(&self, v: &mut Validator) {
+        v.field("userCode", self.user_code.as_deref()).required("用户编码").code();
+        v.field("roleCode", self.role_code.as_deref()).required("角色编码").code();
+        v.field("orgCode", self.org_code.as_deref()).code();
+    }
+}
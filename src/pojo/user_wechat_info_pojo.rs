@@ -5,18 +5,23 @@ use serde_json::{json, Value};
 
 use crate::util::paged_struct::Pageable;
 use crate::util::{common_func, IntoJsonValue};
+use crate::util::validation::{Validate, Validator};
 use crate::util::date_format;
+use crate::util::desensitize::Desensitize;
 
 #[derive(FromQueryResult, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserWechatInfoVo {
     pub id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub union_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wechat_open_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mini_open_id: Option<String>,
     pub nickname: Option<String>,
     pub language: Option<String>,
-    pub subscribe: Option<i8>,
+    pub subscribe: Option<i16>,
     pub head_img_url: Option<String>,
     pub subscribe_time: Option<i64>,
     pub remark: Option<String>,
@@ -27,7 +32,7 @@ pub struct UserWechatInfoVo {
     pub qr_scene_str: Option<String>,
     pub app_id: Option<String>,
     pub app_type: Option<String>,
-    pub is_del: Option<i8>,
+    pub is_del: Option<i16>,
     #[serde(with = "date_format")]
     pub create_time: Option<chrono::DateTime<chrono::Local>>,
     #[serde(with = "date_format")]
@@ -36,6 +41,14 @@ pub struct UserWechatInfoVo {
     pub update_by: Option<i64>,
 }
 
+impl Desensitize for UserWechatInfoVo {
+    fn desensitize(&mut self) {
+        self.union_id = None;
+        self.wechat_open_id = None;
+        self.mini_open_id = None;
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UserWechatInfoCondition {
@@ -82,7 +95,7 @@ pub struct UserWechatInfoDto {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub subscribe: Option<i8>,
+    pub subscribe: Option<i16>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub head_img_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -122,4 +135,25 @@ impl IntoJsonValue for UserWechatInfoDto {
         }
         Value::Object(json_object)
     }
-} 
\ No newline at end of file
+}
+
+impl Validate for UserWechatInfoDto {
+    fn validate(&self, v: &mut Validator) {
+        // the id is the one assigned by WeChat, never generated
+        let partial = v.is_partial();
+        v.ensure("recId", partial || self.rec_id.is_some(), "微信用户ID不能为空！");
+        v.field("unionId", self.union_id.as_deref()).max_len(128);
+        v.field("wechatOpenId", self.wechat_open_id.as_deref()).max_len(128);
+        v.field("miniOpenId", self.mini_open_id.as_deref()).max_len(128);
+        v.field("nickname", self.nickname.as_deref()).max_len(128);
+        v.field("language", self.language.as_deref()).max_len(32);
+        v.field("headImgUrl", self.head_img_url.as_deref()).max_len(512);
+        v.field("remark", self.remark.as_deref()).max_len(512);
+        v.field("subscribeScene", self.subscribe_scene.as_deref()).max_len(64);
+        v.field("qrScene", self.qr_scene.as_deref()).max_len(64);
+        v.field("qrSceneStr", self.qr_scene_str.as_deref()).max_len(128);
+        v.field("appId", self.app_id.as_deref()).max_len(64);
+        v.field("appType", self.app_type.as_deref()).max_len(32);
+        v.ensure("subscribe", self.subscribe.is_none_or(|subscribe| matches!(subscribe, 0 | 1)), "subscribe 必须是 0 或 1！");
+    }
+}
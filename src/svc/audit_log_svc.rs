@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use crate::{mapper::audit_log_mapper::AuditLogMapperTrait, pojo::audit_log_pojo::*, util::paged_struct::PageData};
+use sea_orm::DbErr;
+
+pub struct AuditLogSvc {
+    mapper: Arc<dyn AuditLogMapperTrait>,
+}
+
+impl AuditLogSvc {
+    pub fn new(mapper: Arc<dyn AuditLogMapperTrait>) -> Self {
+        Self { mapper }
+    }
+
+    pub async fn page(&self, condition: AuditLogCondition) -> Result<PageData<AuditLogVo>, DbErr> {
+        self.mapper.page(condition).await
+    }
+}
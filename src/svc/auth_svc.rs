@@ -0,0 +1,342 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Duration;
+use sea_orm::ActiveValue::Set;
+use tracing::{info, warn};
+
+use crate::{
+    entities::{refresh_token, user, user_info},
+    mapper::{
+        refresh_token_mapper::RefreshTokenMapperTrait,
+        token_deny_list_mapper::TokenDenyListMapperTrait,
+        user_info_mapper::UserInfoMapperTrait,
+        user_mapper::UserMapperTrait,
+    },
+    pojo::{auth_pojo::*, user_pojo::USER_STATUS_NORMAL},
+    svc::authz_svc::AuthzSvc,
+    util::{common_func, exception::AppError, jwt::JwtKeys, password},
+};
+
+/// Row holding the password of an account
+enum Credential {
+    User(i64),
+    UserInfo(i64),
+}
+
+pub struct AuthSvc {
+    user_mapper: Arc<dyn UserMapperTrait>,
+    user_info_mapper: Arc<dyn UserInfoMapperTrait>,
+    refresh_token_mapper: Arc<dyn RefreshTokenMapperTrait>,
+    token_deny_list_mapper: Arc<dyn TokenDenyListMapperTrait>,
+    authz_svc: Arc<AuthzSvc>,
+    jwt_keys: Arc<JwtKeys>,
+}
+
+impl AuthSvc {
+    pub fn new(
+        user_mapper: Arc<dyn UserMapperTrait>,
+        user_info_mapper: Arc<dyn UserInfoMapperTrait>,
+        refresh_token_mapper: Arc<dyn RefreshTokenMapperTrait>,
+        token_deny_list_mapper: Arc<dyn TokenDenyListMapperTrait>,
+        authz_svc: Arc<AuthzSvc>,
+        jwt_keys: Arc<JwtKeys>,
+    ) -> Self {
+        Self {
+            user_mapper,
+            user_info_mapper,
+            refresh_token_mapper,
+            token_deny_list_mapper,
+            authz_svc,
+            jwt_keys,
+        }
+    }
+
+    /// Authenticates `user.user_name` + password and opens a new session.
+    ///
+    /// The `user` row is the account; the `user_info` row sharing the same
+    /// username supplies `user_code`/`org_code`, and its password is used when
+    /// the account itself has none. Plaintext or outdated hashes are rehashed
+    /// with the current parameters after a successful login.
+    pub async fn login(&self, login_dto: LoginDto, user_agent: Option<String>) -> Result<LoginVo> {
+        let (user, user_info) = self
+            .authenticate(&login_dto.username, &login_dto.password)
+            .await?;
+        Self::ensure_active(&user)?;
+        self.user_mapper.update_last_login_time(user.id).await?;
+        info!("user {} logged in", user.id);
+
+        let family_id = common_func::random_hex(16);
+        self.issue_tokens(&user, user_info.as_ref(), &family_id, login_dto.device, user_agent)
+            .await
+    }
+
+    /// Exchanges a refresh token for a new access/refresh token pair.
+    ///
+    /// Every refresh token is single use. Presenting one that was already
+    /// rotated means it leaked, so the whole session (token family) is revoked.
+    pub async fn refresh(&self, refresh_dto: RefreshDto, user_agent: Option<String>) -> Result<LoginVo> {
+        let invalid = || AppError::Unauthorized("无效的刷新令牌！".to_string());
+        let token_hash = common_func::sha256_hex(&refresh_dto.refresh_token);
+        let refresh_token = self
+            .refresh_token_mapper
+            .get_by_token_hash(&token_hash)
+            .await?
+            .ok_or_else(invalid)?;
+        if refresh_token.revoke_time.is_some() {
+            return Err(invalid().into());
+        }
+        if refresh_token.used_time.is_some()
+            || self.refresh_token_mapper.mark_used(refresh_token.id).await? == 0
+        {
+            warn!(
+                "refresh token reuse detected, revoking session {} of user {}",
+                refresh_token.family_id, refresh_token.user_id
+            );
+            self.refresh_token_mapper.revoke_family(&refresh_token.family_id).await?;
+            return Err(invalid().into());
+        }
+        if refresh_token.expire_time < chrono::Local::now().naive_local() {
+            return Err(invalid().into());
+        }
+
+        let user = self
+            .user_mapper
+            .get_account_by_id(refresh_token.user_id)
+            .await?
+            .ok_or_else(invalid)?;
+        Self::ensure_active(&user)?;
+        let user_info = self.user_info_mapper.get_by_username(&user.user_name).await?;
+        let device = refresh_dto.device.or(refresh_token.device);
+        self.issue_tokens(&user, user_info.as_ref(), &refresh_token.family_id, device, user_agent)
+            .await
+    }
+
+    fn ensure_active(user: &user::Model) -> Result<()> {
+        if user.status.unwrap_or(USER_STATUS_NORMAL) != USER_STATUS_NORMAL {
+            return Err(AppError::Unauthorized("账号已被停用！".to_string()).into());
+        }
+        Ok(())
+    }
+
+    /// Signs an access token and stores a fresh refresh token for session `family_id`
+    async fn issue_tokens(
+        &self,
+        user: &user::Model,
+        user_info: Option<&user_info::Model>,
+        family_id: &str,
+        device: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<LoginVo> {
+        let user_code = user_info.and_then(|info| info.user_code.clone());
+        let org_code = user_info.and_then(|info| info.org_code.clone());
+        let roles = match &user_code {
+            Some(user_code) => self.authz_svc.effective_role_codes(user_code).await?,
+            None => vec![],
+        };
+        let now = chrono::Local::now();
+        let claims = Claims {
+            iss: self.jwt_keys.issuer.clone(),
+            sub: user.id.to_string(),
+            uid: user.id,
+            user_code: user_code.clone(),
+            org_code: org_code.clone(),
+            roles: roles.clone(),
+            is_admin: user.is_admin.unwrap_or(0) == 1,
+            iat: now.timestamp(),
+            exp: now.timestamp() + self.jwt_keys.access_token_ttl,
+            jti: common_func::random_hex(16),
+            sid: family_id.to_string(),
+        };
+
+        let refresh_token = common_func::random_hex(32);
+        self.refresh_token_mapper
+            .save(refresh_token::ActiveModel {
+                user_id: Set(user.id),
+                token_hash: Set(common_func::sha256_hex(&refresh_token)),
+                family_id: Set(family_id.to_string()),
+                device: Set(device),
+                user_agent: Set(user_agent),
+                expire_time: Set(now.naive_local() + Duration::seconds(self.jwt_keys.refresh_token_ttl)),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(LoginVo {
+            access_token: self.jwt_keys.encode(&claims)?,
+            token_type: "Bearer".to_string(),
+            expires_in: self.jwt_keys.access_token_ttl,
+            refresh_token,
+            refresh_expires_in: self.jwt_keys.refresh_token_ttl,
+            user_code,
+            org_code,
+            roles,
+        })
+    }
+
+    /// Organization the requests of `claims` are confined to: the `requested` one, which must be
+    /// an organization the caller works for, or else the organization of the token. Administrators
+    /// are only confined when they request an organization.
+    pub async fn tenant(&self, claims: &Claims, requested: Option<&str>) -> Result<Option<String>> {
+        if claims.is_admin {
+            return Ok(requested.map(str::to_string));
+        }
+        let Some(requested) = requested else {
+            return match &claims.org_code {
+                Some(org_code) => Ok(Some(org_code.clone())),
+                None => Err(AppError::Forbidden("账号未归属任何组织！".to_string()).into()),
+            };
+        };
+        let is_member = match &claims.user_code {
+            _ if claims.org_code.as_deref() == Some(requested) => true,
+            Some(user_code) => self
+                .user_info_mapper
+                .list_org_codes(user_code)
+                .await?
+                .iter()
+                .any(|org_code| org_code == requested),
+            None => false,
+        };
+        if !is_member {
+            return Err(AppError::Forbidden(format!("无权访问组织 {} 的数据！", requested)).into());
+        }
+        Ok(Some(requested.to_string()))
+    }
+
+    /// Verifies and decodes an access token issued by [`AuthSvc::login`].
+    /// Tokens whose `jti` or session was revoked are rejected via the deny-list.
+    pub async fn verify_token(&self, token: &str) -> Result<Claims> {
+        let invalid = || AppError::Unauthorized("无效的访问令牌！".to_string());
+        let claims = self.jwt_keys.decode::<Claims>(token).map_err(|_| invalid())?;
+        if self
+            .token_deny_list_mapper
+            .is_denied(vec![claims.jti.clone(), claims.sid.clone()])
+            .await?
+        {
+            return Err(invalid().into());
+        }
+        Ok(claims)
+    }
+
+    /// Ends the session of the calling access token
+    pub async fn logout(&self, claims: &Claims) -> Result<()> {
+        self.revoke_session(claims.uid, &claims.sid, "logout").await?;
+        self.token_deny_list_mapper.delete_expired().await?;
+        info!("user {} logged out of session {}", claims.uid, claims.sid);
+        Ok(())
+    }
+
+    /// Lists the live sessions of the calling user
+    pub async fn list_sessions(&self, claims: &Claims) -> Result<Vec<SessionVo>> {
+        let sessions = self
+            .refresh_token_mapper
+            .list_active(claims.uid)
+            .await?
+            .into_iter()
+            .map(|refresh_token| SessionVo {
+                current: refresh_token.family_id == claims.sid,
+                session_id: refresh_token.family_id,
+                device: refresh_token.device,
+                user_agent: refresh_token.user_agent,
+                last_active_time: refresh_token
+                    .create_time
+                    .and_then(|time| time.and_local_timezone(chrono::Local).single()),
+                expire_time: refresh_token.expire_time.and_local_timezone(chrono::Local).single(),
+            })
+            .collect();
+        Ok(sessions)
+    }
+
+    /// Revokes every live session of a user, including their outstanding access tokens.
+    /// Returns the number of sessions revoked.
+    pub async fn revoke_user_sessions(&self, user_id: i64, reason: &str) -> Result<u64> {
+        let sessions = self.refresh_token_mapper.list_active(user_id).await?;
+        for refresh_token in &sessions {
+            self.revoke_session(user_id, &refresh_token.family_id, reason).await?;
+        }
+        if !sessions.is_empty() {
+            info!("revoked {} sessions of user {} ({})", sessions.len(), user_id, reason);
+        }
+        Ok(sessions.len() as u64)
+    }
+
+    /// Revokes the refresh tokens of a session and denies its access tokens until they expire
+    async fn revoke_session(&self, user_id: i64, family_id: &str, reason: &str) -> Result<()> {
+        self.refresh_token_mapper.revoke_family(family_id).await?;
+        let expire_time = chrono::Local::now().naive_local() + Duration::seconds(self.jwt_keys.access_token_ttl);
+        self.token_deny_list_mapper
+            .save(family_id, user_id, reason, expire_time)
+            .await?;
+        Ok(())
+    }
+
+    pub fn public_key_pem(&self) -> String {
+        self.jwt_keys.public_key_pem().to_string()
+    }
+
+    /// Changes the password of an account after verifying the old one
+    pub async fn change_password(&self, change_password_dto: ChangePasswordDto) -> Result<()> {
+        if change_password_dto.new_password.is_empty() {
+            return Err(AppError::invalid_field("newPassword", "新密码不能为空！").into());
+        }
+        let (user, user_info) = self
+            .authenticate(&change_password_dto.username, &change_password_dto.old_password)
+            .await?;
+        let (credential, _) = Self::credential_of(&user, user_info.as_ref())
+            .ok_or_else(|| AppError::Unauthorized("用户名或密码错误！".to_string()))?;
+        self.store_password(credential, &password::hash(&change_password_dto.new_password)?)
+            .await?;
+        info!("user {} changed password", user.id);
+        self.revoke_user_sessions(user.id, "password changed").await?;
+        Ok(())
+    }
+
+    /// Loads the account of `username` and verifies `plain` against its stored password
+    async fn authenticate(
+        &self,
+        username: &str,
+        plain: &str,
+    ) -> Result<(user::Model, Option<user_info::Model>)> {
+        let bad_credentials = || AppError::Unauthorized("用户名或密码错误！".to_string());
+        let user = self
+            .user_mapper
+            .get_by_user_name(username)
+            .await?
+            .ok_or_else(bad_credentials)?;
+        let user_info = self.user_info_mapper.get_by_username(username).await?;
+        let (credential, stored) =
+            Self::credential_of(&user, user_info.as_ref()).ok_or_else(bad_credentials)?;
+        if !password::verify(plain, stored) {
+            return Err(bad_credentials().into());
+        }
+        if password::needs_rehash(stored) {
+            self.store_password(credential, &password::hash(plain)?).await?;
+        }
+        Ok((user, user_info))
+    }
+
+    async fn store_password(&self, credential: Credential, password_hash: &str) -> Result<()> {
+        match credential {
+            Credential::User(rec_id) => self.user_mapper.update_password(rec_id, password_hash).await?,
+            Credential::UserInfo(rec_id) => {
+                self.user_info_mapper.update_password(rec_id, password_hash).await?
+            }
+        };
+        Ok(())
+    }
+
+    fn credential_of<'a>(
+        user: &'a user::Model,
+        user_info: Option<&'a user_info::Model>,
+    ) -> Option<(Credential, &'a str)> {
+        match (&user.password, user_info) {
+            (Some(stored), _) if !stored.is_empty() => Some((Credential::User(user.id), stored)),
+            (_, Some(info)) => info
+                .password
+                .as_deref()
+                .filter(|stored| !stored.is_empty())
+                .map(|stored| (Credential::UserInfo(info.id), stored)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::{
+    entities::{permission, role, user_info},
+    mapper::{
+        authz_mapper::AuthzMapperTrait,
+        permission_mapper::PermissionMapperTrait,
+    },
+    pojo::{authz_pojo::*, permission_pojo::{PermissionTreeCondition, PermissionVo}, role_pojo::DataScope},
+    util::{exception::AppError, tree::{self, TreeNode}},
+};
+
+/// An effective role of a user together with every binding granting it
+/// and the ancestor roles it inherits permissions from, nearest first
+struct ResolvedRole {
+    role: role::Model,
+    sources: BTreeSet<GrantSourceVo>,
+    ancestors: Vec<role::Model>,
+}
+
+impl ResolvedRole {
+    /// Permissions of the role merged with those of all its ancestors
+    fn permission_keys(&self) -> PermissionKeys {
+        let mut permission_keys = PermissionKeys::parse(self.role.permissions.as_ref());
+        for ancestor in &self.ancestors {
+            let inherited = PermissionKeys::parse(ancestor.permissions.as_ref());
+            permission_keys.ids.extend(inherited.ids);
+            permission_keys.keys.extend(inherited.keys);
+        }
+        permission_keys
+    }
+}
+
+/// Permission references stored in `role.permissions`
+#[derive(Default)]
+struct PermissionKeys {
+    ids: HashSet<i64>,
+    keys: HashSet<String>,
+}
+
+impl PermissionKeys {
+    /// Accepts an array of permission ids, codes/uuids, or objects with `id`/`code`/`uuid`
+    fn parse(permissions: Option<&Value>) -> Self {
+        let mut permission_keys = PermissionKeys::default();
+        let Some(Value::Array(items)) = permissions else {
+            return permission_keys;
+        };
+        for item in items {
+            match item {
+                Value::Number(id) => permission_keys.ids.extend(id.as_i64()),
+                Value::String(key) => {
+                    permission_keys.keys.insert(key.clone());
+                }
+                Value::Object(obj) => {
+                    permission_keys.ids.extend(obj.get("id").and_then(Value::as_i64));
+                    for field in ["code", "uuid"] {
+                        if let Some(key) = obj.get(field).and_then(Value::as_str) {
+                            permission_keys.keys.insert(key.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        permission_keys
+    }
+
+    fn grants(&self, permission: &permission::Model) -> bool {
+        self.grants_node(permission.id, &permission.uuid, permission.code.as_deref())
+    }
+
+    fn grants_node(&self, id: i64, uuid: &str, code: Option<&str>) -> bool {
+        self.ids.contains(&id) || self.keys.contains(uuid) || code.is_some_and(|code| self.keys.contains(code))
+    }
+}
+
+pub struct AuthzSvc {
+    mapper: Arc<dyn AuthzMapperTrait>,
+    permission_mapper: Arc<dyn PermissionMapperTrait>,
+}
+
+impl AuthzSvc {
+    pub fn new(mapper: Arc<dyn AuthzMapperTrait>, permission_mapper: Arc<dyn PermissionMapperTrait>) -> Self {
+        Self { mapper, permission_mapper }
+    }
+
+    /// Roles of a user unioned over `user_role_ref`, `user_group_ref` + `group_role_ref`,
+    /// `department_role_ref`, `position_role_ref` and `organization_role_ref`
+    pub async fn effective_roles(&self, user_code: &str) -> Result<Vec<RoleGrantVo>> {
+        let user_info = self.get_user_info(user_code).await?;
+        let resolved_roles = self.resolve_roles(&user_info).await?;
+        Ok(resolved_roles.iter().map(Self::role_grant_vo).collect())
+    }
+
+    /// Codes of the effective roles of a user, empty when the user is unknown
+    pub async fn effective_role_codes(&self, user_code: &str) -> Result<Vec<String>> {
+        let Some(user_info) = self.mapper.get_user_info_by_user_code(user_code).await? else {
+            return Ok(vec![]);
+        };
+        let resolved_roles = self.resolve_roles(&user_info).await?;
+        Ok(resolved_roles.into_iter().filter_map(|resolved| resolved.role.code).collect())
+    }
+
+    /// Effective roles of a user and the `permission` rows they expand to,
+    /// each grant reporting the roles and bindings it came from
+    pub async fn effective_permissions(&self, user_code: &str) -> Result<EffectivePermissionsVo> {
+        let user_info = self.get_user_info(user_code).await?;
+        let resolved_roles = self.resolve_roles(&user_info).await?;
+
+        let role_keys: Vec<(String, PermissionKeys)> = resolved_roles
+            .iter()
+            .filter_map(|resolved| {
+                let role_code = resolved.role.code.clone()?;
+                Some((role_code, resolved.permission_keys()))
+            })
+            .collect();
+        let ids = role_keys.iter().flat_map(|(_, keys)| keys.ids.iter().copied()).collect::<HashSet<_>>();
+        let keys = role_keys.iter().flat_map(|(_, keys)| keys.keys.iter().cloned()).collect::<HashSet<_>>();
+        let permissions = self
+            .mapper
+            .list_permissions_by_keys(ids.into_iter().collect(), keys.into_iter().collect())
+            .await?
+            .into_iter()
+            .map(|permission| {
+                let granted_by = role_keys
+                    .iter()
+                    .filter(|(_, keys)| keys.grants(&permission))
+                    .map(|(role_code, _)| role_code.clone())
+                    .collect();
+                PermissionGrantVo {
+                    id: permission.id,
+                    uuid: permission.uuid,
+                    parent_uuid: permission.parent_uuid,
+                    code: permission.code,
+                    name: permission.name,
+                    application: permission.application,
+                    node_type: permission.node_type,
+                    granted_by,
+                }
+            })
+            .collect();
+
+        Ok(EffectivePermissionsVo {
+            user_code: user_code.to_string(),
+            org_code: user_info.org_code,
+            department_code: user_info.department_code,
+            position_code: user_info.position_code,
+            roles: resolved_roles.iter().map(Self::role_grant_vo).collect(),
+            permissions,
+        })
+    }
+
+    /// Permission tree of an application restricted to the nodes granted to a user.
+    /// Ancestors of granted nodes are kept so that every granted node stays reachable,
+    /// administrators receive the whole tree.
+    pub async fn menus(&self, user_code: &str, is_admin: bool, application: Option<String>) -> Result<Vec<TreeNode<PermissionVo>>> {
+        let nodes = self
+            .permission_mapper
+            .list_tree_nodes(PermissionTreeCondition { application, org_code: None })
+            .await?;
+        let nodes = if is_admin {
+            nodes
+        } else {
+            let user_info = self.get_user_info(user_code).await?;
+            let mut permission_keys = PermissionKeys::default();
+            for resolved in self.resolve_roles(&user_info).await? {
+                let role_keys = resolved.permission_keys();
+                permission_keys.ids.extend(role_keys.ids);
+                permission_keys.keys.extend(role_keys.keys);
+            }
+            Self::granted_with_ancestors(nodes, &permission_keys)
+        };
+        Ok(tree::build_forest(
+            nodes,
+            |node| node.uuid.clone(),
+            |node| node.parent_uuid.clone().filter(|uuid| !uuid.is_empty()),
+        ))
+    }
+
+    fn granted_with_ancestors(nodes: Vec<PermissionVo>, permission_keys: &PermissionKeys) -> Vec<PermissionVo> {
+        let parents: HashMap<&str, &str> = nodes
+            .iter()
+            .filter_map(|node| Some((node.uuid.as_deref()?, node.parent_uuid.as_deref()?)))
+            .collect();
+        let mut kept: HashSet<String> = HashSet::new();
+        for node in &nodes {
+            let (Some(id), Some(uuid)) = (node.id, node.uuid.as_deref()) else {
+                continue;
+            };
+            if !permission_keys.grants_node(id, uuid, node.code.as_deref()) {
+                continue;
+            }
+            let mut next = Some(uuid);
+            while let Some(uuid) = next.filter(|uuid| !uuid.is_empty()) {
+                if !kept.insert(uuid.to_string()) {
+                    break;
+                }
+                next = parents.get(uuid).copied();
+            }
+        }
+        nodes
+            .into_iter()
+            .filter(|node| node.uuid.as_ref().is_some_and(|uuid| kept.contains(uuid)))
+            .collect()
+    }
+
+    /// Permission code guarding a route inside `org_code`. An `api` permission node whose
+    /// `link_url` is `"{METHOD} {route}"` or just `"{route}"` overrides the given default, the
+    /// method specific node winning, then the node of the org over one shared by every org.
+    pub async fn route_permission(
+        &self,
+        method: &str,
+        route: &str,
+        org_code: Option<String>,
+        default_code: Option<String>,
+    ) -> Result<Option<String>> {
+        let method_route = format!("{} {}", method, route);
+        let mut overrides = self
+            .mapper
+            .list_api_permissions(vec![method_route.clone(), route.to_string()], &org_code)
+            .await?;
+        overrides.sort_by_key(|permission| {
+            (permission.link_url.as_deref() != Some(method_route.as_str()), permission.org_code.is_none())
+        });
+        let code = overrides.first().and_then(|permission| permission.code.clone());
+        Ok(code.or(default_code))
+    }
+
+    /// Whether a user holds a permission code in any application, inside `org_code`
+    /// or else the user's own org
+    pub async fn is_granted(&self, user_code: &str, org_code: Option<String>, permission_code: &str) -> Result<bool> {
+        let checks = vec![PermissionCheckItem { permission_code: permission_code.to_string(), application: None }];
+        let results = self.check(user_code, org_code, checks).await?;
+        Ok(results.iter().any(|result| result.allowed))
+    }
+
+    async fn get_user_info(&self, user_code: &str) -> Result<user_info::Model> {
+        self.mapper
+            .get_user_info_by_user_code(user_code)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("用户 {} 不存在！", user_code)).into())
+    }
+
+    /// The user as seen inside `org_code`, the user's own org when none is given. Without a user
+    /// info row in `org_code` the user holds nothing there and `None` is returned.
+    async fn get_user_info_in(&self, user_code: &str, org_code: Option<String>) -> Result<Option<user_info::Model>> {
+        let user_info = self.get_user_info(user_code).await?;
+        match org_code {
+            Some(org_code) if user_info.org_code.as_ref() != Some(&org_code) => {
+                Ok(self.mapper.get_user_info_in_org(user_code, &org_code).await?)
+            }
+            _ => Ok(Some(user_info)),
+        }
+    }
+
+    /// Data scope of a user, optionally inside another org than the user's own: the widest
+    /// `data_scope` among the effective roles, a role without one inheriting it from its
+    /// nearest ancestor having one and otherwise seeing its whole org. A user without
+    /// roles only sees its own rows, the department scopes expand along `department.path`.
+    pub async fn data_scope(&self, user_code: &str, org_code: Option<String>) -> Result<DataScopeVo> {
+        let Some(user_info) = self.get_user_info_in(user_code, org_code.clone()).await? else {
+            // not a member of the org, only rows of the user's own are left to see
+            return Ok(DataScopeVo {
+                user_code: user_code.to_string(),
+                scope: DataScope::Own,
+                org_code,
+                department_paths: vec![],
+                department_codes: vec![],
+            });
+        };
+        let scope = self
+            .resolve_roles(&user_info)
+            .await?
+            .iter()
+            .map(|resolved| {
+                std::iter::once(&resolved.role)
+                    .chain(&resolved.ancestors)
+                    .find_map(|role| role.data_scope.as_deref().and_then(DataScope::parse))
+                    .unwrap_or(DataScope::Org)
+            })
+            .max()
+            .unwrap_or(DataScope::Own);
+
+        let mut data_scope = DataScopeVo {
+            user_code: user_code.to_string(),
+            scope,
+            org_code: user_info.org_code.clone().filter(|_| scope != DataScope::All),
+            department_paths: vec![],
+            department_codes: vec![],
+        };
+        if !matches!(scope, DataScope::Department | DataScope::DepartmentAndBelow) {
+            return Ok(data_scope);
+        }
+        let (Some(org_code), Some(department_code)) = (&user_info.org_code, &user_info.department_code) else {
+            return Ok(data_scope);
+        };
+        let Some(department) = self.mapper.get_department(org_code, department_code).await? else {
+            return Ok(data_scope);
+        };
+        let path = department
+            .path
+            .filter(|path| !path.is_empty())
+            .unwrap_or_else(|| tree::child_path(None, department_code));
+        data_scope.department_codes = match scope {
+            DataScope::DepartmentAndBelow => self.mapper.list_department_codes_below(org_code, &path).await?,
+            _ => vec![department_code.clone()],
+        };
+        data_scope.department_paths = vec![path];
+        Ok(data_scope)
+    }
+
+    /// Checks permission codes for a user, optionally inside another org than the user's own,
+    /// a user not working for that org is denied every permission there.
+    /// Only the requested permissions are loaded, not the user's whole permission set.
+    pub async fn check(
+        &self,
+        user_code: &str,
+        org_code: Option<String>,
+        checks: Vec<PermissionCheckItem>,
+    ) -> Result<Vec<PermissionCheckVo>> {
+        let resolved_roles = match self.get_user_info_in(user_code, org_code).await? {
+            Some(user_info) => self.resolve_roles(&user_info).await?,
+            None => vec![],
+        };
+        let role_keys: Vec<(String, PermissionKeys)> = resolved_roles
+            .into_iter()
+            .filter_map(|resolved| {
+                let role_code = resolved.role.code.clone()?;
+                Some((role_code, resolved.permission_keys()))
+            })
+            .collect();
+        let codes = checks.iter().map(|check| check.permission_code.clone()).collect();
+        let permissions = self.mapper.list_permissions_by_keys(vec![], codes).await?;
+
+        let results = checks
+            .into_iter()
+            .map(|check| {
+                let granted_by: BTreeSet<String> = permissions
+                    .iter()
+                    .filter(|permission| permission.code.as_ref() == Some(&check.permission_code))
+                    .filter(|permission| {
+                        check.application.is_none() || permission.application == check.application
+                    })
+                    .flat_map(|permission| {
+                        role_keys
+                            .iter()
+                            .filter(|(_, keys)| keys.grants(permission))
+                            .map(|(role_code, _)| role_code.clone())
+                    })
+                    .collect();
+                PermissionCheckVo {
+                    allowed: !granted_by.is_empty(),
+                    permission_code: check.permission_code,
+                    application: check.application,
+                    granted_by: granted_by.into_iter().collect(),
+                }
+            })
+            .collect();
+        Ok(results)
+    }
+
+    async fn resolve_roles(&self, user_info: &user_info::Model) -> Result<Vec<ResolvedRole>> {
+        let mut sources_by_role: BTreeMap<String, BTreeSet<GrantSourceVo>> = BTreeMap::new();
+        for binding in self.mapper.list_role_bindings(user_info).await? {
+            sources_by_role.entry(binding.role_code).or_default().insert(GrantSourceVo {
+                source_type: binding.source_type,
+                source_code: binding.source_code,
+            });
+        }
+        let roles = self
+            .mapper
+            .list_roles_by_codes(sources_by_role.keys().cloned().collect())
+            .await?;
+        let roles_by_code = self.load_ancestors(&roles).await?;
+        let mut resolved_roles: Vec<ResolvedRole> = roles
+            .into_iter()
+            .filter_map(|role| {
+                let sources = sources_by_role.get(role.code.as_ref()?)?.clone();
+                let ancestors = Self::ancestors_of(&role, &roles_by_code);
+                Some(ResolvedRole { role, sources, ancestors })
+            })
+            .collect();
+        resolved_roles.sort_by(|a, b| a.role.code.cmp(&b.role.code));
+        Ok(resolved_roles)
+    }
+
+    /// Loads the `parent_code` chains above `roles` level by level, keyed by role code
+    async fn load_ancestors(&self, roles: &[role::Model]) -> Result<HashMap<String, role::Model>> {
+        let mut roles_by_code: HashMap<String, role::Model> = roles
+            .iter()
+            .filter_map(|role| Some((role.code.clone()?, role.clone())))
+            .collect();
+        let mut pending = Self::missing_parents(roles.iter(), &roles_by_code);
+        while !pending.is_empty() {
+            let parents = self.mapper.list_roles_by_codes(pending).await?;
+            for parent in &parents {
+                if let Some(code) = &parent.code {
+                    roles_by_code.insert(code.clone(), parent.clone());
+                }
+            }
+            pending = Self::missing_parents(parents.iter(), &roles_by_code);
+        }
+        Ok(roles_by_code)
+    }
+
+    fn missing_parents<'a>(
+        roles: impl Iterator<Item = &'a role::Model>,
+        roles_by_code: &HashMap<String, role::Model>,
+    ) -> Vec<String> {
+        roles
+            .filter_map(|role| role.parent_code.clone())
+            .filter(|code| !code.is_empty() && !roles_by_code.contains_key(code))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Ancestors of a role nearest first, stopping at a missing parent or a loop
+    fn ancestors_of(role: &role::Model, roles_by_code: &HashMap<String, role::Model>) -> Vec<role::Model> {
+        let mut seen: HashSet<&str> = role.code.as_deref().into_iter().collect();
+        let mut ancestors = vec![];
+        let mut next = role.parent_code.as_deref();
+        while let Some(parent) = next.and_then(|code| roles_by_code.get(code)) {
+            let Some(code) = parent.code.as_deref() else { break };
+            if !seen.insert(code) {
+                break;
+            }
+            ancestors.push(parent.clone());
+            next = parent.parent_code.as_deref();
+        }
+        ancestors
+    }
+
+    fn role_grant_vo(resolved: &ResolvedRole) -> RoleGrantVo {
+        RoleGrantVo {
+            role_code: resolved.role.code.clone().unwrap_or_default(),
+            role_name: Some(resolved.role.name.clone()),
+            application: resolved.role.application.clone(),
+            sources: resolved.sources.iter().cloned().collect(),
+            inherits: resolved.ancestors.iter().filter_map(|ancestor| ancestor.code.clone()).collect(),
+        }
+    }
+}
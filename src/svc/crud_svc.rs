@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use crate::{
+    mapper::crud_mapper::{CrudMapper, CrudMapperTrait, CrudResource},
+    pojo::audit_log_pojo::AuditAction,
+    svc::Services,
+    util::{exception::AppError, paged_struct::PageData, rec_sign, tenant},
+};
+use sea_orm::DbErr;
+
+/// Per-resource extension points of the generic CRUD service. Every hook has a
+/// default, a resource only overrides what differs from plain CRUD.
+#[async_trait::async_trait]
+pub trait CrudHooks: CrudResource {
+    /// Other services the hooks call into, `()` for a resource without any
+    type Deps: Send + Sync;
+
+    /// Mapper the service reaches the table through, `dyn CrudMapperTrait<Self>` unless the
+    /// hooks need queries beyond CRUD
+    type Mapper: CrudMapperTrait<Self> + ?Sized;
+
+    /// Whether `Vo` carries columns only emitted in the sensitive projection
+    const SENSITIVE: bool = false;
+
+    /// The service of this resource among the services of an application
+    fn svc(svcs: &Services) -> &CrudSvc<Self>;
+
+    /// Rejects a dto before it reaches the mapper
+    fn validate(_dto: &Self::Dto, _action: AuditAction) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Narrows the condition of a list/page query to the rows the caller may see
+    async fn restrict(_svc: &CrudSvc<Self>, _condition: &mut Self::Condition) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Clears the sensitive columns of a record leaving for a caller without the sensitive projection
+    fn desensitize(_vo: &mut Self::Vo) {}
+
+    async fn save(svc: &CrudSvc<Self>, dto: Self::Dto) -> anyhow::Result<i64> {
+        svc.save_record(dto).await
+    }
+
+    async fn update_by_id(svc: &CrudSvc<Self>, dto: Self::Dto) -> anyhow::Result<u64> {
+        svc.update_record(dto).await
+    }
+
+    async fn delete_by_ids(svc: &CrudSvc<Self>, dto: Self::Dto) -> anyhow::Result<u64> {
+        Ok(svc.mapper.delete_by_ids(dto).await?)
+    }
+
+    async fn remove_by_ids(svc: &CrudSvc<Self>, dto: Self::Dto) -> anyhow::Result<u64> {
+        Ok(svc.mapper.remove_by_ids(dto).await?)
+    }
+}
+
+pub struct CrudSvc<R: CrudHooks> {
+    pub(crate) mapper: Arc<R::Mapper>,
+    pub(crate) deps: R::Deps,
+}
+
+impl<R: CrudHooks> CrudSvc<R> {
+    pub fn new(mapper: Arc<R::Mapper>, deps: R::Deps) -> Self {
+        Self { mapper, deps }
+    }
+
+    pub async fn list(&self, mut condition: R::Condition) -> anyhow::Result<Vec<R::Vo>> {
+        R::restrict(self, &mut condition).await?;
+        Ok(self.mapper.list(condition).await?)
+    }
+
+    pub async fn page(&self, mut condition: R::Condition) -> anyhow::Result<PageData<R::Vo>> {
+        R::restrict(self, &mut condition).await?;
+        Ok(self.mapper.page(condition).await?)
+    }
+
+    pub async fn get_by_id(&self, rec_id: i64) -> Result<Option<R::Vo>, DbErr> {
+        self.mapper.get_by_id(rec_id).await
+    }
+
+    pub async fn save(&self, dto: R::Dto) -> anyhow::Result<i64> {
+        tenant::check(CrudMapper::<R>::org_code(&dto).as_deref())?;
+        R::validate(&dto, AuditAction::Save)?;
+        R::save(self, dto).await
+    }
+
+    pub async fn update_by_id(&self, dto: R::Dto) -> anyhow::Result<u64> {
+        if CrudMapper::<R>::rec_id(&dto).is_none() {
+            return Err(AppError::invalid_field("recId", "缺少记录ID recId！").into());
+        }
+        tenant::check(CrudMapper::<R>::org_code(&dto).as_deref())?;
+        R::validate(&dto, AuditAction::Update)?;
+        R::update_by_id(self, dto).await
+    }
+
+    pub async fn delete_by_ids(&self, dto: R::Dto) -> anyhow::Result<u64> {
+        Self::require_rec_ids(&dto)?;
+        R::validate(&dto, AuditAction::Delete)?;
+        R::delete_by_ids(self, dto).await
+    }
+
+    pub async fn remove_by_ids(&self, dto: R::Dto) -> anyhow::Result<u64> {
+        Self::require_rec_ids(&dto)?;
+        R::validate(&dto, AuditAction::Remove)?;
+        R::remove_by_ids(self, dto).await
+    }
+
+    /// Plain insert, for hooks which prepared the dto themselves
+    pub async fn save_record(&self, dto: R::Dto) -> anyhow::Result<i64> {
+        Ok(self.mapper.save(dto).await?)
+    }
+
+    /// Plain update, for hooks which prepared the dto themselves. A versioned record is
+    /// only updated if it still carries the `rec_sign` the caller read.
+    pub async fn update_record(&self, dto: R::Dto) -> anyhow::Result<u64> {
+        if !R::VERSIONED {
+            return Ok(self.mapper.update_by_id(dto).await?);
+        }
+        rec_sign::require(CrudMapper::<R>::rec_sign(&dto).as_deref())?;
+        let rec_id = CrudMapper::<R>::rec_id(&dto);
+        let result = self.mapper.update_by_id(dto).await?;
+        if result == 0 {
+            let exists = match rec_id {
+                Some(rec_id) => self.mapper.get_by_id(rec_id).await?.is_some(),
+                None => false,
+            };
+            return Err(rec_sign::stale(exists));
+        }
+        Ok(result)
+    }
+
+    /// Deletes must name the records they apply to
+    fn require_rec_ids(dto: &R::Dto) -> Result<(), AppError> {
+        if CrudMapper::<R>::rec_ids(dto).is_empty() {
+            return Err(AppError::invalid_field("recIds", "缺少记录ID recIds！"));
+        }
+        Ok(())
+    }
+}
@@ -1,48 +1,96 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use crate::{mapper::department_mapper::{DepartmentMapper, DepartmentMapperTrait}, pojo::department_pojo::*, util::paged_struct::PageData, AppState};
-use once_cell::sync::OnceCell;
-use sea_orm::DbErr;
+use crate::{
+    mapper::{
+        department_mapper::{DepartmentMapperTrait, DepartmentResource},
+        organization_mapper::OrganizationMapperTrait,
+    },
+    pojo::{department_pojo::*, position_pojo::PositionVo},
+    svc::{crud_svc::{CrudHooks, CrudSvc}, Services},
+    util::{exception::AppError, tenant, tree::{self, TreeNode}},
+};
 
-pub struct DepartmentSvc {
-    mapper: &'static DepartmentMapper,
-}
+#[async_trait::async_trait]
+impl CrudHooks for DepartmentResource {
+    type Deps = Arc<dyn OrganizationMapperTrait>;
+    type Mapper = dyn DepartmentMapperTrait;
 
-impl DepartmentSvc {
-    pub fn new(state: &AppState) -> Self {
-        Self { mapper: DepartmentMapper::get_instance(Arc::new(state.clone())) }
+    fn svc(svcs: &Services) -> &DepartmentSvc {
+        &svcs.department
     }
 
-    pub fn get_instance(state: &AppState) -> &'static DepartmentSvc {
-        static INSTANCE: OnceCell<DepartmentSvc> = OnceCell::new();
-        INSTANCE.get_or_init(|| DepartmentSvc::new(state))
+    /// Creates a department, its `path` is derived from the parent department of the same organization
+    async fn save(svc: &DepartmentSvc, mut department_dto: DepartmentDto) -> anyhow::Result<i64> {
+        let code = department_dto.code.clone().unwrap_or_default();
+        let parent_path = svc
+            .parent_path(department_dto.parent_code.as_deref(), department_dto.org_code.as_deref())
+            .await?;
+        department_dto.path = Some(tree::child_path(parent_path.as_deref(), &code));
+        svc.save_record(department_dto).await
     }
 
-    pub async fn list(&self, condition: DepartmentCondition) -> Result<Vec<DepartmentVo>, DbErr> {
-        self.mapper.list(condition).await
-    }
-    
-    pub async fn page(&self, condition: DepartmentCondition) -> Result<PageData<DepartmentVo>, DbErr> {
-        self.mapper.page(condition).await
-    }
-    
-    pub async fn get_by_id(&self, rec_id: i64) -> Result<Option<DepartmentVo>, DbErr> {
-        self.mapper.get_by_id(rec_id).await
+    /// Updates a department, it is re-parented through `move_node` only
+    async fn update_by_id(svc: &DepartmentSvc, mut department_dto: DepartmentDto) -> anyhow::Result<u64> {
+        let rec_id = department_dto.rec_id.unwrap_or_default() as i64;
+        // path is maintained by the server only
+        department_dto.path = svc
+            .check_tree_update(rec_id, department_dto.code.as_deref(), department_dto.parent_code.as_deref())
+            .await?;
+        svc.update_record(department_dto).await
     }
-    
-    pub async fn save(&self, department_dto: DepartmentDto) -> Result<i64, DbErr> {
-        self.mapper.save(department_dto).await
-    }
-    
-    pub async fn update_by_id(&self, department_dto: DepartmentDto) -> Result<u64, DbErr> {
-        self.mapper.update_by_id(department_dto).await
-    }
-    
-    pub async fn delete_by_ids(&self, department_dto: DepartmentDto) -> Result<u64, DbErr> {
-        self.mapper.delete_by_ids(department_dto).await
+}
+
+pub type DepartmentSvc = CrudSvc<DepartmentResource>;
+
+impl DepartmentSvc {
+    /// Departments of the organization `org_code` nested below their parent department,
+    /// each with its positions and the headcounts of itself and of its subtree
+    pub async fn tree(&self, org_code: &str) -> anyhow::Result<Vec<TreeNode<DepartmentTreeVo>>> {
+        tenant::check(Some(org_code))?;
+        self.organization_mapper()
+            .get_node(org_code, None)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("组织 {} 不存在！", org_code)))?;
+        let departments = self.mapper.list_by_org(org_code).await?;
+        let mut positions: HashMap<String, Vec<PositionVo>> = HashMap::new();
+        for position in self.mapper.list_positions_by_org(org_code).await? {
+            if let Some(department_code) = position.department_code.clone() {
+                positions.entry(department_code).or_default().push(position);
+            }
+        }
+        let headcounts: HashMap<String, i64> = self.mapper.count_members_by_department(org_code).await?.into_iter().collect();
+
+        let nodes = departments
+            .into_iter()
+            .map(|department| {
+                let code = department.code.clone().unwrap_or_default();
+                DepartmentTreeVo {
+                    direct_headcount: headcounts.get(&code).copied().unwrap_or_default() as u64,
+                    headcount: 0,
+                    positions: positions.remove(&code).unwrap_or_default(),
+                    department,
+                }
+            })
+            .collect();
+        let mut departments = tree::build_forest(
+            nodes,
+            |node| node.department.code.clone(),
+            |node| node.department.parent_code.clone().filter(|code| !code.is_empty()),
+        );
+        for department in &mut departments {
+            sum_headcount(department);
+        }
+        Ok(departments)
     }
-    
-    pub async fn remove_by_ids(&self, department_dto: DepartmentDto) -> Result<u64, DbErr> {
-        self.mapper.remove_by_ids(department_dto).await
+
+    fn organization_mapper(&self) -> &dyn OrganizationMapperTrait {
+        self.deps.as_ref()
     }
-} 
\ No newline at end of file
+}
+
+/// Sets the `headcount` of `node` and of every node below it, returns the one of `node`
+fn sum_headcount(node: &mut TreeNode<DepartmentTreeVo>) -> u64 {
+    let below: u64 = node.children.iter_mut().map(sum_headcount).sum();
+    node.node.headcount = node.node.direct_headcount + below;
+    node.node.headcount
+}
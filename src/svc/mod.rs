@@ -1,3 +1,8 @@
+pub mod audit_log_svc;
+pub mod crud_svc;
+pub mod tree_svc;
+pub mod auth_svc;
+pub mod authz_svc;
 pub mod user_svc;
 pub mod organization_svc;
 pub mod role_svc;
@@ -15,6 +20,10 @@ pub mod department_role_ref_svc;
 pub mod position_role_ref_svc;
 pub mod organization_role_ref_svc;
 
+pub use audit_log_svc::AuditLogSvc;
+pub use crud_svc::{CrudHooks, CrudSvc};
+pub use auth_svc::AuthSvc;
+pub use authz_svc::AuthzSvc;
 pub use user_svc::UserSvc;
 pub use organization_svc::OrganizationSvc;
 pub use role_svc::RoleSvc;
@@ -30,4 +39,66 @@ pub use user_group_ref_svc::UserGroupRefSvc;
 pub use group_role_ref_svc::GroupRoleRefSvc;
 pub use department_role_ref_svc::DepartmentRoleRefSvc;
 pub use position_role_ref_svc::PositionRoleRefSvc;
-pub use organization_role_ref_svc::OrganizationRoleRefSvc;
\ No newline at end of file
+pub use organization_role_ref_svc::OrganizationRoleRefSvc;
+
+use std::sync::Arc;
+
+use crate::{mapper::Mappers, util::jwt::JwtKeys};
+
+/// Every service of the application, built once per `AppState` on top of its `Mappers`
+pub struct Services {
+    pub audit_log: AuditLogSvc,
+    pub auth: Arc<AuthSvc>,
+    pub authz: Arc<AuthzSvc>,
+    pub organization: OrganizationSvc,
+    pub role: RoleSvc,
+    pub permission: PermissionSvc,
+    pub position: PositionSvc,
+    pub department: DepartmentSvc,
+    pub group: GroupSvc,
+    pub system_config: SystemConfigSvc,
+    pub user_wechat_info: UserWechatInfoSvc,
+    pub user: UserSvc,
+    pub user_info: UserInfoSvc,
+    pub user_role_ref: UserRoleRefSvc,
+    pub user_group_ref: UserGroupRefSvc,
+    pub position_role_ref: PositionRoleRefSvc,
+    pub department_role_ref: DepartmentRoleRefSvc,
+    pub group_role_ref: GroupRoleRefSvc,
+    pub organization_role_ref: OrganizationRoleRefSvc,
+}
+
+impl Services {
+    pub fn new(mappers: &Mappers, jwt_keys: Arc<JwtKeys>) -> Self {
+        let authz = Arc::new(AuthzSvc::new(mappers.authz.clone(), mappers.permission.clone()));
+        let auth = Arc::new(AuthSvc::new(
+            mappers.user.clone(),
+            mappers.user_info.clone(),
+            mappers.refresh_token.clone(),
+            mappers.token_deny_list.clone(),
+            authz.clone(),
+            jwt_keys,
+        ));
+        Self {
+            audit_log: AuditLogSvc::new(mappers.audit_log.clone()),
+            organization: CrudSvc::new(mappers.organization.clone(), ()),
+            role: CrudSvc::new(mappers.role.clone(), ()),
+            permission: CrudSvc::new(mappers.permission.clone(), ()),
+            position: CrudSvc::new(mappers.position.clone(), ()),
+            department: CrudSvc::new(mappers.department.clone(), mappers.organization.clone()),
+            group: CrudSvc::new(mappers.group.clone(), ()),
+            system_config: CrudSvc::new(mappers.system_config.clone(), ()),
+            user_wechat_info: CrudSvc::new(mappers.user_wechat_info.clone(), ()),
+            user: CrudSvc::new(mappers.user.clone(), auth.clone()),
+            user_info: CrudSvc::new(mappers.user_info.clone(), authz.clone()),
+            user_role_ref: CrudSvc::new(mappers.user_role_ref.clone(), ()),
+            user_group_ref: CrudSvc::new(mappers.user_group_ref.clone(), ()),
+            position_role_ref: CrudSvc::new(mappers.position_role_ref.clone(), ()),
+            department_role_ref: CrudSvc::new(mappers.department_role_ref.clone(), ()),
+            group_role_ref: CrudSvc::new(mappers.group_role_ref.clone(), ()),
+            organization_role_ref: CrudSvc::new(mappers.organization_role_ref.clone(), ()),
+            auth,
+            authz,
+        }
+    }
+}
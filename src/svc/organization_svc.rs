@@ -1,48 +1,77 @@
-use std::sync::Arc;
-
-use crate::{mapper::organization_mapper::{OrganizationMapper, OrganizationMapperTrait}, pojo::organization_pojo::*, util::paged_struct::PageData, AppState};
-use once_cell::sync::OnceCell;
+use crate::{
+    mapper::organization_mapper::{OrganizationMapperTrait, OrganizationResource},
+    pojo::organization_pojo::*,
+    svc::{crud_svc::{CrudHooks, CrudSvc}, Services},
+    util::{exception::AppError, tree::{self, TreeNode}},
+};
 use sea_orm::DbErr;
 
-pub struct OrganizationSvc {
-    mapper: &'static OrganizationMapper,
-}
+#[async_trait::async_trait]
+impl CrudHooks for OrganizationResource {
+    type Deps = ();
+    type Mapper = dyn OrganizationMapperTrait;
 
-impl OrganizationSvc {
-    pub fn new(state: &AppState) -> Self {
-        Self { mapper: OrganizationMapper::get_instance(Arc::new(state.clone())) }
+    fn svc(svcs: &Services) -> &OrganizationSvc {
+        &svcs.organization
     }
 
-    pub fn get_instance(state: &AppState) -> &'static OrganizationSvc {
-        static INSTANCE: OnceCell<OrganizationSvc> = OnceCell::new();
-        INSTANCE.get_or_init(|| OrganizationSvc::new(state))
+    /// Creates an organization, its `path` is derived from the parent organization
+    async fn save(svc: &OrganizationSvc, mut organization_dto: OrganizationDto) -> anyhow::Result<i64> {
+        let code = organization_dto.code.clone().unwrap_or_default();
+        if svc.mapper.get_node(&code, None).await?.is_some() {
+            return Err(AppError::Conflict(format!("组织编码 {} 已存在！", code)).into());
+        }
+        let parent_path = svc.parent_path(organization_dto.parent_code.as_deref(), None).await?;
+        organization_dto.path = Some(tree::child_path(parent_path.as_deref(), &code));
+        svc.save_record(organization_dto).await
     }
 
-    pub async fn list(&self, condition: OrganizationCondition) -> Result<Vec<OrganizationVo>, DbErr> {
-        self.mapper.list(condition).await
-    }
-    
-    pub async fn page(&self, condition: OrganizationCondition) -> Result<PageData<OrganizationVo>, DbErr> {
-        self.mapper.page(condition).await
+    /// Updates an organization, it is re-parented through `move_node` only
+    async fn update_by_id(svc: &OrganizationSvc, mut organization_dto: OrganizationDto) -> anyhow::Result<u64> {
+        let rec_id = organization_dto.rec_id.unwrap_or_default() as i64;
+        // path is maintained by the server only
+        organization_dto.path = svc
+            .check_tree_update(rec_id, organization_dto.code.as_deref(), organization_dto.parent_code.as_deref())
+            .await?;
+        svc.update_record(organization_dto).await
     }
-    
-    pub async fn get_by_id(&self, rec_id: i64) -> Result<Option<OrganizationVo>, DbErr> {
-        self.mapper.get_by_id(rec_id).await
-    }
-    
-    pub async fn save(&self, organization_dto: OrganizationDto) -> Result<i64, DbErr> {
-        self.mapper.save(organization_dto).await
+}
+
+pub type OrganizationSvc = CrudSvc<OrganizationResource>;
+
+impl OrganizationSvc {
+    /// Organizations nested below their parent organization, siblings in creation order
+    pub async fn tree(&self, condition: OrganizationTreeCondition) -> Result<Vec<TreeNode<OrganizationVo>>, DbErr> {
+        let organizations = self.mapper.list_tree_nodes(condition).await?;
+        Ok(tree::build_forest(
+            organizations,
+            |organization| organization.code.clone(),
+            |organization| organization.parent_code.clone().filter(|code| !code.is_empty()),
+        ))
     }
-    
-    pub async fn update_by_id(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr> {
-        self.mapper.update_by_id(organization_dto).await
+
+    /// Every organization below `code`, parents before their children
+    pub async fn descendants(&self, code: &str) -> anyhow::Result<Vec<OrganizationVo>> {
+        let path = self.path_of(code).await?;
+        Ok(self.mapper.list_descendants(&path).await?)
     }
-    
-    pub async fn delete_by_ids(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr> {
-        self.mapper.delete_by_ids(organization_dto).await
+
+    /// The organizations above `code`, the root first
+    pub async fn ancestors(&self, code: &str) -> anyhow::Result<Vec<OrganizationVo>> {
+        let path = self.path_of(code).await?;
+        let codes = tree::ancestor_codes(&path);
+        let mut ancestors = self.mapper.list_by_codes(codes.clone()).await?;
+        ancestors.sort_by_key(|organization| codes.iter().position(|code| Some(code) == organization.code.as_ref()));
+        Ok(ancestors)
     }
-    
-    pub async fn remove_by_ids(&self, organization_dto: OrganizationDto) -> Result<u64, DbErr> {
-        self.mapper.remove_by_ids(organization_dto).await
+
+    /// Materialized path of the organization `code`
+    async fn path_of(&self, code: &str) -> anyhow::Result<String> {
+        let organization = self
+            .mapper
+            .get_node(code, None)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("组织 {} 不存在！", code)))?;
+        Ok(organization.path)
     }
-} 
\ No newline at end of file
+}
@@ -1,48 +1,62 @@
-use std::sync::Arc;
-
-use crate::{mapper::role_mapper::{RoleMapper, RoleMapperTrait}, pojo::role_pojo::*, util::paged_struct::PageData, AppState};
-use once_cell::sync::OnceCell;
+use crate::{
+    mapper::role_mapper::{RoleMapperTrait, RoleResource},
+    pojo::role_pojo::*,
+    svc::{crud_svc::{CrudHooks, CrudSvc}, Services},
+    util::{exception::AppError, tree::{self, TreeNode}},
+};
 use sea_orm::DbErr;
 
-pub struct RoleSvc {
-    mapper: &'static RoleMapper,
-}
-
-impl RoleSvc {
-    pub fn new(state: &AppState) -> Self {
-        Self { mapper: RoleMapper::get_instance(Arc::new(state.clone())) }
-    }
+#[async_trait::async_trait]
+impl CrudHooks for RoleResource {
+    type Deps = ();
+    type Mapper = dyn RoleMapperTrait;
 
-    pub fn get_instance(state: &AppState) -> &'static RoleSvc {
-        static INSTANCE: OnceCell<RoleSvc> = OnceCell::new();
-        INSTANCE.get_or_init(|| RoleSvc::new(state))
+    fn svc(svcs: &Services) -> &RoleSvc {
+        &svcs.role
     }
 
-    pub async fn list(&self, condition: RoleCondition) -> Result<Vec<RoleVo>, DbErr> {
-        self.mapper.list(condition).await
+    /// Creates a role, its `path` is derived from the parent role
+    async fn save(svc: &RoleSvc, mut role_dto: RoleDto) -> anyhow::Result<i64> {
+        let code = role_dto.code.clone().unwrap_or_default();
+        if svc.mapper.get_by_code(&code).await?.is_some() {
+            return Err(AppError::Conflict(format!("角色编码 {} 已存在！", code)).into());
+        }
+        let parent_path = svc.parent_path(role_dto.parent_code.as_deref(), None).await?;
+        role_dto.path = Some(tree::child_path(parent_path.as_deref(), &code));
+        svc.save_record(role_dto).await
     }
     
-    pub async fn page(&self, condition: RoleCondition) -> Result<PageData<RoleVo>, DbErr> {
-        self.mapper.page(condition).await
+    /// Updates a role if it still carries the `rec_sign` the caller read,
+    /// it is re-parented through `move_node` only
+    async fn update_by_id(svc: &RoleSvc, mut role_dto: RoleDto) -> anyhow::Result<u64> {
+        let rec_id = role_dto.rec_id.unwrap_or_default() as i64;
+        if role_dto.code.as_deref() == Some("") {
+            return Err(AppError::invalid_field("code", "角色编码不能为空！").into());
+        }
+        // path is maintained by the server only
+        role_dto.path = svc
+            .check_tree_update(rec_id, role_dto.code.as_deref(), role_dto.parent_code.as_deref())
+            .await?;
+        // a new path means a new code
+        if let (Some(_), Some(code)) = (&role_dto.path, role_dto.code.as_deref()) {
+            if svc.mapper.get_by_code(code).await?.is_some() {
+                return Err(AppError::Conflict(format!("角色编码 {} 已存在！", code)).into());
+            }
+        }
+        svc.update_record(role_dto).await
     }
-    
-    pub async fn get_by_id(&self, rec_id: i64) -> Result<Option<RoleVo>, DbErr> {
-        self.mapper.get_by_id(rec_id).await
-    }
-    
-    pub async fn save(&self, role_dto: RoleDto) -> Result<i64, DbErr> {
-        self.mapper.save(role_dto).await
-    }
-    
-    pub async fn update_by_id(&self, role_dto: RoleDto) -> Result<u64, DbErr> {
-        self.mapper.update_by_id(role_dto).await
-    }
-    
-    pub async fn delete_by_ids(&self, role_dto: RoleDto) -> Result<u64, DbErr> {
-        self.mapper.delete_by_ids(role_dto).await
-    }
-    
-    pub async fn remove_by_ids(&self, role_dto: RoleDto) -> Result<u64, DbErr> {
-        self.mapper.remove_by_ids(role_dto).await
+}
+
+pub type RoleSvc = CrudSvc<RoleResource>;
+
+impl RoleSvc {
+    /// Roles nested below their parent role, siblings in creation order
+    pub async fn tree(&self, condition: RoleTreeCondition) -> Result<Vec<TreeNode<RoleVo>>, DbErr> {
+        let roles = self.mapper.list_tree_nodes(condition).await?;
+        Ok(tree::build_forest(
+            roles,
+            |role| role.code.clone(),
+            |role| role.parent_code.clone().filter(|code| !code.is_empty()),
+        ))
     }
-} 
\ No newline at end of file
+}
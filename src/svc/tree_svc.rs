@@ -0,0 +1,109 @@
+use crate::{
+    mapper::tree_mapper::{TreeMapperTrait, TreeNodeRow, TreeResource},
+    pojo::tree_pojo::MoveDto,
+    svc::crud_svc::{CrudHooks, CrudSvc},
+    util::{exception::AppError, rec_sign, tenant, tree},
+};
+
+/// Hierarchy operations of every resource whose rows form a tree
+impl<R: CrudHooks + TreeResource> CrudSvc<R>
+where
+    R::Mapper: TreeMapperTrait<R>,
+{
+    /// Moves the node `code` and its whole subtree below `parent_code`, or to the roots
+    /// without one. Returns the number of nodes whose path was rewritten.
+    pub async fn move_node(&self, code: &str, move_dto: MoveDto) -> anyhow::Result<u64> {
+        rec_sign::require(move_dto.rec_sign.as_deref())?;
+        let node = self
+            .mapper
+            .get_node(code, None)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("{} {} 不存在！", R::LABEL, code)))?;
+        let parent_code = move_dto.parent_code.filter(|parent_code| !parent_code.is_empty());
+        let parent_path = match parent_code.as_deref() {
+            Some(parent_code) => {
+                let parent = self.parent_node(parent_code, node.scope.as_deref()).await?;
+                if parent.path.starts_with(&node.path) {
+                    return Err(AppError::invalid_field(
+                        "parentCode",
+                        format!("{} {} 不能移动到自身或其下级{}之下！", R::LABEL, code, R::LABEL),
+                    )
+                    .into());
+                }
+                Some(parent.path)
+            }
+            None => {
+                Self::check_tenant_root()?;
+                None
+            }
+        };
+        let path = tree::child_path(parent_path.as_deref(), code);
+        let rec_sign = move_dto.rec_sign.unwrap_or_default();
+        match self.mapper.move_subtree(&node, parent_code, path, &rec_sign).await? {
+            0 => Err(rec_sign::stale(true)),
+            moved => Ok(moved),
+        }
+    }
+
+    /// Path of the parent node `parent_code`, `None` for a root node. For a resource
+    /// with a `SCOPE` column the parent must carry the node's `scope`.
+    pub async fn parent_path(&self, parent_code: Option<&str>, scope: Option<&str>) -> anyhow::Result<Option<String>> {
+        if tree::is_root(parent_code) {
+            Self::check_tenant_root()?;
+            return Ok(None);
+        }
+        let parent = self.parent_node(parent_code.unwrap_or_default(), scope).await?;
+        Ok(Some(parent.path))
+    }
+
+    /// Checks an update of the node `rec_id` setting `code` and `parent_code`: the parent is only
+    /// changed through `move_node` and a node with children keeps its code. Returns the new path
+    /// of a leaf whose code changes, `None` when the path stays as it is.
+    pub async fn check_tree_update(&self, rec_id: i64, code: Option<&str>, parent_code: Option<&str>) -> anyhow::Result<Option<String>> {
+        let current = self
+            .mapper
+            .get_node_by_id(rec_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("{} {} 不存在！", R::LABEL, rec_id)))?;
+        let requested_parent = parent_code.map(|parent_code| Some(parent_code).filter(|code| !code.is_empty()));
+        if requested_parent.is_some_and(|parent_code| parent_code != current.parent_code.as_deref()) {
+            return Err(AppError::invalid_field("parentCode", format!("请通过移动接口调整上级{}！", R::LABEL)).into());
+        }
+        let Some(code) = code.filter(|code| *code != current.code) else {
+            return Ok(None);
+        };
+        if self.mapper.count_children(&current.code).await? > 0 {
+            return Err(AppError::invalid_field("code", format!("存在下级{}的{}不能修改编码！", R::LABEL, R::LABEL)).into());
+        }
+        let parent_path = self.parent_path(current.parent_code.as_deref(), current.scope.as_deref()).await?;
+        Ok(Some(tree::child_path(parent_path.as_deref(), code)))
+    }
+
+    /// A tenant cannot place a node above its own when the nodes are the tenants
+    fn check_tenant_root() -> Result<(), AppError> {
+        match tenant::current() {
+            Some(org_code) if R::TENANT_ROOTED => Err(AppError::Forbidden(format!(
+                "只能在组织 {} 之下调整{}！",
+                org_code,
+                R::LABEL
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    async fn parent_node(&self, parent_code: &str, scope: Option<&str>) -> anyhow::Result<TreeNodeRow> {
+        let parent = self
+            .mapper
+            .get_node(parent_code, scope)
+            .await?
+            .ok_or_else(|| AppError::invalid_field("parentCode", format!("父{} {} 不存在！", R::LABEL, parent_code)))?;
+        if R::SCOPE.is_some() && parent.scope.as_deref() != scope {
+            return Err(AppError::invalid_field(
+                "parentCode",
+                format!("父{} {} 不属于同一组织！", R::LABEL, parent_code),
+            )
+            .into());
+        }
+        Ok(parent)
+    }
+}
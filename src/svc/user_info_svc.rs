@@ -1,48 +1,62 @@
 use std::sync::Arc;
 
-use crate::{mapper::user_info_mapper::{UserInfoMapper, UserInfoMapperTrait}, pojo::user_info_pojo::*, util::paged_struct::PageData, AppState};
-use once_cell::sync::OnceCell;
-use sea_orm::DbErr;
+use crate::{
+    mapper::user_info_mapper::{UserInfoMapperTrait, UserInfoResource},
+    pojo::user_info_pojo::*,
+    svc::{authz_svc::AuthzSvc, crud_svc::{CrudHooks, CrudSvc}, Services},
+    util::{audit, desensitize::Desensitize, password, tenant},
+};
 
-pub struct UserInfoSvc {
-    mapper: &'static UserInfoMapper,
-}
+#[async_trait::async_trait]
+impl CrudHooks for UserInfoResource {
+    type Deps = Arc<AuthzSvc>;
+    type Mapper = dyn UserInfoMapperTrait;
+    const SENSITIVE: bool = true;
 
-impl UserInfoSvc {
-    pub fn new(state: &AppState) -> Self {
-        Self { mapper: UserInfoMapper::get_instance(Arc::new(state.clone())) }
+    fn svc(svcs: &Services) -> &UserInfoSvc {
+        &svcs.user_info
     }
 
-    pub fn get_instance(state: &AppState) -> &'static UserInfoSvc {
-        static INSTANCE: OnceCell<UserInfoSvc> = OnceCell::new();
-        INSTANCE.get_or_init(|| UserInfoSvc::new(state))
+    /// Confines a listing to the data scope of the caller's roles, administrators see every row
+    async fn restrict(svc: &UserInfoSvc, condition: &mut UserInfoCondition) -> anyhow::Result<()> {
+        let context = audit::context();
+        if context.is_admin {
+            return Ok(());
+        }
+        if let Some(user_code) = context.user_code {
+            condition.data_scope = Some(svc.authz_svc().data_scope(&user_code, tenant::current()).await?);
+        }
+        Ok(())
     }
 
-    pub async fn list(&self, condition: UserInfoCondition) -> Result<Vec<UserInfoVo>, DbErr> {
-        self.mapper.list(condition).await
-    }
-    
-    pub async fn page(&self, condition: UserInfoCondition) -> Result<PageData<UserInfoVo>, DbErr> {
-        self.mapper.page(condition).await
-    }
-    
-    pub async fn get_by_id(&self, rec_id: i64) -> Result<Option<UserInfoVo>, DbErr> {
-        self.mapper.get_by_id(rec_id).await
+    fn desensitize(vo: &mut UserInfoVo) {
+        vo.desensitize();
     }
-    
-    pub async fn save(&self, user_info_dto: UserInfoDto) -> Result<i64, DbErr> {
-        self.mapper.save(user_info_dto).await
-    }
-    
-    pub async fn update_by_id(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr> {
-        self.mapper.update_by_id(user_info_dto).await
+
+    async fn save(svc: &UserInfoSvc, mut user_info_dto: UserInfoDto) -> anyhow::Result<i64> {
+        hash_password(&mut user_info_dto)?;
+        svc.save_record(user_info_dto).await
     }
-    
-    pub async fn delete_by_ids(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr> {
-        self.mapper.delete_by_ids(user_info_dto).await
+
+    async fn update_by_id(svc: &UserInfoSvc, mut user_info_dto: UserInfoDto) -> anyhow::Result<u64> {
+        hash_password(&mut user_info_dto)?;
+        svc.update_record(user_info_dto).await
     }
-    
-    pub async fn remove_by_ids(&self, user_info_dto: UserInfoDto) -> Result<u64, DbErr> {
-        self.mapper.remove_by_ids(user_info_dto).await
+}
+
+pub type UserInfoSvc = CrudSvc<UserInfoResource>;
+
+impl UserInfoSvc {
+    fn authz_svc(&self) -> &AuthzSvc {
+        &self.deps
     }
-} 
\ No newline at end of file
+}
+
+/// Replaces a plaintext password in the dto by its hash, an empty one leaves the column untouched
+fn hash_password(user_info_dto: &mut UserInfoDto) -> anyhow::Result<()> {
+    user_info_dto.password = match user_info_dto.password.take() {
+        Some(plain) if !plain.is_empty() => Some(password::hash(&plain)?),
+        _ => None,
+    };
+    Ok(())
+}
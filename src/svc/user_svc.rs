@@ -1,54 +1,97 @@
 use std::sync::Arc;
 
-use crate::{mapper::user_mapper::{UserMapper, UserMapperTrait}, pojo::user_pojo::*, util::paged_struct::PageData, AppState};
-use once_cell::sync::OnceCell;
-use sea_orm::DbErr;
+use crate::{
+    mapper::{crud_mapper::CrudMapper, user_mapper::{UserMapperTrait, UserResource}},
+    pojo::user_pojo::*,
+    svc::{auth_svc::AuthSvc, crud_svc::{CrudHooks, CrudSvc}, Services},
+    util::{desensitize::Desensitize, password},
+};
 
-pub struct UserSvc {
-    mapper: &'static UserMapper,
-}
+#[async_trait::async_trait]
+impl CrudHooks for UserResource {
+    type Deps = Arc<AuthSvc>;
+    type Mapper = dyn UserMapperTrait;
+    const SENSITIVE: bool = true;
 
-impl UserSvc {
+    fn svc(svcs: &Services) -> &UserSvc {
+        &svcs.user
+    }
 
-    pub fn new(state: &AppState) -> Self {
-        Self { mapper: UserMapper::get_instance(Arc::new(state.clone())) }
+    fn desensitize(vo: &mut UserVo) {
+        vo.desensitize();
     }
 
-    pub fn get_instance(state: &AppState) -> &'static UserSvc {
-        static INSTANCE: OnceCell<UserSvc> = OnceCell::new();
-        INSTANCE.get_or_init(|| UserSvc::new(state))
+    /// Creates a user, an account without a `type` or `status` gets the default one
+    async fn save(svc: &UserSvc, mut user_dto: UserDto) -> anyhow::Result<i64> {
+        hash_password(&mut user_dto)?;
+        user_dto.r#type.get_or_insert(USER_TYPE_DEFAULT);
+        user_dto.status.get_or_insert(USER_STATUS_NORMAL);
+        svc.save_record(user_dto).await
     }
 
-    pub async fn list(&self, condition: UserCondition) -> Result<Vec<UserVo>, DbErr> {
-        self.mapper.list(condition).await
+    /// Updates a user, disabling the account also ends all of its sessions
+    async fn update_by_id(svc: &UserSvc, mut user_dto: UserDto) -> anyhow::Result<u64> {
+        hash_password(&mut user_dto)?;
+        let disabled_id = match (user_dto.rec_id, user_dto.status) {
+            (Some(rec_id), Some(status)) if status != USER_STATUS_NORMAL => Some(rec_id as i64),
+            _ => None,
+        };
+        let result = svc.update_record(user_dto).await?;
+        if let Some(rec_id) = disabled_id.filter(|_| result > 0) {
+            svc.auth_svc().revoke_user_sessions(rec_id, "disabled").await?;
+        }
+        Ok(result)
     }
-    
-    pub async fn page(&self, condition: UserCondition) -> Result<PageData<UserVo>, DbErr> {
-        self.mapper.page(condition).await
+
+    async fn delete_by_ids(svc: &UserSvc, user_dto: UserDto) -> anyhow::Result<u64> {
+        let rec_ids = svc.affected_rec_ids(&user_dto).await?;
+        let result = svc.mapper.delete_by_ids(user_dto).await?;
+        if result > 0 {
+            svc.revoke_sessions_of(&rec_ids, "deleted").await?;
+        }
+        Ok(result)
     }
-    
-    pub async fn get_by_id(&self, rec_id: i64) -> Result<Option<UserVo>, DbErr> {
-        self.mapper.get_by_id(rec_id).await
+
+    async fn remove_by_ids(svc: &UserSvc, user_dto: UserDto) -> anyhow::Result<u64> {
+        let rec_ids = svc.affected_rec_ids(&user_dto).await?;
+        let result = svc.mapper.remove_by_ids(user_dto).await?;
+        if result > 0 {
+            svc.revoke_sessions_of(&rec_ids, "removed").await?;
+        }
+        Ok(result)
     }
-    
-    pub async fn save(&self, user_dto: UserDto) -> Result<i64, DbErr> {
-        self.mapper.save(user_dto).await
+}
+
+pub type UserSvc = CrudSvc<UserResource>;
+
+impl UserSvc {
+    /// Ends every session of a user, returns the number of sessions revoked
+    pub async fn revoke_sessions(&self, rec_id: i64) -> anyhow::Result<u64> {
+        self.auth_svc().revoke_user_sessions(rec_id, "revoked").await
     }
-    
-    pub async fn update_by_id(&self, user_dto: UserDto) -> Result<u64, DbErr> {
-        self.mapper.update_by_id(user_dto).await
+
+    /// Ids of the dto the delete will reach, those outside of the caller's organization left out
+    async fn affected_rec_ids(&self, user_dto: &UserDto) -> anyhow::Result<Vec<i64>> {
+        Ok(self.mapper.tenant_rec_ids(CrudMapper::<UserResource>::rec_ids(user_dto)).await?)
     }
-    
-    pub async fn delete_by_ids(&self, user_dto: UserDto) -> Result<u64, DbErr> {
-        self.mapper.delete_by_ids(user_dto).await
+
+    async fn revoke_sessions_of(&self, rec_ids: &[i64], reason: &str) -> anyhow::Result<()> {
+        for rec_id in rec_ids {
+            self.auth_svc().revoke_user_sessions(*rec_id, reason).await?;
+        }
+        Ok(())
     }
-    
-    pub async fn remove_by_ids(&self, user_dto: UserDto) -> Result<u64, DbErr> {
-        self.mapper.remove_by_ids(user_dto).await
+
+    fn auth_svc(&self) -> &AuthSvc {
+        &self.deps
     }
-    
 }
 
-
-
-
+/// Replaces a plaintext password in the dto by its hash, an empty one leaves the column untouched
+fn hash_password(user_dto: &mut UserDto) -> anyhow::Result<()> {
+    user_dto.password = match user_dto.password.take() {
+        Some(plain) if !plain.is_empty() => Some(password::hash(&plain)?),
+        _ => None,
+    };
+    Ok(())
+}
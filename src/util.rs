@@ -1,6 +1,7 @@
 use serde_json::Value;
 
 pub trait IntoJsonValue {
+    #[allow(clippy::wrong_self_convention)]
     fn into_json_with_snake_key(&self) -> Value;
 }
 
@@ -22,44 +23,383 @@ pub mod common_func {
             })
             .collect::<String>()
     }
+
+    /**
+     * Lowercase hex SHA-256 digest of `text`
+     */
+    pub fn sha256_hex(text: &str) -> String {
+        openssl::sha::sha256(text.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    }
+
+    /**
+     * Random lowercase hex string built from `byte_len` random bytes
+     */
+    pub fn random_hex(byte_len: usize) -> String {
+        use rand::RngCore;
+        let mut bytes = vec![0_u8; byte_len];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+    }
 }
 
 pub mod exception {
 
+    use std::fmt;
+
     use super::result_struct::RespResult;
-    use axum::{http::StatusCode, Json};
+    use axum::{
+        extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        Json,
+    };
+    use sea_orm::{DbErr, SqlErr};
+    use serde::Serialize;
+    use tracing::error;
 
-    pub fn internal_err<E>(err: E) -> (StatusCode, Json<RespResult<String>>)
-    where
-        E: std::error::Error,
-    {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(RespResult::sys_error(err.to_string())),
-        )
+    /// One rejected field of a request, `field` named as in the json body
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct FieldError {
+        pub field: String,
+        pub message: String,
     }
 
-    // anyhow implament
-    pub fn internal_anyhow_err(err: anyhow::Error) -> (StatusCode, Json<RespResult<String>>)
-    {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(RespResult::sys_error(err.to_string())),
-        )
+    impl FieldError {
+        pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+            Self { field: field.into(), message: message.into() }
+        }
+    }
+
+    /// Every way a request can fail. Each kind is answered with its HTTP status and a stable
+    /// `code` / `error` pair in the `RespResult` body, clients branch on those and not on `message`.
+    #[derive(Debug)]
+    pub enum AppError {
+        Validation { message: String, fields: Vec<FieldError> },
+        Unauthorized(String),
+        Forbidden(String),
+        NotFound(String),
+        Conflict(String),
+        Internal(anyhow::Error),
+    }
+
+    impl AppError {
+        /// A rejected request as a whole
+        pub fn validation(message: impl Into<String>) -> Self {
+            AppError::Validation { message: message.into(), fields: vec![] }
+        }
+
+        /// A request rejected because of one of its fields
+        pub fn invalid_field(field: &str, message: impl Into<String>) -> Self {
+            let message = message.into();
+            AppError::Validation { message: message.clone(), fields: vec![FieldError::new(field, message)] }
+        }
+
+        /// A request rejected because of the given fields
+        pub fn invalid_fields(fields: Vec<FieldError>) -> Self {
+            AppError::Validation { message: "请求参数校验失败！".to_string(), fields }
+        }
+
+        pub fn status_code(&self) -> StatusCode {
+            match self {
+                AppError::Validation { .. } => StatusCode::BAD_REQUEST,
+                AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+                AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+                AppError::NotFound(_) => StatusCode::NOT_FOUND,
+                AppError::Conflict(_) => StatusCode::CONFLICT,
+                AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            }
+        }
+
+        /// Numeric `code` of the body, the HTTP status of the kind
+        pub fn code(&self) -> i32 {
+            self.status_code().as_u16() as i32
+        }
+
+        /// String `error` of the body
+        pub fn error_code(&self) -> &'static str {
+            match self {
+                AppError::Validation { .. } => "validation",
+                AppError::Unauthorized(_) => "unauthorized",
+                AppError::Forbidden(_) => "forbidden",
+                AppError::NotFound(_) => "not_found",
+                AppError::Conflict(_) => "conflict",
+                AppError::Internal(_) => "internal",
+            }
+        }
+
+        pub fn message(&self) -> &str {
+            match self {
+                AppError::Validation { message, .. }
+                | AppError::Unauthorized(message)
+                | AppError::Forbidden(message)
+                | AppError::NotFound(message)
+                | AppError::Conflict(message) => message,
+                AppError::Internal(_) => "系统错误！",
+            }
+        }
+    }
+
+    impl fmt::Display for AppError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                AppError::Internal(err) => write!(f, "{}", err),
+                _ => write!(f, "{}", self.message()),
+            }
+        }
+    }
+
+    impl std::error::Error for AppError {}
+
+    impl IntoResponse for AppError {
+        fn into_response(self) -> Response {
+            let status = self.status_code();
+            let mut resp = RespResult::fail(self.code(), self.message().to_string());
+            resp.error = Some(self.error_code());
+            match self {
+                AppError::Validation { fields, .. } => resp.fields = fields,
+                AppError::Internal(err) => {
+                    error!("request failed: {:?}", err);
+                    resp.data = err.to_string();
+                }
+                _ => {}
+            }
+            (status, Json(resp)).into_response()
+        }
+    }
+
+    /// Errors of the svc layer: an `AppError` raised there keeps its kind,
+    /// database errors are classified and everything else is internal
+    impl From<anyhow::Error> for AppError {
+        fn from(err: anyhow::Error) -> Self {
+            let err = match err.downcast::<AppError>() {
+                Ok(app_err) => return app_err,
+                Err(err) => err,
+            };
+            match err.downcast::<DbErr>() {
+                Ok(db_err) => db_err.into(),
+                Err(err) => AppError::Internal(err),
+            }
+        }
+    }
+
+    impl From<DbErr> for AppError {
+        fn from(err: DbErr) -> Self {
+            match err.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => {
+                    AppError::Conflict("数据已存在，请勿重复提交！".to_string())
+                }
+                Some(SqlErr::ForeignKeyConstraintViolation(_)) => {
+                    AppError::Conflict("数据仍被其他记录引用！".to_string())
+                }
+                _ => match err {
+                    DbErr::RecordNotFound(_) => AppError::NotFound("数据不存在！".to_string()),
+                    // refused by a mapper as repeating the unique key of another row
+                    DbErr::RecordNotInserted | DbErr::RecordNotUpdated => {
+                        AppError::Conflict("数据已存在，请勿重复提交！".to_string())
+                    }
+                    err => AppError::Internal(err.into()),
+                },
+            }
+        }
+    }
+
+    impl From<JsonRejection> for AppError {
+        fn from(rejection: JsonRejection) -> Self {
+            AppError::validation(format!("请求体格式错误：{}", rejection.body_text()))
+        }
+    }
+
+    impl From<QueryRejection> for AppError {
+        fn from(rejection: QueryRejection) -> Self {
+            AppError::validation(format!("查询参数格式错误：{}", rejection.body_text()))
+        }
+    }
+
+    impl From<PathRejection> for AppError {
+        fn from(rejection: PathRejection) -> Self {
+            AppError::validation(format!("路径参数格式错误：{}", rejection.body_text()))
+        }
     }
 
 }
 
+/// Declarative rules of request bodies, checked by the `ValidJson` / `ValidPatch`
+/// extractors before a body reaches a service
+pub mod validation {
+
+    use super::exception::{AppError, FieldError};
+
+    /// Longest business code, the width of the `code` columns
+    pub const CODE_MAX_LEN: usize = 64;
+
+    /// A request body carrying rules on its fields
+    pub trait Validate {
+        /// Reports every rule the body breaks to `v`
+        fn validate(&self, v: &mut Validator);
+    }
+
+    /**
+     * Checks `value` against its rules and answers all violations at once.
+     * A `partial` body is an update: absent fields keep their stored value and are not required.
+     */
+    pub fn check<T: Validate>(value: &T, partial: bool) -> Result<(), AppError> {
+        let mut validator = Validator { partial, fields: vec![] };
+        value.validate(&mut validator);
+        if validator.fields.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::invalid_fields(validator.fields))
+    }
+
+    /// Violations collected from one body
+    pub struct Validator {
+        partial: bool,
+        fields: Vec<FieldError>,
+    }
+
+    impl Validator {
+        /// Rules of the field `name`, as named in the json body
+        pub fn field<'v, 'a>(&'v mut self, name: impl Into<String>, value: Option<&'a str>) -> FieldRules<'v, 'a> {
+            FieldRules { validator: self, name: name.into(), value, failed: false }
+        }
+
+        /// A rule spanning several fields, reported on `name` when `valid` is false
+        pub fn ensure(&mut self, name: &str, valid: bool, message: impl Into<String>) -> &mut Self {
+            if !valid {
+                self.fields.push(FieldError::new(name, message));
+            }
+            self
+        }
+
+        /// Whether the body is an update of the fields it carries
+        pub fn is_partial(&self) -> bool {
+            self.partial
+        }
+    }
+
+    /// Rules of one string field, only the first rule it breaks is reported
+    pub struct FieldRules<'v, 'a> {
+        validator: &'v mut Validator,
+        name: String,
+        value: Option<&'a str>,
+        failed: bool,
+    }
+
+    impl FieldRules<'_, '_> {
+        /// Present and not blank, `label` names the field in the message
+        pub fn required(mut self, label: &str) -> Self {
+            let missing = match self.value {
+                Some(value) => value.trim().is_empty(),
+                None => !self.validator.partial,
+            };
+            if missing {
+                self.fail(format!("{}不能为空！", label));
+            }
+            self
+        }
+
+        /// At most `max` characters
+        pub fn max_len(mut self, max: usize) -> Self {
+            if self.value.is_some_and(|value| value.chars().count() > max) {
+                let message = format!("{} 长度不能超过 {} 个字符！", self.name, max);
+                self.fail(message);
+            }
+            self
+        }
+
+        /// At least `min` characters
+        pub fn min_len(mut self, min: usize) -> Self {
+            if self.value.is_some_and(|value| value.chars().count() < min) {
+                let message = format!("{} 长度不能少于 {} 个字符！", self.name, min);
+                self.fail(message);
+            }
+            self
+        }
+
+        /// A business code: letters, digits, `_`, `-` and `.`, short enough for a `code` column.
+        /// Codes are joined by `/` into materialized paths and must not contain it.
+        pub fn code(mut self) -> Self {
+            self = self.max_len(CODE_MAX_LEN);
+            let valid = |value: &str| value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+            if self.value.is_some_and(|value| !valid(value)) {
+                let message = format!("{} 只能包含字母、数字、下划线、中划线和点！", self.name);
+                self.fail(message);
+            }
+            self
+        }
+
+        /// An email address: `local@domain.tld` without whitespace
+        pub fn email(mut self) -> Self {
+            let valid = |value: &str| match value.split_once('@') {
+                Some((local, domain)) => {
+                    !local.is_empty()
+                        && !domain.contains('@')
+                        && domain.split('.').count() >= 2
+                        && domain.split('.').all(|label| !label.is_empty())
+                        && !value.chars().any(char::is_whitespace)
+                }
+                None => false,
+            };
+            if self.value.is_some_and(|value| !value.is_empty() && !valid(value)) {
+                let message = format!("{} 不是有效的邮箱地址！", self.name);
+                self.fail(message);
+            }
+            self
+        }
+
+        /// A mobile number: 11 digits starting with 1, or `+` and 8 to 15 digits in E.164 form
+        pub fn cellphone(mut self) -> Self {
+            let digits = |value: &str| value.chars().all(|c| c.is_ascii_digit());
+            let valid = |value: &str| match value.strip_prefix('+') {
+                Some(number) => (8..=15).contains(&number.len()) && digits(number) && !number.starts_with('0'),
+                None => value.len() == 11 && digits(value) && value.starts_with('1'),
+            };
+            if self.value.is_some_and(|value| !value.is_empty() && !valid(value)) {
+                let message = format!("{} 不是有效的手机号码！", self.name);
+                self.fail(message);
+            }
+            self
+        }
+
+        /// One of the `allowed` values
+        pub fn one_of(mut self, allowed: &[&str]) -> Self {
+            if self.value.is_some_and(|value| !allowed.contains(&value)) {
+                let message = format!("{} 必须是 {} 之一！", self.name, allowed.join("、"));
+                self.fail(message);
+            }
+            self
+        }
+
+        fn fail(&mut self, message: String) {
+            if !self.failed {
+                self.failed = true;
+                self.validator.fields.push(FieldError::new(self.name.as_str(), message));
+            }
+        }
+    }
+}
+
 pub mod result_struct {
     use std::any::Any;
 
     use serde::Serialize;
 
+    use super::exception::FieldError;
+
     #[derive(Serialize, Debug, Default)]
     pub struct RespResult<T> {
         pub code: i32,
         pub data: T,
         pub message: String,
+        /// Stable string code of a failure, see `AppError::error_code`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub error: Option<&'static str>,
+        /// Rejected fields of a request which failed validation
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub fields: Vec<FieldError>,
     }
 
     impl<T> RespResult<T>
@@ -70,14 +410,21 @@ pub mod result_struct {
             Self {
                 code: 0,
                 message: "ok".to_string(),
-                data: data,
+                data,
+                error: None,
+                fields: vec![],
             }
         }
-        pub fn sys_error(data: T) -> Self {
+    }
+
+    impl RespResult<String> {
+        pub fn fail(code: i32, message: String) -> Self {
             Self {
-                code: 500,
-                message: "系统错误！".to_string(),
-                data: data,
+                code,
+                message,
+                data: String::new(),
+                error: None,
+                fields: vec![],
             }
         }
     }
@@ -123,21 +470,208 @@ pub mod paged_struct {
             Some(20)
         }
         fn get_offset(&self) -> Option<u64> {
-            let page = match self.get_page() {
-                Some(page) => page,
-                None => 1,
-            };
-            let size = match self.get_size() {
-                Some(size) => size,
-                None => 20,
-            };
-            return if page == 1 {
+            let page = self.get_page().unwrap_or(1);
+            let size = self.get_size().unwrap_or(20);
+            if page == 1 {
                 Some(0)
             } else {
                 Some((page - 1) * size)
-            };
+            }
+        }
+    }
+}
+
+pub mod rec_sign {
+
+    use sea_orm::{sea_query::Cond, ColumnTrait, Condition};
+
+    use super::{common_func, exception::AppError};
+
+    /**
+     * Fresh version token written to `rec_sign` on every successful write
+     */
+    pub fn generate() -> String {
+        common_func::random_hex(16)
+    }
+
+    /**
+     * Condition matching rows still carrying the version token the caller read.
+     * Rows written before versioning have no token and match an empty one.
+     */
+    pub fn matches<C: ColumnTrait>(column: C, rec_sign: &str) -> Condition {
+        if rec_sign.is_empty() {
+            Cond::any().add(column.is_null()).add(column.eq(""))
+        } else {
+            Cond::all().add(column.eq(rec_sign))
+        }
+    }
+
+    /**
+     * Updates must name the version they were based on
+     */
+    pub fn require(rec_sign: Option<&str>) -> anyhow::Result<()> {
+        match rec_sign {
+            Some(_) => Ok(()),
+            None => Err(AppError::invalid_field("recSign", "缺少版本号 recSign！").into()),
+        }
+    }
+
+    /**
+     * Error for a versioned update which matched no row: the row changed in the meantime,
+     * or it does not exist at all
+     */
+    pub fn stale(exists: bool) -> anyhow::Error {
+        if exists {
+            AppError::Conflict("数据已被他人修改，请刷新后重试！".to_string()).into()
+        } else {
+            AppError::NotFound("数据不存在！".to_string()).into()
+        }
+    }
+}
+
+pub mod tree {
+
+    use std::{collections::HashMap, hash::Hash};
+
+    use serde::Serialize;
+
+    /**
+     * Materialized path of a node below `parent_path`, e.g. `/group/subsidiary/`.
+     * A path lists the codes of all ancestors followed by the node's own code.
+     */
+    pub fn child_path(parent_path: Option<&str>, code: &str) -> String {
+        let parent_path = parent_path.filter(|path| !path.is_empty()).unwrap_or("/");
+        format!("{}{}/", parent_path, code)
+    }
+
+    /**
+     * Codes of the ancestors encoded in `path`, root first, without the node itself
+     */
+    pub fn ancestor_codes(path: &str) -> Vec<String> {
+        let mut codes = path
+            .split('/')
+            .filter(|code| !code.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        codes.pop();
+        codes
+    }
+
+    /**
+     * Whether a `parent_code` value denotes a root node
+     */
+    pub fn is_root(parent_code: Option<&str>) -> bool {
+        parent_code.is_none_or(str::is_empty)
+    }
+
+    #[derive(Serialize, Debug)]
+    pub struct TreeNode<T> {
+        #[serde(flatten)]
+        pub node: T,
+        pub children: Vec<TreeNode<T>>,
+    }
+
+    /**
+     * Nest `items` by key and parent key, keeping the input order among siblings.
+     * Items whose parent is not part of `items` become roots; cycles are broken.
+     */
+    pub fn build_forest<T, K, FK, FP>(items: Vec<T>, key: FK, parent_key: FP) -> Vec<TreeNode<T>>
+    where
+        K: Eq + Hash,
+        FK: Fn(&T) -> Option<K>,
+        FP: Fn(&T) -> Option<K>,
+    {
+        let index: HashMap<K, usize> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| key(item).map(|k| (k, i)))
+            .collect();
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut roots = vec![];
+        for (i, item) in items.iter().enumerate() {
+            match parent_key(item).and_then(|parent| index.get(&parent).copied()) {
+                Some(parent) if parent != i => children.entry(parent).or_default().push(i),
+                _ => roots.push(i),
+            }
+        }
+
+        fn take<T>(i: usize, slots: &mut Vec<Option<T>>, children: &HashMap<usize, Vec<usize>>) -> Option<TreeNode<T>> {
+            let node = slots[i].take()?;
+            let sub_nodes = children
+                .get(&i)
+                .map(|sub| sub.iter().filter_map(|&c| take(c, slots, children)).collect())
+                .unwrap_or_default();
+            Some(TreeNode { node, children: sub_nodes })
+        }
+
+        let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+        let mut forest: Vec<TreeNode<T>> = roots
+            .into_iter()
+            .filter_map(|i| take(i, &mut slots, &children))
+            .collect();
+        for i in 0..slots.len() {
+            if let Some(node) = take(i, &mut slots, &children) {
+                forest.push(node);
+            }
+        }
+        forest
+    }
+}
+
+pub mod desensitize {
+
+    use serde::Deserialize;
+
+    use super::paged_struct::PageData;
+
+    /// Columns a `Desensitize` impl clears, by column name: the id card number and the
+    /// WeChat ids of `user`, `user_info` and `user_wechat_info`
+    pub const SENSITIVE_COLUMNS: [&str; 9] = [
+        "id_card_no",
+        "open_id",
+        "wechat_open_id",
+        "wechat_union_id",
+        "wx_union_id",
+        "wx_open_id",
+        "wx_mini_open_id",
+        "union_id",
+        "mini_open_id",
+    ];
+
+    /// Vo types carrying sensitive columns (id card numbers, open ids, ...) which
+    /// are only emitted to privileged callers asking for them explicitly,
+    /// an impl clears exactly the fields of its Vo named in `SENSITIVE_COLUMNS`
+    pub trait Desensitize {
+        /// Clear every sensitive field so that it is skipped on serialization
+        fn desensitize(&mut self);
+    }
+
+    impl<T: Desensitize> Desensitize for Vec<T> {
+        fn desensitize(&mut self) {
+            self.iter_mut().for_each(Desensitize::desensitize);
         }
     }
+
+    impl<T: Desensitize> Desensitize for Option<T> {
+        fn desensitize(&mut self) {
+            if let Some(value) = self {
+                value.desensitize();
+            }
+        }
+    }
+
+    impl<T: Desensitize> Desensitize for PageData<T> {
+        fn desensitize(&mut self) {
+            self.page_data.desensitize();
+        }
+    }
+
+    /// Query parameter asking for the sensitive projection of a record
+    #[derive(Deserialize, Debug, Default)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SensitiveProjection {
+        pub include_sensitive: Option<bool>,
+    }
 }
 
 pub mod date_format {
@@ -145,7 +679,7 @@ pub mod date_format {
 
     use sea_orm::prelude::DateTimeLocal;
     use serde::Serializer;
-    const FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+    const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
     // The signature of a serialize_with function must follow the pattern:
     //
@@ -158,11 +692,9 @@ pub mod date_format {
     where
         S: Serializer,
     {
-        if date.is_none() {
-            serializer.serialize_none()
-        } else {
-            let s = format!("{}", date.unwrap().format(FORMAT));
-            serializer.serialize_str(&s)
+        match date {
+            Some(date) => serializer.serialize_str(&date.format(FORMAT).to_string()),
+            None => serializer.serialize_none(),
         }
     }
 
@@ -189,3 +721,406 @@ pub mod date_format {
     }
     */
 }
+
+pub mod jwt {
+
+    use anyhow::{anyhow, bail, Result};
+    use openssl::{
+        base64,
+        hash::MessageDigest,
+        pkey::{PKey, Private, Public},
+        rsa::Rsa,
+        sign::{Signer, Verifier},
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+    use tracing::warn;
+
+    const HEADER: &str = r#"{"alg":"RS256","typ":"JWT"}"#;
+
+    /// RS256 key pair and token settings used to issue and verify access tokens
+    pub struct JwtKeys {
+        private_key: PKey<Private>,
+        public_key: PKey<Public>,
+        public_key_pem: String,
+        pub issuer: String,
+        pub access_token_ttl: i64,
+        pub refresh_token_ttl: i64,
+    }
+
+    impl JwtKeys {
+        /**
+         * Load the signing key from `JWT_PRIVATE_KEY_PATH` (PKCS#1 or PKCS#8 PEM).
+         * Without it an ephemeral key is generated, so tokens do not survive a restart.
+         */
+        pub fn from_env() -> Result<Self> {
+            let rsa = match std::env::var("JWT_PRIVATE_KEY_PATH") {
+                Ok(path) => Rsa::private_key_from_pem(&std::fs::read(path)?)?,
+                Err(_) => {
+                    warn!("JWT_PRIVATE_KEY_PATH is not set, generating an ephemeral RSA key");
+                    Rsa::generate(2048)?
+                }
+            };
+            let issuer = std::env::var("JWT_ISSUER").unwrap_or_else(|_| "auth-center".to_string());
+            let ttl_from_env = |key: &str, default: i64| {
+                std::env::var(key)
+                    .ok()
+                    .and_then(|ttl| ttl.parse::<i64>().ok())
+                    .unwrap_or(default)
+            };
+            let mut jwt_keys = Self::from_rsa(rsa, issuer, ttl_from_env("JWT_ACCESS_TOKEN_TTL", 1800))?;
+            jwt_keys.refresh_token_ttl = ttl_from_env("JWT_REFRESH_TOKEN_TTL", 30 * 24 * 3600);
+            Ok(jwt_keys)
+        }
+
+        pub fn from_rsa(rsa: Rsa<Private>, issuer: String, access_token_ttl: i64) -> Result<Self> {
+            let public_key_pem = String::from_utf8(rsa.public_key_to_pem()?)?;
+            let public_key = PKey::public_key_from_pem(public_key_pem.as_bytes())?;
+            Ok(Self {
+                private_key: PKey::from_rsa(rsa)?,
+                public_key,
+                public_key_pem,
+                issuer,
+                access_token_ttl,
+                refresh_token_ttl: 30 * 24 * 3600,
+            })
+        }
+
+        pub fn public_key_pem(&self) -> &str {
+            &self.public_key_pem
+        }
+
+        /**
+         * Serialize and sign `claims` into a compact RS256 JWT
+         */
+        pub fn encode<T: Serialize>(&self, claims: &T) -> Result<String> {
+            let signing_input = format!(
+                "{}.{}",
+                base64_url_encode(HEADER.as_bytes()),
+                base64_url_encode(&serde_json::to_vec(claims)?)
+            );
+            let mut signer = Signer::new(MessageDigest::sha256(), &self.private_key)?;
+            signer.update(signing_input.as_bytes())?;
+            let signature = signer.sign_to_vec()?;
+            Ok(format!("{}.{}", signing_input, base64_url_encode(&signature)))
+        }
+
+        /**
+         * Verify signature, algorithm, `iss` and `exp` of `token`, then deserialize its claims
+         */
+        pub fn decode<T: DeserializeOwned>(&self, token: &str) -> Result<T> {
+            let mut parts = token.split('.');
+            let (Some(header), Some(payload), Some(signature), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                bail!("malformed token");
+            };
+            let header: serde_json::Value = serde_json::from_slice(&base64_url_decode(header)?)?;
+            if header["alg"] != "RS256" {
+                bail!("unsupported token algorithm");
+            }
+            let mut verifier = Verifier::new(MessageDigest::sha256(), &self.public_key)?;
+            verifier.update(signing_input(token).as_bytes())?;
+            if !verifier.verify(&base64_url_decode(signature)?)? {
+                bail!("invalid token signature");
+            }
+            let claims: serde_json::Value = serde_json::from_slice(&base64_url_decode(payload)?)?;
+            if claims["iss"] != self.issuer.as_str() {
+                bail!("token of another issuer");
+            }
+            let exp = claims["exp"].as_i64().ok_or_else(|| anyhow!("token without exp"))?;
+            if exp < chrono::Local::now().timestamp() {
+                bail!("token expired");
+            }
+            Ok(serde_json::from_value(claims)?)
+        }
+    }
+
+    fn signing_input(token: &str) -> &str {
+        token.rsplit_once('.').map(|(prefix, _)| prefix).unwrap_or(token)
+    }
+
+    fn base64_url_encode(bytes: &[u8]) -> String {
+        base64::encode_block(bytes)
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_")
+    }
+
+    fn base64_url_decode(text: &str) -> Result<Vec<u8>> {
+        let mut std_text = text.replace('-', "+").replace('_', "/");
+        while !std_text.len().is_multiple_of(4) {
+            std_text.push('=');
+        }
+        Ok(base64::decode_block(&std_text)?)
+    }
+}
+
+pub mod password {
+
+    use anyhow::Result;
+    use once_cell::sync::Lazy;
+    use openssl::{base64, hash::MessageDigest, memcmp, pkcs5::pbkdf2_hmac, rand::rand_bytes};
+
+    const ALGORITHM: &str = "pbkdf2-sha256";
+    const SALT_LEN: usize = 16;
+    const HASH_LEN: usize = 32;
+
+    /// PBKDF2 iteration count for new hashes, overridable by `PASSWORD_HASH_ITERATIONS`
+    static ITERATIONS: Lazy<u32> = Lazy::new(|| {
+        std::env::var("PASSWORD_HASH_ITERATIONS")
+            .ok()
+            .and_then(|iterations| iterations.parse::<u32>().ok())
+            .unwrap_or(310_000)
+    });
+
+    /**
+     * Hash `plain` with a random salt into `$pbkdf2-sha256$i=<iterations>$<salt>$<hash>`
+     */
+    pub fn hash(plain: &str) -> Result<String> {
+        let mut salt = [0_u8; SALT_LEN];
+        rand_bytes(&mut salt)?;
+        let derived = derive(plain, &salt, *ITERATIONS)?;
+        Ok(format!(
+            "${}$i={}${}${}",
+            ALGORITHM,
+            *ITERATIONS,
+            base64::encode_block(&salt),
+            base64::encode_block(&derived)
+        ))
+    }
+
+    /**
+     * Check `plain` against a stored value. Values without the algorithm prefix are
+     * legacy plaintext rows and are compared as is.
+     */
+    pub fn verify(plain: &str, stored: &str) -> bool {
+        match parse(stored) {
+            Some((iterations, salt, expected)) => derive(plain, &salt, iterations)
+                .map(|derived| memcmp::eq(&derived, &expected))
+                .unwrap_or(false),
+            None if stored.starts_with('$') => false,
+            None => stored.len() == plain.len() && memcmp::eq(stored.as_bytes(), plain.as_bytes()),
+        }
+    }
+
+    /**
+     * Whether a stored value is plaintext or was hashed with other parameters than the current ones
+     */
+    pub fn needs_rehash(stored: &str) -> bool {
+        !matches!(parse(stored), Some((iterations, _, _)) if iterations == *ITERATIONS)
+    }
+
+    fn derive(plain: &str, salt: &[u8], iterations: u32) -> Result<Vec<u8>> {
+        let mut derived = vec![0_u8; HASH_LEN];
+        pbkdf2_hmac(plain.as_bytes(), salt, iterations as usize, MessageDigest::sha256(), &mut derived)?;
+        Ok(derived)
+    }
+
+    fn parse(stored: &str) -> Option<(u32, Vec<u8>, Vec<u8>)> {
+        let mut parts = stored.strip_prefix('$')?.split('$');
+        if parts.next()? != ALGORITHM {
+            return None;
+        }
+        let iterations = parts.next()?.strip_prefix("i=")?.parse::<u32>().ok()?;
+        let salt = base64::decode_block(parts.next()?).ok()?;
+        let hash = base64::decode_block(parts.next()?).ok()?;
+        if parts.next().is_some() || iterations == 0 {
+            return None;
+        }
+        Some((iterations, salt, hash))
+    }
+}
+
+pub mod audit {
+
+    use std::{future::Future, str::FromStr};
+
+    use chrono::NaiveDateTime;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Value};
+
+    /**
+     * Who a request acts for and where it came from
+     */
+    #[derive(Debug, Clone, Default)]
+    pub struct AuditContext {
+        /// `user.id` of the authenticated account
+        pub principal_id: i64,
+        pub user_code: Option<String>,
+        pub org_code: Option<String>,
+        pub ip: Option<String>,
+        /// Whether the account is an administrator, which no data scope applies to
+        pub is_admin: bool,
+    }
+
+    tokio::task_local! {
+        static CONTEXT: AuditContext;
+    }
+
+    /**
+     * Run `f` on behalf of the authenticated account described by `context`
+     */
+    pub async fn scope<F: Future>(context: AuditContext, f: F) -> F::Output {
+        CONTEXT.scope(context, f).await
+    }
+
+    /**
+     * Context of the current request, empty outside of an authenticated request
+     */
+    pub fn context() -> AuditContext {
+        CONTEXT.try_with(AuditContext::clone).unwrap_or_default()
+    }
+
+    /**
+     * Account the current request acts for, 0 outside of an authenticated request
+     */
+    pub fn principal_id() -> i64 {
+        CONTEXT.try_with(|context| context.principal_id).unwrap_or(0)
+    }
+
+    pub fn now() -> NaiveDateTime {
+        chrono::Local::now().naive_local()
+    }
+
+    fn set_column<A: ActiveModelTrait>(active_model: &mut A, name: &str, value: Value) {
+        if let Ok(column) = <A::Entity as EntityTrait>::Column::from_str(name) {
+            active_model.set(column, value);
+        }
+    }
+
+    fn unset_column<A: ActiveModelTrait>(active_model: &mut A, name: &str) {
+        if let Ok(column) = <A::Entity as EntityTrait>::Column::from_str(name) {
+            active_model.not_set(column);
+        }
+    }
+
+    /**
+     * Stamp `create_by`/`update_by` and `create_time`/`update_time` of a row about to be inserted
+     */
+    pub fn stamp_insert<A: ActiveModelTrait>(active_model: &mut A) {
+        let (principal_id, now) = (principal_id(), now());
+        set_column(active_model, "create_by", principal_id.into());
+        set_column(active_model, "update_by", principal_id.into());
+        set_column(active_model, "create_time", now.into());
+        set_column(active_model, "update_time", now.into());
+    }
+
+    /**
+     * Stamp `update_by`/`update_time` of a row about to be updated, the creation
+     * columns are never taken from the request body
+     */
+    pub fn stamp_update<A: ActiveModelTrait>(active_model: &mut A) {
+        unset_column(active_model, "create_by");
+        unset_column(active_model, "create_time");
+        set_column(active_model, "update_by", principal_id().into());
+        set_column(active_model, "update_time", now().into());
+    }
+
+    /**
+     * Whether a column is never written to the audit log in clear text: the password
+     * and every column hidden from the API by `Desensitize`
+     */
+    pub fn is_redacted(column: &str) -> bool {
+        column == "password" || super::desensitize::SENSITIVE_COLUMNS.contains(&column)
+    }
+
+    /**
+     * Replace the values of the redacted columns at any depth, a changed secret stays visible as changed
+     */
+    pub fn redact(value: serde_json::Value) -> serde_json::Value {
+        use serde_json::Value as Json;
+        match value {
+            Json::Object(fields) => Json::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let value = if is_redacted(&key) && !value.is_null() {
+                            Json::String("******".to_string())
+                        } else {
+                            redact(value)
+                        };
+                        (key, value)
+                    })
+                    .collect(),
+            ),
+            Json::Array(items) => Json::Array(items.into_iter().map(redact).collect()),
+            other => other,
+        }
+    }
+
+    /**
+     * Changed columns per row id, `{"<id>": {"<column>": {"before": .., "after": ..}}}`.
+     * Rows only present on one side report every non-null column.
+     */
+    pub fn diff_rows(before: &[serde_json::Value], after: &[serde_json::Value]) -> serde_json::Value {
+        use serde_json::{json, Map, Value as Json};
+        let row_id = |row: &Json| row.get("id").map(|id| id.to_string());
+        let mut ids: Vec<String> = before.iter().chain(after.iter()).filter_map(row_id).collect();
+        ids.sort();
+        ids.dedup();
+
+        let find = |rows: &[Json], id: &str| rows.iter().find(|row| row_id(row).as_deref() == Some(id)).cloned();
+        let mut diff = Map::new();
+        for id in ids {
+            let before_row = find(before, &id).unwrap_or(Json::Null);
+            let after_row = find(after, &id).unwrap_or(Json::Null);
+            let mut columns: Vec<&String> = before_row
+                .as_object()
+                .into_iter()
+                .chain(after_row.as_object())
+                .flat_map(|row| row.keys())
+                .collect();
+            columns.sort();
+            columns.dedup();
+            let changes: Map<String, Json> = columns
+                .into_iter()
+                .filter_map(|column| {
+                    let old = before_row.get(column).cloned().unwrap_or(Json::Null);
+                    let new = after_row.get(column).cloned().unwrap_or(Json::Null);
+                    (old != new).then(|| (column.clone(), json!({ "before": old, "after": new })))
+                })
+                .collect();
+            if !changes.is_empty() {
+                diff.insert(id, Json::Object(changes));
+            }
+        }
+        Json::Object(diff)
+    }
+}
+
+pub mod tenant {
+
+    use std::future::Future;
+
+    use super::exception::AppError;
+
+    tokio::task_local! {
+        static TENANT: Option<String>;
+    }
+
+    /**
+     * Run `f` confined to the rows of organization `org_code`, `None` runs it unconfined
+     */
+    pub async fn scope<F: Future>(org_code: Option<String>, f: F) -> F::Output {
+        TENANT.scope(org_code, f).await
+    }
+
+    /**
+     * Organization the current request is confined to, `None` for a super-admin
+     * without an `X-Org-Code` header and outside of a request
+     */
+    pub fn current() -> Option<String> {
+        TENANT.try_with(Option::clone).ok().flatten()
+    }
+
+    /**
+     * Reject an `org_code` written by the current request outside of its organization
+     */
+    pub fn check(org_code: Option<&str>) -> Result<(), AppError> {
+        match (org_code.filter(|org_code| !org_code.is_empty()), current()) {
+            (Some(org_code), Some(tenant)) if org_code != tenant => {
+                Err(AppError::Forbidden(format!("无权访问组织 {} 的数据！", org_code)))
+            }
+            _ => Ok(()),
+        }
+    }
+}
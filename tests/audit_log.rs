@@ -0,0 +1,123 @@
+//! The audit log written by every mutation and read through `/audit/page`
+
+mod common;
+
+use std::net::SocketAddr;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Method, Request},
+};
+use common::{fixtures, TestApp, TRUSTED_PROXY};
+use sea_orm::ConnectionTrait;
+use serde_json::{json, Value};
+
+/// Entries of `entity` in the audit log, oldest first
+async fn entries(app: &TestApp, entity: &str) -> Vec<Value> {
+    let response = app.get(&format!("/audit/page?entity={}&size=100", entity)).await;
+    let mut entries = response.data()["page_data"].as_array().unwrap().clone();
+    entries.sort_by_key(|entry| entry["id"].as_i64());
+    entries
+}
+
+fn actions(entries: &[Value]) -> Vec<&str> {
+    entries.iter().map(|entry| entry["action"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn sensitive_columns_never_reach_the_audit_log() {
+    let app = TestApp::spawn().await;
+    fixtures::organization(&app, "ORG1").await;
+    let id = app
+        .create(
+            "/userInfo",
+            json!({
+                "userCode": "U1",
+                "realName": "U1 name",
+                "userType": "staff",
+                "orgCode": "ORG1",
+                "idCardNo": "110101199001011234",
+                "wxUnionId": "secret-union",
+                "wxOpenId": "secret-open",
+                "wxMiniOpenId": "secret-mini",
+            }),
+        )
+        .await;
+    app.post("/userWechatInfo", json!({ "recId": 1, "unionId": "secret-union", "miniOpenId": "secret-mini" }))
+        .await
+        .data();
+    let rec_sign = app.read("/userInfo", id).await["recSign"].clone();
+    app.put("/userInfo", json!({ "recId": id, "recSign": rec_sign, "wxOpenId": "secret-open-2" })).await.data();
+
+    let logged = [entries(&app, "user_info").await, entries(&app, "user_wechat_info").await].concat();
+    assert_eq!(logged.len(), 3);
+    let logged = Value::Array(logged).to_string();
+    for secret in ["110101199001011234", "secret-union", "secret-open", "secret-mini"] {
+        assert!(!logged.contains(secret), "{} in the audit log", secret);
+    }
+    assert!(logged.contains("******"));
+}
+
+#[tokio::test]
+async fn deletes_reaching_no_row_are_not_logged() {
+    let app = TestApp::spawn().await;
+    let org1 = fixtures::organization(&app, "ORG1").await;
+    let org2 = fixtures::organization(&app, "ORG2").await;
+
+    assert_eq!(app.put("/organization/delByIds", json!({ "recIds": [9999] })).await.data(), 0);
+    assert_eq!(app.delete("/organization", json!({ "recIds": [9999] })).await.data(), 0);
+    let response = app
+        .send_in_org(Method::PUT, "/organization/delByIds", Some(&app.token), Some("ORG1"), Some(json!({ "recIds": [org1, org2] })))
+        .await;
+    assert_eq!(response.data(), 1);
+
+    let logged = entries(&app, "organization").await;
+    assert_eq!(actions(&logged), ["save", "save", "delete"]);
+    assert_eq!(logged[2]["recIds"], json!([org1]));
+}
+
+#[tokio::test]
+async fn a_mutation_whose_audit_entry_fails_is_rolled_back() {
+    let app = TestApp::spawn().await;
+    let id = fixtures::organization(&app, "ORG1").await;
+    let rec_sign = app.read("/organization", id).await["recSign"].clone();
+
+    app.db().execute_unprepared("ALTER TABLE audit_log RENAME TO audit_log_off").await.unwrap();
+    let response = app.post("/organization", json!({ "code": "ORG2", "name": "ORG2 org" })).await;
+    assert_eq!(response.status, 500);
+    let response = app.put("/organization", json!({ "recId": id, "recSign": rec_sign, "name": "renamed" })).await;
+    assert_eq!(response.status, 500);
+    let response = app.put("/organization/delByIds", json!({ "recIds": [id] })).await;
+    assert_eq!(response.status, 500);
+    app.db().execute_unprepared("ALTER TABLE audit_log_off RENAME TO audit_log").await.unwrap();
+
+    let organizations = app.get("/organization/list").await.data().clone();
+    let organizations: Vec<(&Value, &Value)> =
+        organizations.as_array().unwrap().iter().map(|record| (&record["code"], &record["name"])).collect();
+    assert_eq!(organizations, [(&json!("ORG1"), &json!("ORG1 org"))]);
+    assert_eq!(actions(&entries(&app, "organization").await), ["save"]);
+}
+
+#[tokio::test]
+async fn forwarding_headers_are_only_believed_from_a_trusted_proxy() {
+    let app = TestApp::spawn().await;
+    let create = |code: &str, peer: &str, forwarded_for: &str| {
+        let body = json!({ "code": code, "name": format!("{} org", code) });
+        Request::builder()
+            .method(Method::POST)
+            .uri("/organization")
+            .header(header::AUTHORIZATION, format!("Bearer {}", app.token))
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-forwarded-for", forwarded_for)
+            .extension(ConnectInfo(format!("{}:40000", peer).parse::<SocketAddr>().unwrap()))
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    app.dispatch(create("ORG1", "192.0.2.7", "198.51.100.1")).await.data();
+    app.dispatch(create("ORG2", TRUSTED_PROXY, &format!("198.51.100.1, 203.0.113.5, {}", TRUSTED_PROXY))).await.data();
+
+    let ips: Vec<Value> = entries(&app, "organization").await.iter().map(|entry| entry["ip"].clone()).collect();
+    assert_eq!(ips, [json!("192.0.2.7"), json!("203.0.113.5")]);
+}
@@ -0,0 +1,146 @@
+//! Route permissions enforced by the middleware and the `/authz/check` endpoints
+
+mod common;
+
+use axum::http::Method;
+use common::{fixtures, TestApp, TestResponse};
+use serde_json::{json, Value};
+
+/// ORG1 and ORG2, person U1 of ORG1 with account U1 holding role READER, and role WRITER
+/// granted to everyone working for ORG2; the permissions belong to application `bi`
+async fn seed(app: &TestApp) -> String {
+    for org_code in ["ORG1", "ORG2"] {
+        fixtures::organization(app, org_code).await;
+    }
+    fixtures::department(app, "D1", "ORG1").await;
+    fixtures::position(app, "P1", "D1", "ORG1").await;
+    for (index, code) in ["report:read", "report:write"].iter().enumerate() {
+        app.create(
+            "/permission",
+            json!({ "uuid": format!("p{}", index), "code": code, "name": code, "nodeType": "button", "application": "bi" }),
+        )
+        .await;
+    }
+    app.create("/role", json!({ "code": "READER", "name": "Reader", "permissions": ["report:read"] })).await;
+    app.create("/role", json!({ "code": "WRITER", "name": "Writer", "permissions": ["report:write"] })).await;
+    fixtures::user_info(app, "U1", "ORG1", "D1", "P1").await;
+    app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "READER" })).await;
+    app.create("/organizationRoleRef", json!({ "orgCode": "ORG2", "roleCode": "WRITER" })).await;
+    fixtures::user(app, "U1", "u1@pass").await;
+    app.login("U1", "u1@pass").await
+}
+
+async fn get_as(app: &TestApp, token: &str, uri: &str) -> TestResponse {
+    app.send(Method::GET, uri, Some(token), None).await
+}
+
+async fn api_node(app: &TestApp, uuid: &str, link_url: &str, code: &str) {
+    app.create("/permission", json!({ "uuid": uuid, "code": code, "name": link_url, "nodeType": "api", "linkUrl": link_url }))
+        .await;
+}
+
+async fn check(app: &TestApp, body: Value) -> Value {
+    app.post("/authz/check", body).await.data().clone()
+}
+
+#[tokio::test]
+async fn a_check_answers_with_the_granting_roles() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+
+    let result = check(&app, json!({ "userCode": "U1", "permissionCode": "report:read" })).await;
+    assert_eq!(result["allowed"], true);
+    assert_eq!(result["grantedBy"], json!(["READER"]));
+    let result = check(&app, json!({ "userCode": "U1", "permissionCode": "report:write" })).await;
+    assert_eq!(result["allowed"], false);
+    assert_eq!(result["grantedBy"], json!([]));
+    let result = check(&app, json!({ "userCode": "U1", "permissionCode": "report:read", "application": "crm" })).await;
+    assert_eq!(result["allowed"], false);
+
+    let result = check(&app, json!({ "token": token, "permissionCode": "report:read", "application": "bi" })).await;
+    assert_eq!(result["allowed"], true);
+}
+
+#[tokio::test]
+async fn a_batch_check_answers_in_request_order() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    let checks = json!([
+        { "permissionCode": "report:write" },
+        { "permissionCode": "report:read" },
+        { "permissionCode": "report:export" },
+    ]);
+    let response = app.post("/authz/check-batch", json!({ "userCode": "U1", "checks": checks })).await;
+    let results: Vec<(Value, Value)> = response
+        .data()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| (result["permissionCode"].clone(), result["allowed"].clone()))
+        .collect();
+    assert_eq!(
+        results,
+        [
+            (json!("report:write"), json!(false)),
+            (json!("report:read"), json!(true)),
+            (json!("report:export"), json!(false)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn another_org_only_grants_its_roles_to_the_people_working_there() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    let in_org2 = json!({ "userCode": "U1", "permissionCode": "report:write", "orgCode": "ORG2" });
+
+    assert_eq!(check(&app, in_org2.clone()).await["allowed"], false);
+    let response = app.get("/authz/users/U1/data-scope?orgCode=ORG2").await;
+    assert_eq!(response.data()["scope"], "self");
+
+    app.create(
+        "/userInfo",
+        json!({ "userCode": "U1", "username": "u1-org2", "realName": "U1 name", "userType": "staff", "orgCode": "ORG2" }),
+    )
+    .await;
+    let result = check(&app, in_org2).await;
+    assert_eq!(result["allowed"], true);
+    assert_eq!(result["grantedBy"], json!(["WRITER"]));
+    let response = app.get("/authz/users/U1/data-scope?orgCode=ORG2").await;
+    assert_eq!(response.data()["scope"], "org");
+}
+
+#[tokio::test]
+async fn a_route_needs_its_permission_unless_the_caller_is_an_administrator() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+
+    let response = get_as(&app, &token, "/organization/list").await;
+    assert_eq!(response.status, 403);
+    assert_eq!(response.body["error"], "forbidden");
+    assert_eq!(response.body["message"], "缺少权限 organization:read！");
+    assert_eq!(get_as(&app, &app.token, "/organization/list").await.status, 200);
+
+    app.create("/permission", json!({ "uuid": "p9", "code": "organization:read", "name": "Organizations", "nodeType": "button" }))
+        .await;
+    app.create("/role", json!({ "code": "ORG_VIEWER", "name": "Org viewer", "permissions": ["organization:read"] })).await;
+    app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "ORG_VIEWER" })).await;
+    assert_eq!(get_as(&app, &token, "/organization/list").await.status, 200);
+    let response = app.send(Method::POST, "/organization", Some(&token), Some(json!({ "code": "ORG3", "name": "ORG3" }))).await;
+    assert_eq!(response.body["message"], "缺少权限 organization:write！");
+}
+
+#[tokio::test]
+async fn an_api_node_remaps_the_permission_of_a_route() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+
+    api_node(&app, "api-1", "/organization/list", "report:write").await;
+    let response = get_as(&app, &token, "/organization/list").await;
+    assert_eq!(response.body["message"], "缺少权限 report:write！");
+
+    api_node(&app, "api-2", "GET /organization/list", "report:read").await;
+    assert_eq!(get_as(&app, &token, "/organization/list").await.status, 200);
+    assert_eq!(get_as(&app, &token, "/organization/tree").await.status, 403);
+}
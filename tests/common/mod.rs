@@ -0,0 +1,255 @@
+//! Harness of the integration tests: the application router on a freshly migrated
+//! in-memory SQLite database, driven in process without binding a socket
+
+#![allow(dead_code)]
+
+use std::sync::{Arc, Once};
+
+use auth_center::{
+    connect_db,
+    entities::user,
+    init_status,
+    mapper::Mappers,
+    migration::Migrator,
+    route::app_route,
+    svc::Services,
+    util::{jwt::JwtKeys, password},
+    AppState,
+};
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use sea_orm_migration::MigratorTrait;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+pub const ADMIN_USERNAME: &str = "admin";
+pub const ADMIN_PASSWORD: &str = "admin@123";
+
+/// The one reverse proxy whose forwarding headers the application believes
+pub const TRUSTED_PROXY: &str = "10.0.0.9";
+
+static ENV: Once = Once::new();
+
+/// The environment `init_status` reads, the same for every test of the binary
+fn init_env() {
+    ENV.call_once(|| {
+        std::env::set_var("DATABASE_URL", "sqlite::memory:");
+        std::env::set_var("AUTO_MIGRATE", "true");
+        // the default iteration count makes every fixture login take a noticeable time
+        std::env::set_var("PASSWORD_HASH_ITERATIONS", "1000");
+        std::env::set_var("TRUSTED_PROXIES", TRUSTED_PROXY);
+    });
+}
+
+/// One application with a database of its own, logged in as an administrator
+pub struct TestApp {
+    pub router: Router,
+    pub state: Arc<AppState>,
+    pub token: String,
+}
+
+/// Status and decoded body of a response, `Value::Null` for a body that is not JSON
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+impl TestResponse {
+    /// `data` of a `RespResult` answered with code 0
+    pub fn data(&self) -> &Value {
+        assert_eq!(self.status, StatusCode::OK, "unexpected response {}", self.body);
+        assert_eq!(self.body["code"], 0, "unexpected response {}", self.body);
+        &self.body["data"]
+    }
+
+    /// `code` of a `RespResult`
+    pub fn code(&self) -> i64 {
+        self.body["code"].as_i64().unwrap_or_default()
+    }
+}
+
+impl TestApp {
+    /// Builds the router `build_app_route` serves on a new in-memory database
+    /// and seeds the administrator account used by the requests
+    pub async fn spawn() -> Self {
+        init_env();
+        let state = init_status().await.expect("application state");
+        Self::start(state).await
+    }
+
+    /// Like `spawn`, the mappers first handed to `customize`, e.g. to replace one with a fake
+    pub async fn spawn_with(customize: impl FnOnce(&mut Mappers)) -> Self {
+        init_env();
+        let db = connect_db().await.expect("database");
+        Migrator::up(&db, None).await.expect("migrations");
+        let mut mappers = Mappers::new(&db);
+        customize(&mut mappers);
+        let jwt_keys = Arc::new(JwtKeys::from_env().expect("jwt keys"));
+        let svcs = Arc::new(Services::new(&mappers, jwt_keys));
+        Self::start(Arc::new(AppState::with_services(db, svcs))).await
+    }
+
+    async fn start(state: Arc<AppState>) -> Self {
+        let router = app_route(state.clone());
+        user::ActiveModel {
+            user_name: Set(ADMIN_USERNAME.to_string()),
+            real_name: Set(Some("Administrator".to_string())),
+            password: Set(Some(password::hash(ADMIN_PASSWORD).unwrap())),
+            is_admin: Set(Some(1)),
+            ..Default::default()
+        }
+        .insert(state.db())
+        .await
+        .expect("administrator");
+
+        let mut app = Self { router, state, token: String::new() };
+        app.token = app.login(ADMIN_USERNAME, ADMIN_PASSWORD).await;
+        app
+    }
+
+    pub fn db(&self) -> &DatabaseConnection {
+        self.state.db()
+    }
+
+    /// Access token of an account
+    pub async fn login(&self, username: &str, password: &str) -> String {
+        let response = self
+            .send(Method::POST, "/auth/login", None, Some(json!({ "username": username, "password": password })))
+            .await;
+        response.data()["accessToken"].as_str().expect("access token").to_string()
+    }
+
+    /// Sends a request as the administrator
+    pub async fn request(&self, method: Method, uri: &str, body: Option<Value>) -> TestResponse {
+        self.send(method, uri, Some(&self.token), body).await
+    }
+
+    pub async fn get(&self, uri: &str) -> TestResponse {
+        self.request(Method::GET, uri, None).await
+    }
+
+    pub async fn post(&self, uri: &str, body: Value) -> TestResponse {
+        self.request(Method::POST, uri, Some(body)).await
+    }
+
+    pub async fn put(&self, uri: &str, body: Value) -> TestResponse {
+        self.request(Method::PUT, uri, Some(body)).await
+    }
+
+    pub async fn delete(&self, uri: &str, body: Value) -> TestResponse {
+        self.request(Method::DELETE, uri, Some(body)).await
+    }
+
+    /// Sends a request with an optional bearer token
+    pub async fn send(&self, method: Method, uri: &str, token: Option<&str>, body: Option<Value>) -> TestResponse {
+        self.send_in_org(method, uri, token, None, body).await
+    }
+
+    /// Sends a request acting on organization `org_code` through the `X-Org-Code` header
+    pub async fn send_in_org(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        org_code: Option<&str>,
+        body: Option<Value>,
+    ) -> TestResponse {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        if let Some(org_code) = org_code {
+            builder = builder.header("x-org-code", org_code);
+        }
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap();
+        self.dispatch(request).await
+    }
+
+    /// Sends a request built by the caller
+    pub async fn dispatch(&self, request: Request<Body>) -> TestResponse {
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        TestResponse { status, body }
+    }
+
+    /// Creates a record through `POST base` and returns its id
+    pub async fn create(&self, base: &str, body: Value) -> i64 {
+        self.post(base, body).await.data().as_i64().expect("record id")
+    }
+
+    /// A record read back through `GET base/:id`, sensitive columns included
+    pub async fn read(&self, base: &str, id: i64) -> Value {
+        self.get(&format!("{}/{}?includeSensitive=true", base, id)).await.data().clone()
+    }
+}
+
+/// Records most tests start from, created through the API like a client would
+pub mod fixtures {
+    use serde_json::json;
+
+    use super::TestApp;
+
+    pub async fn organization(app: &TestApp, code: &str) -> i64 {
+        app.create("/organization", json!({ "code": code, "name": format!("{} org", code) })).await
+    }
+
+    pub async fn department(app: &TestApp, code: &str, org_code: &str) -> i64 {
+        app.create("/department", json!({ "code": code, "name": format!("{} dept", code), "orgCode": org_code }))
+            .await
+    }
+
+    pub async fn position(app: &TestApp, code: &str, department_code: &str, org_code: &str) -> i64 {
+        app.create(
+            "/position",
+            json!({ "code": code, "name": format!("{} position", code), "departmentCode": department_code, "orgCode": org_code }),
+        )
+        .await
+    }
+
+    pub async fn group(app: &TestApp, code: &str, org_code: &str) -> i64 {
+        app.create(
+            "/group",
+            json!({ "code": code, "name": format!("{} group", code), "type": "team", "application": "auth", "orgCode": org_code }),
+        )
+        .await
+    }
+
+    pub async fn role(app: &TestApp, code: &str) -> i64 {
+        app.create("/role", json!({ "code": code, "name": format!("{} role", code), "application": "auth" })).await
+    }
+
+    /// A person of `org_code`, placed in a department and a position
+    pub async fn user_info(app: &TestApp, user_code: &str, org_code: &str, department_code: &str, position_code: &str) -> i64 {
+        app.create(
+            "/userInfo",
+            json!({
+                "userCode": user_code,
+                "username": user_code,
+                "realName": format!("{} name", user_code),
+                "userType": "staff",
+                "orgCode": org_code,
+                "departmentCode": department_code,
+                "positionCode": position_code,
+            }),
+        )
+        .await
+    }
+
+    /// A login account, with its password hashed by the `/user` hooks
+    pub async fn user(app: &TestApp, user_name: &str, password: &str) -> i64 {
+        app.create("/user", json!({ "userName": user_name, "password": password })).await
+    }
+}
@@ -0,0 +1,263 @@
+//! Every route `CrudRoutes::crud` registers, exercised for each resource of the router
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use auth_center::{
+    entities::group,
+    mapper::{crud_mapper::CrudMapperTrait, group_mapper::GroupResource},
+    pojo::group_pojo::{GroupCondition, GroupDto, GroupVo},
+    util::paged_struct::{PageData, PageInfo},
+};
+use common::TestApp;
+use sea_orm::{DbErr, EntityTrait, PaginatorTrait};
+use serde_json::{json, Value};
+
+/// A resource served under `base`, the body creating one record and a change applied to it
+struct Resource {
+    base: &'static str,
+    create: Value,
+    update: Value,
+}
+
+fn resources() -> Vec<Resource> {
+    vec![
+        Resource {
+            base: "/organization",
+            create: json!({ "code": "ORG1", "name": "Head office", "type": "company" }),
+            update: json!({ "name": "Head office renamed" }),
+        },
+        Resource {
+            base: "/department",
+            create: json!({ "code": "D1", "name": "R&D", "orgCode": "ORG1" }),
+            update: json!({ "description": "research and development" }),
+        },
+        Resource {
+            base: "/position",
+            create: json!({ "code": "P1", "name": "Engineer", "departmentCode": "D1", "orgCode": "ORG1" }),
+            update: json!({ "name": "Senior engineer" }),
+        },
+        Resource {
+            base: "/group",
+            create: json!({ "code": "G1", "name": "Reviewers", "type": "team", "application": "auth", "orgCode": "ORG1" }),
+            update: json!({ "description": "code reviewers" }),
+        },
+        Resource {
+            base: "/role",
+            create: json!({ "code": "R1", "name": "Auditor", "application": "auth" }),
+            update: json!({ "description": "reads the audit log" }),
+        },
+        Resource {
+            base: "/permission",
+            create: json!({ "uuid": "perm-1", "code": "audit:read", "name": "Read audit log", "nodeType": "api" }),
+            update: json!({ "name": "Read the audit log" }),
+        },
+        Resource {
+            base: "/user",
+            create: json!({ "userName": "alice", "realName": "Alice", "password": "alice@123" }),
+            update: json!({ "realName": "Alice Liddell" }),
+        },
+        Resource {
+            base: "/userInfo",
+            create: json!({ "userCode": "U1", "username": "alice", "realName": "Alice", "userType": "staff", "orgCode": "ORG1" }),
+            update: json!({ "nickName": "ally" }),
+        },
+        Resource {
+            base: "/userWechatInfo",
+            create: json!({ "recId": 9001, "unionId": "union-1", "nickname": "alice" }),
+            update: json!({ "nickname": "ally" }),
+        },
+        Resource {
+            base: "/systemConfig",
+            create: json!({ "configKey": "login.captcha", "configValue": "off", "status": "enabled" }),
+            update: json!({ "configValue": "on" }),
+        },
+        Resource {
+            base: "/userRoleRef",
+            create: json!({ "userCode": "U1", "roleCode": "R1", "orgCode": "ORG1" }),
+            update: json!({ "roleCode": "R2" }),
+        },
+        Resource {
+            base: "/userGroupRef",
+            create: json!({ "userCode": "U1", "groupCode": "G1", "orgCode": "ORG1" }),
+            update: json!({ "groupCode": "G2" }),
+        },
+        Resource {
+            base: "/groupRoleRef",
+            create: json!({ "groupCode": "G1", "roleCode": "R1", "orgCode": "ORG1" }),
+            update: json!({ "roleCode": "R2" }),
+        },
+        Resource {
+            base: "/departmentRoleRef",
+            create: json!({ "departmentCode": "D1", "roleCode": "R1", "orgCode": "ORG1" }),
+            update: json!({ "roleCode": "R2" }),
+        },
+        Resource {
+            base: "/positionRoleRef",
+            create: json!({ "positionCode": "P1", "roleCode": "R1", "orgCode": "ORG1" }),
+            update: json!({ "roleCode": "R2" }),
+        },
+        Resource {
+            base: "/organizationRoleRef",
+            create: json!({ "orgCode": "ORG1", "roleCode": "R1" }),
+            update: json!({ "roleCode": "R2" }),
+        },
+    ]
+}
+
+/// Whether every field of `expected` has the same value in `record`
+fn assert_contains(base: &str, record: &Value, expected: &Value) {
+    for (key, value) in expected.as_object().unwrap() {
+        if key == "recId" || key == "password" {
+            continue;
+        }
+        assert_eq!(&record[key], value, "{} field {} of {}", base, key, record);
+    }
+}
+
+fn ids(records: &Value) -> Vec<i64> {
+    records.as_array().unwrap().iter().filter_map(|record| record["id"].as_i64()).collect()
+}
+
+#[tokio::test]
+async fn every_resource_supports_the_crud_routes() {
+    let app = TestApp::spawn().await;
+
+    for resource in resources() {
+        let base = resource.base;
+        let id = app.create(base, resource.create.clone()).await;
+
+        let record = app.read(base, id).await;
+        assert_eq!(record["id"], id, "{} get by id", base);
+        assert_contains(base, &record, &resource.create);
+
+        let list = app.get(&format!("{}/list", base)).await;
+        assert!(ids(list.data()).contains(&id), "{} list", base);
+
+        let page = app.get(&format!("{}/page?page=1&size=10", base)).await;
+        let page = page.data();
+        assert!(ids(&page["page_data"]).contains(&id), "{} page", base);
+        assert!(page["page_info"]["total"].as_u64().unwrap() >= 1, "{} page total", base);
+
+        let mut update = resource.update.clone();
+        update["recId"] = json!(id);
+        update["recSign"] = record["recSign"].clone();
+        assert_eq!(app.put(base, update.clone()).await.data(), 1, "{} update", base);
+        assert_contains(base, &app.read(base, id).await, &resource.update);
+
+        assert_eq!(app.put(&format!("{}/delByIds", base), json!({ "recIds": [id] })).await.data(), 1, "{} soft delete", base);
+        assert_eq!(app.read(base, id).await["isDel"], -1, "{} soft deleted flag", base);
+        assert!(!ids(app.get(&format!("{}/list", base)).await.data()).contains(&id), "{} list after soft delete", base);
+
+        assert_eq!(app.delete(base, json!({ "recIds": [id] })).await.data(), 1, "{} remove", base);
+        assert!(app.read(base, id).await.is_null(), "{} get after remove", base);
+    }
+}
+
+#[tokio::test]
+async fn versioned_resources_reject_a_stale_rec_sign() {
+    let app = TestApp::spawn().await;
+    let id = common::fixtures::organization(&app, "ORG1").await;
+    let rec_sign = app.read("/organization", id).await["recSign"].clone();
+
+    let update = json!({ "recId": id, "recSign": rec_sign, "name": "first" });
+    assert_eq!(app.put("/organization", update).await.data(), 1);
+
+    let stale = app.put("/organization", json!({ "recId": id, "recSign": rec_sign, "name": "second" })).await;
+    assert_eq!(stale.status, 409);
+    assert_eq!(app.read("/organization", id).await["name"], "first");
+}
+
+#[tokio::test]
+async fn crud_routes_require_a_token() {
+    let app = TestApp::spawn().await;
+
+    let response = app.send(axum::http::Method::GET, "/organization/list", None, None).await;
+    assert_eq!(response.status, 401);
+}
+
+/// Groups kept in memory instead of the database, their ids being their position plus one
+#[derive(Default)]
+struct MemoryGroupMapper {
+    groups: Mutex<Vec<GroupDto>>,
+}
+
+impl MemoryGroupMapper {
+    fn vo(id: usize, group: &GroupDto) -> GroupVo {
+        GroupVo {
+            id: Some(id as i64),
+            code: group.code.clone(),
+            parent_code: None,
+            name: group.name.clone(),
+            r#type: group.r#type.clone(),
+            description: None,
+            application: group.application.clone(),
+            org_code: group.org_code.clone(),
+            extra: None,
+            remark: None,
+            path: None,
+            is_del: Some(0),
+            create_time: None,
+            update_time: None,
+            create_by: None,
+            update_by: None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CrudMapperTrait<GroupResource> for MemoryGroupMapper {
+    async fn list(&self, _condition: GroupCondition) -> Result<Vec<GroupVo>, DbErr> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups.iter().enumerate().map(|(index, group)| Self::vo(index + 1, group)).collect())
+    }
+
+    async fn page(&self, condition: GroupCondition) -> Result<PageData<GroupVo>, DbErr> {
+        let list = self.list(condition).await?;
+        Ok(PageData::new(PageInfo::from(1, 20, list.len() as u64), list))
+    }
+
+    async fn get_by_id(&self, rec_id: i64) -> Result<Option<GroupVo>, DbErr> {
+        let groups = self.groups.lock().unwrap();
+        let index = rec_id as usize;
+        Ok(groups.get(index.wrapping_sub(1)).map(|group| Self::vo(index, group)))
+    }
+
+    async fn save(&self, dto: GroupDto) -> Result<i64, DbErr> {
+        let mut groups = self.groups.lock().unwrap();
+        groups.push(dto);
+        Ok(groups.len() as i64)
+    }
+
+    async fn update_by_id(&self, _dto: GroupDto) -> Result<u64, DbErr> {
+        Ok(0)
+    }
+
+    async fn delete_by_ids(&self, _dto: GroupDto) -> Result<u64, DbErr> {
+        Ok(0)
+    }
+
+    async fn remove_by_ids(&self, _dto: GroupDto) -> Result<u64, DbErr> {
+        Ok(0)
+    }
+
+    async fn tenant_rec_ids(&self, rec_ids: Vec<i64>) -> Result<Vec<i64>, DbErr> {
+        Ok(rec_ids)
+    }
+}
+
+#[tokio::test]
+async fn crud_routes_serve_whichever_mapper_the_services_were_built_with() {
+    let mapper = Arc::new(MemoryGroupMapper::default());
+    let app = TestApp::spawn_with(|mappers| mappers.group = mapper.clone()).await;
+
+    let group = json!({ "code": "G1", "name": "Reviewers", "type": "team", "application": "auth" });
+    let id = app.create("/group", group).await;
+    assert_eq!(id, 1);
+    assert_eq!(app.read("/group", id).await["name"], "Reviewers");
+    assert_eq!(ids(app.get("/group/list").await.data()), [1]);
+
+    assert_eq!(mapper.groups.lock().unwrap().len(), 1);
+    assert_eq!(group::Entity::find().count(app.db()).await.unwrap(), 0);
+}
@@ -0,0 +1,139 @@
+//! `role.data_scope`, the effective data scope of a user and its enforcement on `/userInfo`
+
+mod common;
+
+use axum::http::Method;
+use common::{fixtures, TestApp};
+use serde_json::{json, Value};
+
+/// ORG1 with departments RD (with BACKEND below it) and SALES, manager MGR in RD, DEV1
+/// in BACKEND and SAL1 in SALES; account MGR logs in as the manager
+async fn seed(app: &TestApp) {
+    fixtures::organization(app, "ORG1").await;
+    fixtures::department(app, "RD", "ORG1").await;
+    app.create("/department", json!({ "code": "BACKEND", "name": "Backend", "orgCode": "ORG1", "parentCode": "RD" }))
+        .await;
+    fixtures::department(app, "SALES", "ORG1").await;
+    for (department_code, position_code) in [("RD", "LEAD"), ("BACKEND", "DEV"), ("SALES", "REP")] {
+        fixtures::position(app, position_code, department_code, "ORG1").await;
+    }
+    fixtures::user_info(app, "MGR", "ORG1", "RD", "LEAD").await;
+    fixtures::user_info(app, "DEV1", "ORG1", "BACKEND", "DEV").await;
+    fixtures::user_info(app, "SAL1", "ORG1", "SALES", "REP").await;
+    app.create("/permission", json!({ "uuid": "p1", "code": "userInfo:read", "name": "People", "nodeType": "button" }))
+        .await;
+    fixtures::user(app, "MGR", "mgr@pass").await;
+}
+
+/// Grants MGR a role reading people with `data_scope`
+async fn grant(app: &TestApp, data_scope: Option<&str>) {
+    app.create(
+        "/role",
+        json!({ "code": "VIEWER", "name": "Viewer", "permissions": ["userInfo:read"], "dataScope": data_scope }),
+    )
+    .await;
+    app.create("/userRoleRef", json!({ "userCode": "MGR", "roleCode": "VIEWER" })).await;
+}
+
+/// User codes of `/userInfo/page` as seen by MGR
+async fn visible_people(app: &TestApp) -> Vec<String> {
+    let token = app.login("MGR", "mgr@pass").await;
+    let response = app.send(Method::GET, "/userInfo/page", Some(&token), None).await;
+    let page = response.data();
+    let mut user_codes: Vec<String> = page["page_data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|person| person["userCode"].as_str().unwrap().to_string())
+        .collect();
+    user_codes.sort();
+    assert_eq!(page["page_info"]["total"], user_codes.len());
+    user_codes
+}
+
+fn sorted(values: &Value) -> Vec<String> {
+    let mut values: Vec<String> = values.as_array().unwrap().iter().map(|value| value.as_str().unwrap().to_string()).collect();
+    values.sort();
+    values
+}
+
+#[tokio::test]
+async fn the_scope_of_a_user_expands_along_the_department_paths() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    grant(&app, Some("department_and_below")).await;
+
+    let response = app.get("/authz/users/MGR/data-scope").await;
+    let data_scope = response.data();
+    assert_eq!(data_scope["scope"], "department_and_below");
+    assert_eq!(data_scope["orgCode"], "ORG1");
+    assert_eq!(data_scope["departmentPaths"], json!(["/RD/"]));
+    assert_eq!(sorted(&data_scope["departmentCodes"]), ["BACKEND", "RD"]);
+
+    let response = app.get("/authz/users/SAL1/data-scope").await;
+    assert_eq!(response.data()["scope"], "self");
+    assert_eq!(response.data()["departmentCodes"], json!([]));
+}
+
+#[tokio::test]
+async fn the_departments_below_match_the_path_literally() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    grant(&app, Some("department_and_below")).await;
+    for (code, parent_code) in [("R_D", None), ("QA", Some("R_D")), ("RXD", None), ("OPS", Some("RXD"))] {
+        app.create("/department", json!({ "code": code, "name": code, "orgCode": "ORG1", "parentCode": parent_code }))
+            .await;
+    }
+    fixtures::position(&app, "HEAD", "R_D", "ORG1").await;
+    fixtures::user_info(&app, "MGR2", "ORG1", "R_D", "HEAD").await;
+    app.create("/userRoleRef", json!({ "userCode": "MGR2", "roleCode": "VIEWER" })).await;
+
+    let response = app.get("/authz/users/MGR2/data-scope").await;
+    assert_eq!(sorted(&response.data()["departmentCodes"]), ["QA", "R_D"]);
+}
+
+#[tokio::test]
+async fn the_user_info_page_only_lists_the_rows_of_the_scope() {
+    for (data_scope, expected) in [
+        (Some("self"), vec!["MGR"]),
+        (Some("department"), vec!["MGR"]),
+        (Some("department_and_below"), vec!["DEV1", "MGR"]),
+        (Some("org"), vec!["DEV1", "MGR", "SAL1"]),
+        (Some("all"), vec!["DEV1", "MGR", "SAL1"]),
+        (None, vec!["DEV1", "MGR", "SAL1"]),
+    ] {
+        let app = TestApp::spawn().await;
+        seed(&app).await;
+        grant(&app, data_scope).await;
+        assert_eq!(visible_people(&app).await, expected, "data scope {:?}", data_scope);
+    }
+}
+
+#[tokio::test]
+async fn the_widest_role_wins_and_a_role_inherits_the_scope_of_its_ancestors() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    app.create("/role", json!({ "code": "BASE", "name": "Base", "dataScope": "department" })).await;
+    app.create(
+        "/role",
+        json!({ "code": "VIEWER", "name": "Viewer", "parentCode": "BASE", "permissions": ["userInfo:read"] }),
+    )
+    .await;
+    app.create("/userRoleRef", json!({ "userCode": "MGR", "roleCode": "VIEWER" })).await;
+    assert_eq!(app.get("/authz/users/MGR/data-scope").await.data()["scope"], "department");
+
+    app.create("/role", json!({ "code": "OWN", "name": "Own", "dataScope": "self" })).await;
+    app.create("/userRoleRef", json!({ "userCode": "MGR", "roleCode": "OWN" })).await;
+    assert_eq!(app.get("/authz/users/MGR/data-scope").await.data()["scope"], "department");
+    assert_eq!(visible_people(&app).await, ["MGR"]);
+}
+
+#[tokio::test]
+async fn an_unknown_data_scope_is_rejected() {
+    let app = TestApp::spawn().await;
+
+    let response = app.post("/role", json!({ "code": "R1", "name": "R1", "dataScope": "team" })).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["fields"][0]["field"], "dataScope");
+    assert_eq!(app.get("/authz/users/NOPE/data-scope").await.status, 404);
+}
@@ -0,0 +1,90 @@
+//! `GET /organization/:orgCode/departments/tree`, the departments of one organization with positions and headcounts
+
+mod common;
+
+use common::{fixtures, TestApp};
+use serde_json::{json, Value};
+
+async fn department(app: &TestApp, code: &str, org_code: &str, parent_code: &str) -> i64 {
+    app.create(
+        "/department",
+        json!({ "code": code, "name": format!("{} dept", code), "orgCode": org_code, "parentCode": parent_code }),
+    )
+    .await
+}
+
+fn codes(records: &Value) -> Vec<String> {
+    records
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|record| record["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// ORG1: RD (BACKEND, FRONTEND) and SALES; ORG2: OPS
+async fn seed(app: &TestApp) {
+    fixtures::organization(app, "ORG1").await;
+    fixtures::organization(app, "ORG2").await;
+    fixtures::department(app, "RD", "ORG1").await;
+    department(app, "BACKEND", "ORG1", "RD").await;
+    department(app, "FRONTEND", "ORG1", "RD").await;
+    fixtures::department(app, "SALES", "ORG1").await;
+    fixtures::department(app, "OPS", "ORG2").await;
+
+    fixtures::position(app, "CTO", "RD", "ORG1").await;
+    fixtures::position(app, "DEV", "BACKEND", "ORG1").await;
+    fixtures::position(app, "SRE", "OPS", "ORG2").await;
+
+    fixtures::user_info(app, "U1", "ORG1", "RD", "CTO").await;
+    fixtures::user_info(app, "U2", "ORG1", "BACKEND", "DEV").await;
+    fixtures::user_info(app, "U3", "ORG1", "BACKEND", "DEV").await;
+    fixtures::user_info(app, "U4", "ORG1", "FRONTEND", "DEV").await;
+    fixtures::user_info(app, "U5", "ORG2", "OPS", "SRE").await;
+}
+
+#[tokio::test]
+async fn the_tree_nests_the_departments_of_one_organization() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    let tree = app.get("/organization/ORG1/departments/tree").await;
+    let tree = tree.data();
+    assert_eq!(codes(tree), ["RD", "SALES"]);
+    assert_eq!(codes(&tree[0]["children"]), ["BACKEND", "FRONTEND"]);
+    assert!(tree[1]["children"].as_array().unwrap().is_empty());
+
+    assert_eq!(codes(app.get("/organization/ORG2/departments/tree").await.data()), ["OPS"]);
+}
+
+#[tokio::test]
+async fn every_node_carries_its_headcounts_and_positions() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    let tree = app.get("/organization/ORG1/departments/tree").await;
+    let rd = &tree.data()[0];
+    assert_eq!((rd["directHeadcount"].as_u64(), rd["headcount"].as_u64()), (Some(1), Some(4)));
+    let backend = &rd["children"][0];
+    assert_eq!((backend["directHeadcount"].as_u64(), backend["headcount"].as_u64()), (Some(2), Some(2)));
+    let sales = &tree.data()[1];
+    assert_eq!((sales["directHeadcount"].as_u64(), sales["headcount"].as_u64()), (Some(0), Some(0)));
+
+    assert_eq!(codes(&rd["positions"]), ["CTO"]);
+    assert_eq!(codes(&backend["positions"]), ["DEV"]);
+    assert!(rd["children"][1]["positions"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn deleted_members_are_not_counted_and_unknown_organizations_are_not_found() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    let u2 = app.get("/userInfo/list?userCode=U2").await.data()[0]["id"].as_i64().unwrap();
+    app.put("/userInfo/delByIds", json!({ "recIds": [u2] })).await.data();
+
+    let tree = app.get("/organization/ORG1/departments/tree").await;
+    assert_eq!(tree.data()[0]["headcount"], 3);
+    assert_eq!(tree.data()[0]["children"][0]["directHeadcount"], 1);
+
+    assert_eq!(app.get("/organization/NOPE/departments/tree").await.status, 404);
+}
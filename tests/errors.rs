@@ -0,0 +1,110 @@
+//! Status, stable codes and field details of failed requests
+
+mod common;
+
+use axum::http::Method;
+use common::{fixtures, TestApp};
+use serde_json::json;
+
+#[tokio::test]
+async fn malformed_bodies_are_validation_errors() {
+    let app = TestApp::spawn().await;
+
+    let response = app.post("/organization", json!(["not", "an", "object"])).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["code"], 400);
+    assert_eq!(response.body["error"], "validation");
+
+    let response = app.post("/role", json!({ "code": "R1", "roleType": "admin" })).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["error"], "validation");
+}
+
+#[tokio::test]
+async fn malformed_path_and_query_are_validation_errors() {
+    let app = TestApp::spawn().await;
+
+    let response = app.get("/organization/abc").await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["error"], "validation");
+
+    let response = app.get("/organization/page?page=first").await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["error"], "validation");
+}
+
+#[tokio::test]
+async fn deletes_without_ids_name_the_missing_field() {
+    let app = TestApp::spawn().await;
+
+    for response in [
+        app.put("/organization/delByIds", json!({})).await,
+        app.delete("/organization", json!({ "recIds": [] })).await,
+    ] {
+        assert_eq!(response.status, 400);
+        assert_eq!(response.body["error"], "validation");
+        assert_eq!(response.body["fields"][0]["field"], "recIds");
+    }
+
+    let response = app.put("/group", json!({ "name": "no id" })).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["fields"][0]["field"], "recId");
+}
+
+#[tokio::test]
+async fn business_rules_answer_with_their_kind() {
+    let app = TestApp::spawn().await;
+    fixtures::role(&app, "R1").await;
+
+    let response = app.post("/role", json!({ "name": "no code" })).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["fields"], json!([{ "field": "code", "message": "角色编码不能为空！" }]));
+
+    let response = app.post("/role", json!({ "code": "R1", "name": "again" })).await;
+    assert_eq!(response.status, 409);
+    assert_eq!(response.body["code"], 409);
+    assert_eq!(response.body["error"], "conflict");
+
+    let response = app.put("/organization", json!({ "recId": 404, "recSign": "gone", "name": "missing" })).await;
+    assert_eq!(response.status, 404);
+    assert_eq!(response.body["error"], "not_found");
+}
+
+#[tokio::test]
+async fn authentication_failures_are_distinguished() {
+    let app = TestApp::spawn().await;
+
+    let response = app.send(Method::GET, "/role/list", None, None).await;
+    assert_eq!(response.status, 401);
+    assert_eq!(response.body["error"], "unauthorized");
+
+    let response = app.send(Method::GET, "/role/list", Some("not-a-token"), None).await;
+    assert_eq!(response.status, 401);
+    assert_eq!(response.body["error"], "unauthorized");
+
+    fixtures::user(&app, "bob", "bob@pass").await;
+    let token = app.login("bob", "bob@pass").await;
+    let response = app.send(Method::GET, "/role/list", Some(&token), None).await;
+    assert_eq!(response.status, 403);
+    assert_eq!(response.body["error"], "forbidden");
+}
+
+#[tokio::test]
+async fn unknown_routes_are_not_found() {
+    let app = TestApp::spawn().await;
+
+    let response = app.get("/no/such/route").await;
+    assert_eq!(response.status, 404);
+    assert_eq!(response.body["code"], 404);
+    assert_eq!(response.body["error"], "not_found");
+}
+
+#[tokio::test]
+async fn successful_responses_carry_no_error_fields() {
+    let app = TestApp::spawn().await;
+
+    let response = app.get("/organization/list").await;
+    assert_eq!(response.body["code"], 0);
+    assert!(response.body.get("error").is_none());
+    assert!(response.body.get("fields").is_none());
+}
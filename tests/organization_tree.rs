@@ -0,0 +1,107 @@
+//! The organization hierarchy kept in `organization.path` and the routes reading it
+
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+
+async fn organization(app: &TestApp, code: &str, parent_code: Option<&str>) -> i64 {
+    app.create("/organization", json!({ "code": code, "name": format!("{} org", code), "parentCode": parent_code }))
+        .await
+}
+
+/// Group HQ with subsidiaries SUB1 (and its branch BR1) and SUB2, plus a second group OTHER
+async fn seed(app: &TestApp) {
+    organization(app, "HQ", None).await;
+    organization(app, "SUB1", Some("HQ")).await;
+    organization(app, "SUB2", Some("HQ")).await;
+    organization(app, "BR1", Some("SUB1")).await;
+    organization(app, "OTHER", None).await;
+}
+
+fn codes(records: &Value) -> Vec<String> {
+    records
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|record| record["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn create_computes_the_path_from_the_parent() {
+    let app = TestApp::spawn().await;
+    let hq = organization(&app, "HQ", None).await;
+    let sub = app
+        .create("/organization", json!({ "code": "SUB1", "name": "SUB1 org", "parentCode": "HQ", "path": "/forged/" }))
+        .await;
+    let branch = organization(&app, "BR1", Some("SUB1")).await;
+
+    assert_eq!(app.read("/organization", hq).await["path"], "/HQ/");
+    assert_eq!(app.read("/organization", sub).await["path"], "/HQ/SUB1/");
+    assert_eq!(app.read("/organization", branch).await["path"], "/HQ/SUB1/BR1/");
+}
+
+#[tokio::test]
+async fn create_rejects_an_unknown_parent_and_a_taken_code() {
+    let app = TestApp::spawn().await;
+    organization(&app, "HQ", None).await;
+
+    let response = app.post("/organization", json!({ "code": "SUB1", "name": "orphan", "parentCode": "NOPE" })).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["fields"][0]["field"], "parentCode");
+
+    let response = app.post("/organization", json!({ "code": "HQ", "name": "again" })).await;
+    assert_eq!(response.status, 409);
+}
+
+#[tokio::test]
+async fn tree_nests_subsidiaries_below_their_parent() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    let tree = app.get("/organization/tree").await;
+    let tree = tree.data();
+    assert_eq!(codes(tree), ["HQ", "OTHER"]);
+    assert_eq!(codes(&tree[0]["children"]), ["SUB1", "SUB2"]);
+    assert_eq!(codes(&tree[0]["children"][0]["children"]), ["BR1"]);
+    assert!(tree[1]["children"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn descendants_and_ancestors_follow_the_path() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    assert_eq!(codes(app.get("/organization/HQ/descendants").await.data()), ["SUB1", "BR1", "SUB2"]);
+    assert_eq!(codes(app.get("/organization/SUB1/descendants").await.data()), ["BR1"]);
+    assert!(codes(app.get("/organization/OTHER/descendants").await.data()).is_empty());
+
+    assert_eq!(codes(app.get("/organization/BR1/ancestors").await.data()), ["HQ", "SUB1"]);
+    assert!(codes(app.get("/organization/HQ/ancestors").await.data()).is_empty());
+
+    let response = app.get("/organization/NOPE/descendants").await;
+    assert_eq!(response.status, 404);
+}
+
+#[tokio::test]
+async fn soft_deleted_organizations_leave_the_hierarchy() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    let sub2 = app.get("/organization/list?code=SUB2").await.data()[0]["id"].as_i64().unwrap();
+    app.put("/organization/delByIds", json!({ "recIds": [sub2] })).await.data();
+
+    assert_eq!(codes(app.get("/organization/HQ/descendants").await.data()), ["SUB1", "BR1"]);
+    assert_eq!(codes(&app.get("/organization/tree").await.data()[0]["children"]), ["SUB1"]);
+}
+
+#[tokio::test]
+async fn descendants_match_the_path_literally() {
+    let app = TestApp::spawn().await;
+    organization(&app, "A_B", None).await;
+    organization(&app, "C", Some("A_B")).await;
+    organization(&app, "AXB", None).await;
+    organization(&app, "D", Some("AXB")).await;
+
+    assert_eq!(codes(app.get("/organization/A_B/descendants").await.data()), ["C"]);
+}
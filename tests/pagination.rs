@@ -0,0 +1,97 @@
+//! `list` and `page` windows, filters and the soft deleted rows they leave out
+
+mod common;
+
+use common::{fixtures, TestApp};
+use serde_json::{json, Value};
+
+fn codes(records: &Value) -> Vec<String> {
+    records
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|record| record["code"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[tokio::test]
+async fn page_reports_the_window_and_the_total() {
+    let app = TestApp::spawn().await;
+    for index in 1..=7 {
+        fixtures::organization(&app, &format!("ORG{}", index)).await;
+    }
+
+    let first = app.get("/organization/page?page=1&size=3").await;
+    let first = first.data();
+    assert_eq!(first["page_info"], json!({ "page": 1, "size": 3, "total": 7 }));
+    assert_eq!(codes(&first["page_data"]), ["ORG1", "ORG2", "ORG3"]);
+
+    let last = app.get("/organization/page?page=3&size=3").await;
+    let last = last.data();
+    assert_eq!(last["page_info"]["total"], 7);
+    assert_eq!(codes(&last["page_data"]), ["ORG7"]);
+
+    let beyond = app.get("/organization/page?page=4&size=3").await;
+    assert!(beyond.data()["page_data"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn page_defaults_to_the_first_twenty_records() {
+    let app = TestApp::spawn().await;
+    for index in 1..=21 {
+        fixtures::role(&app, &format!("R{:02}", index)).await;
+    }
+
+    let page = app.get("/role/page").await;
+    let page = page.data();
+    assert_eq!(page["page_info"], json!({ "page": 1, "size": 20, "total": 21 }));
+    assert_eq!(page["page_data"].as_array().unwrap().len(), 20);
+}
+
+#[tokio::test]
+async fn list_and_page_apply_the_condition() {
+    let app = TestApp::spawn().await;
+    fixtures::organization(&app, "ORG1").await;
+    fixtures::organization(&app, "ORG2").await;
+    fixtures::department(&app, "D1", "ORG1").await;
+    fixtures::department(&app, "D2", "ORG1").await;
+    fixtures::department(&app, "D3", "ORG2").await;
+
+    let list = app.get("/department/list?orgCode=ORG1").await;
+    assert_eq!(codes(list.data()), ["D1", "D2"]);
+
+    let page = app.get("/department/page?orgCode=ORG2").await;
+    assert_eq!(page.data()["page_info"]["total"], 1);
+    assert_eq!(codes(&page.data()["page_data"]), ["D3"]);
+}
+
+#[tokio::test]
+async fn soft_deleted_records_leave_list_and_page() {
+    let app = TestApp::spawn().await;
+    let kept = fixtures::organization(&app, "ORG1").await;
+    let deleted = fixtures::organization(&app, "ORG2").await;
+
+    let response = app.put("/organization/delByIds", json!({ "recIds": [deleted] })).await;
+    assert_eq!(response.data(), 1);
+
+    assert_eq!(codes(app.get("/organization/list").await.data()), ["ORG1"]);
+    let page = app.get("/organization/page").await;
+    assert_eq!(page.data()["page_info"]["total"], 1);
+
+    // the row itself stays, flagged, until it is removed
+    assert_eq!(app.read("/organization", deleted).await["isDel"], -1);
+    assert_eq!(app.read("/organization", kept).await["isDel"], 0);
+    assert_eq!(app.delete("/organization", json!({ "recIds": [deleted] })).await.data(), 1);
+    assert!(app.read("/organization", deleted).await.is_null());
+}
+
+#[tokio::test]
+async fn soft_delete_counts_only_the_rows_it_changed() {
+    let app = TestApp::spawn().await;
+    let first = fixtures::group(&app, "G1", "ORG1").await;
+    let second = fixtures::group(&app, "G2", "ORG1").await;
+
+    let response = app.put("/group/delByIds", json!({ "recIds": [first, second, 9999] })).await;
+    assert_eq!(response.data(), 2);
+    assert!(app.get("/group/list").await.data().as_array().unwrap().is_empty());
+}
@@ -0,0 +1,60 @@
+//! `/auth/refresh`: single use refresh tokens rotated on every refresh, a reused one ending its session
+
+mod common;
+
+use axum::http::Method;
+use common::{fixtures, TestApp, TestResponse};
+use serde_json::{json, Value};
+
+/// `LoginVo` of a fresh session of account carol
+async fn login(app: &TestApp) -> Value {
+    let body = json!({ "username": "carol", "password": "carol@pass" });
+    app.send(Method::POST, "/auth/login", None, Some(body)).await.data().clone()
+}
+
+async fn refresh(app: &TestApp, refresh_token: &Value) -> TestResponse {
+    app.send(Method::POST, "/auth/refresh", None, Some(json!({ "refreshToken": refresh_token }))).await
+}
+
+#[tokio::test]
+async fn a_refresh_rotates_both_tokens() {
+    let app = TestApp::spawn().await;
+    fixtures::user(&app, "carol", "carol@pass").await;
+    let session = login(&app).await;
+
+    let response = refresh(&app, &session["refreshToken"]).await;
+    let rotated = response.data().clone();
+    assert_ne!(rotated["refreshToken"], session["refreshToken"]);
+    assert_ne!(rotated["accessToken"], session["accessToken"]);
+    assert_eq!(rotated["tokenType"], "Bearer");
+    let token = rotated["accessToken"].as_str().unwrap();
+    assert_eq!(app.send(Method::GET, "/auth/sessions", Some(token), None).await.data().as_array().unwrap().len(), 1);
+
+    let response = refresh(&app, &rotated["refreshToken"]).await;
+    assert_ne!(response.data()["refreshToken"], rotated["refreshToken"]);
+}
+
+#[tokio::test]
+async fn a_reused_refresh_token_revokes_its_whole_session() {
+    let app = TestApp::spawn().await;
+    fixtures::user(&app, "carol", "carol@pass").await;
+    let session = login(&app).await;
+    let other_session = login(&app).await;
+    let rotated = refresh(&app, &session["refreshToken"]).await.data().clone();
+
+    let response = refresh(&app, &session["refreshToken"]).await;
+    assert_eq!(response.status, 401);
+    assert_eq!(response.body["message"], "无效的刷新令牌！");
+    assert_eq!(refresh(&app, &rotated["refreshToken"]).await.status, 401);
+
+    assert!(refresh(&app, &other_session["refreshToken"]).await.data()["accessToken"].is_string());
+}
+
+#[tokio::test]
+async fn an_unknown_refresh_token_is_rejected() {
+    let app = TestApp::spawn().await;
+
+    let response = refresh(&app, &json!("not-a-refresh-token")).await;
+    assert_eq!(response.status, 401);
+    assert_eq!(response.body["error"], "unauthorized");
+}
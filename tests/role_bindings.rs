@@ -0,0 +1,174 @@
+//! The role binding tables and the effective roles `AuthzSvc` resolves from them
+
+mod common;
+
+use common::{fixtures, TestApp};
+use serde_json::{json, Value};
+
+/// Organization ORG1 with department D1, position P1 and group G1, user U1 working
+/// there, roles R1 to R5 and account `u1` logging in as U1
+async fn seed(app: &TestApp) {
+    fixtures::organization(app, "ORG1").await;
+    fixtures::department(app, "D1", "ORG1").await;
+    fixtures::position(app, "P1", "D1", "ORG1").await;
+    fixtures::group(app, "G1", "ORG1").await;
+    for code in ["R1", "R2", "R3", "R4", "R5"] {
+        fixtures::role(app, code).await;
+    }
+    fixtures::user_info(app, "U1", "ORG1", "D1", "P1").await;
+    fixtures::user(app, "U1", "u1@pass").await;
+}
+
+/// Role codes of user U1 with the binding tables each came through
+async fn effective_roles(app: &TestApp) -> Vec<(String, Vec<String>)> {
+    let response = app.get("/authz/users/U1/permissions").await;
+    response.data()["roles"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|role| {
+            let sources = role["sources"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|source| source["sourceType"].as_str().unwrap().to_string())
+                .collect();
+            (role["roleCode"].as_str().unwrap().to_string(), sources)
+        })
+        .collect()
+}
+
+fn role(code: &str, sources: &[&str]) -> (String, Vec<String>) {
+    (code.to_string(), sources.iter().map(|source| source.to_string()).collect())
+}
+
+#[tokio::test]
+async fn every_binding_table_grants_its_role() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "R1", "orgCode": "ORG1" })).await;
+    app.create("/userGroupRef", json!({ "userCode": "U1", "groupCode": "G1", "orgCode": "ORG1" })).await;
+    app.create("/groupRoleRef", json!({ "groupCode": "G1", "roleCode": "R2", "orgCode": "ORG1" })).await;
+    app.create("/departmentRoleRef", json!({ "departmentCode": "D1", "roleCode": "R3" })).await;
+    app.create("/positionRoleRef", json!({ "positionCode": "P1", "roleCode": "R4" })).await;
+    app.create("/organizationRoleRef", json!({ "orgCode": "ORG1", "roleCode": "R5" })).await;
+
+    assert_eq!(
+        effective_roles(&app).await,
+        [
+            role("R1", &["user"]),
+            role("R2", &["group"]),
+            role("R3", &["department"]),
+            role("R4", &["position"]),
+            role("R5", &["organization"]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn login_carries_the_bound_roles() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "R1", "orgCode": "ORG1" })).await;
+    app.create("/departmentRoleRef", json!({ "departmentCode": "D1", "roleCode": "R3" })).await;
+
+    let response = app
+        .send(axum::http::Method::POST, "/auth/login", None, Some(json!({ "username": "U1", "password": "u1@pass" })))
+        .await;
+    let login = response.data();
+    assert_eq!(login["userCode"], "U1");
+    assert_eq!(login["orgCode"], "ORG1");
+    assert_eq!(login["roles"], json!(["R1", "R3"]));
+}
+
+#[tokio::test]
+async fn bindings_of_another_organization_do_not_apply() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "R1", "orgCode": "ORG2" })).await;
+    app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "R2", "orgCode": "ORG1" })).await;
+
+    assert_eq!(effective_roles(&app).await, [role("R2", &["user"])]);
+}
+
+#[tokio::test]
+async fn department_and_position_codes_of_another_organization_do_not_apply() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    fixtures::organization(&app, "ORG2").await;
+    fixtures::department(&app, "D1", "ORG2").await;
+    fixtures::position(&app, "P1", "D1", "ORG2").await;
+    app.create("/departmentRoleRef", json!({ "departmentCode": "D1", "roleCode": "R1", "orgCode": "ORG2" })).await;
+    app.create("/positionRoleRef", json!({ "positionCode": "P1", "roleCode": "R2", "orgCode": "ORG2" })).await;
+    app.create("/departmentRoleRef", json!({ "departmentCode": "D1", "roleCode": "R3", "orgCode": "ORG1" })).await;
+    app.create("/positionRoleRef", json!({ "positionCode": "P1", "roleCode": "R4" })).await;
+
+    assert_eq!(effective_roles(&app).await, [role("R3", &["department"]), role("R4", &["position"])]);
+}
+
+#[tokio::test]
+async fn a_binding_is_unique_per_subject_role_and_organization() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    let binding = json!({ "userCode": "U1", "roleCode": "R1", "orgCode": "ORG1" });
+    app.create("/userRoleRef", binding.clone()).await;
+
+    let duplicate = app.post("/userRoleRef", binding).await;
+    assert_eq!(duplicate.status, 409, "duplicate binding accepted: {}", duplicate.body);
+    assert_eq!(duplicate.body["error"], "conflict");
+
+    let list = app.get("/userRoleRef/list?userCode=U1").await;
+    assert_eq!(list.data().as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn a_binding_without_organization_is_unique_too() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    app.create("/departmentRoleRef", json!({ "departmentCode": "D1", "roleCode": "R1" })).await;
+    let other = app.create("/departmentRoleRef", json!({ "departmentCode": "D1", "roleCode": "R2" })).await;
+
+    let duplicate = app.post("/departmentRoleRef", json!({ "departmentCode": "D1", "roleCode": "R1" })).await;
+    assert_eq!(duplicate.status, 409, "duplicate binding accepted: {}", duplicate.body);
+    assert_eq!(duplicate.body["error"], "conflict");
+
+    let rec_sign = app.read("/departmentRoleRef", other).await["recSign"].clone();
+    let renamed = app.put("/departmentRoleRef", json!({ "recId": other, "recSign": rec_sign, "roleCode": "R1" })).await;
+    assert_eq!(renamed.status, 409, "binding updated into a duplicate: {}", renamed.body);
+
+    let list = app.get("/departmentRoleRef/list?departmentCode=D1").await;
+    let roles: Vec<&Value> = list.data().as_array().unwrap().iter().map(|binding| &binding["roleCode"]).collect();
+    assert_eq!(roles, [&json!("R1"), &json!("R2")]);
+}
+
+#[tokio::test]
+async fn a_soft_deleted_binding_no_longer_grants() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    let user_binding = app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "R1", "orgCode": "ORG1" })).await;
+    let group_binding = app.create("/groupRoleRef", json!({ "groupCode": "G1", "roleCode": "R2", "orgCode": "ORG1" })).await;
+    app.create("/userGroupRef", json!({ "userCode": "U1", "groupCode": "G1", "orgCode": "ORG1" })).await;
+    assert_eq!(effective_roles(&app).await.len(), 2);
+
+    app.put("/userRoleRef/delByIds", json!({ "recIds": [user_binding] })).await.data();
+    app.put("/groupRoleRef/delByIds", json!({ "recIds": [group_binding] })).await.data();
+
+    assert!(effective_roles(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn role_bindings_filter_by_subject_and_role() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    app.create("/groupRoleRef", json!({ "groupCode": "G1", "roleCode": "R1", "orgCode": "ORG1" })).await;
+    app.create("/groupRoleRef", json!({ "groupCode": "G1", "roleCode": "R2", "orgCode": "ORG1" })).await;
+    app.create("/groupRoleRef", json!({ "groupCode": "G2", "roleCode": "R1", "orgCode": "ORG1" })).await;
+
+    let by_group = app.get("/groupRoleRef/list?groupCode=G1").await;
+    let roles: Vec<&Value> = by_group.data().as_array().unwrap().iter().map(|binding| &binding["roleCode"]).collect();
+    assert_eq!(roles, [&json!("R1"), &json!("R2")]);
+
+    let by_role = app.get("/groupRoleRef/page?roleCode=R1").await;
+    assert_eq!(by_role.data()["page_info"]["total"], 2);
+}
@@ -0,0 +1,84 @@
+//! Sensitive columns only returned in the `includeSensitive` projection, passwords never
+
+mod common;
+
+use axum::http::Method;
+use common::{fixtures, TestApp};
+use serde_json::{json, Value};
+
+const SENSITIVE: [&str; 4] = ["idCardNo", "wxUnionId", "wxOpenId", "wxMiniOpenId"];
+
+/// Person U1 of ORG1 with an id card number and WeChat ids, returns its id
+async fn seed(app: &TestApp) -> i64 {
+    fixtures::organization(app, "ORG1").await;
+    fixtures::department(app, "D1", "ORG1").await;
+    fixtures::position(app, "P1", "D1", "ORG1").await;
+    let id = fixtures::user_info(app, "U1", "ORG1", "D1", "P1").await;
+    let rec_sign = app.read("/userInfo", id).await["recSign"].clone();
+    let update = json!({
+        "recId": id,
+        "recSign": rec_sign,
+        "idCardNo": "110101199001011234",
+        "wxUnionId": "union-1",
+        "wxOpenId": "open-1",
+        "wxMiniOpenId": "mini-1",
+    });
+    app.put("/userInfo", update).await.data();
+    id
+}
+
+fn sensitive_fields(record: &Value) -> Vec<&str> {
+    SENSITIVE.into_iter().filter(|field| record.get(*field).is_some()).collect()
+}
+
+#[tokio::test]
+async fn sensitive_columns_are_left_out_unless_requested() {
+    let app = TestApp::spawn().await;
+    let id = seed(&app).await;
+
+    let record = app.get(&format!("/userInfo/{}", id)).await;
+    assert!(sensitive_fields(record.data()).is_empty());
+    let list = app.get("/userInfo/list").await;
+    assert!(sensitive_fields(&list.data()[0]).is_empty());
+    let page = app.get("/userInfo/page").await;
+    assert!(sensitive_fields(&page.data()["page_data"][0]).is_empty());
+
+    let record = app.get(&format!("/userInfo/{}?includeSensitive=true", id)).await;
+    assert_eq!(sensitive_fields(record.data()), SENSITIVE);
+    assert_eq!(record.data()["idCardNo"], "110101199001011234");
+    let list = app.get("/userInfo/list?includeSensitive=true").await;
+    assert_eq!(sensitive_fields(&list.data()[0]), SENSITIVE);
+}
+
+#[tokio::test]
+async fn passwords_are_never_returned() {
+    let app = TestApp::spawn().await;
+    let id = fixtures::user(&app, "carol", "carol@pass").await;
+
+    let account = app.read("/user", id).await;
+    assert_eq!(account["userName"], "carol");
+    assert!(account.get("password").is_none());
+    assert!(app.get("/user/list").await.data()[0].get("password").is_none());
+}
+
+#[tokio::test]
+async fn the_sensitive_projection_is_refused_without_the_privilege() {
+    let app = TestApp::spawn().await;
+    let id = seed(&app).await;
+    app.create("/permission", json!({ "uuid": "p1", "code": "userInfo:read", "name": "People", "nodeType": "button" }))
+        .await;
+    app.create("/role", json!({ "code": "VIEWER", "name": "Viewer", "permissions": ["userInfo:read"] })).await;
+    app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "VIEWER" })).await;
+    fixtures::user(&app, "U1", "u1@pass").await;
+    let token = app.login("U1", "u1@pass").await;
+
+    let record = app.send(Method::GET, &format!("/userInfo/{}", id), Some(&token), None).await;
+    assert!(sensitive_fields(record.data()).is_empty());
+    let response = app
+        .send(Method::GET, &format!("/userInfo/{}?includeSensitive=true", id), Some(&token), None)
+        .await;
+    assert_eq!(response.status, 403);
+    assert_eq!(response.body["message"], "无权查看敏感信息！");
+    let response = app.send(Method::GET, "/userInfo/page?includeSensitive=true", Some(&token), None).await;
+    assert_eq!(response.status, 403);
+}
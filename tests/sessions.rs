@@ -0,0 +1,128 @@
+//! `/auth/logout`, `/auth/sessions` and forced revocation, ended sessions being denied at once
+
+mod common;
+
+use auth_center::util::jwt::JwtKeys;
+use axum::http::Method;
+use common::{fixtures, TestApp, TestResponse};
+use openssl::rsa::Rsa;
+use serde_json::{json, Value};
+
+/// `LoginVo` of a fresh session of account carol on `device`
+async fn login(app: &TestApp, device: &str) -> Value {
+    let body = json!({ "username": "carol", "password": "carol@pass", "device": device });
+    app.send(Method::POST, "/auth/login", None, Some(body)).await.data().clone()
+}
+
+async fn as_session(app: &TestApp, method: Method, uri: &str, session: &Value) -> TestResponse {
+    app.send(method, uri, session["accessToken"].as_str(), None).await
+}
+
+#[tokio::test]
+async fn sessions_lists_the_live_sessions_of_the_caller() {
+    let app = TestApp::spawn().await;
+    fixtures::user(&app, "carol", "carol@pass").await;
+    let laptop = login(&app, "laptop").await;
+    login(&app, "phone").await;
+
+    let response = as_session(&app, Method::GET, "/auth/sessions", &laptop).await;
+    let mut sessions: Vec<(Value, Value)> = response
+        .data()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|session| (session["device"].clone(), session["current"].clone()))
+        .collect();
+    sessions.sort_by_key(|(device, _)| device.to_string());
+    assert_eq!(sessions, [(json!("laptop"), json!(true)), (json!("phone"), json!(false))]);
+}
+
+#[tokio::test]
+async fn logout_denies_the_access_token_and_ends_the_refresh_token() {
+    let app = TestApp::spawn().await;
+    fixtures::user(&app, "carol", "carol@pass").await;
+    let laptop = login(&app, "laptop").await;
+    let phone = login(&app, "phone").await;
+
+    as_session(&app, Method::POST, "/auth/logout", &laptop).await.data();
+
+    let response = as_session(&app, Method::GET, "/auth/sessions", &laptop).await;
+    assert_eq!(response.status, 401);
+    assert_eq!(response.body["message"], "无效的访问令牌！");
+    let refresh = json!({ "refreshToken": laptop["refreshToken"] });
+    assert_eq!(app.send(Method::POST, "/auth/refresh", None, Some(refresh)).await.status, 401);
+
+    let response = as_session(&app, Method::GET, "/auth/sessions", &phone).await;
+    assert_eq!(response.data().as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn an_administrator_can_end_every_session_of_an_account() {
+    let app = TestApp::spawn().await;
+    let carol = fixtures::user(&app, "carol", "carol@pass").await;
+    let laptop = login(&app, "laptop").await;
+    let phone = login(&app, "phone").await;
+
+    assert_eq!(app.request(Method::DELETE, &format!("/user/{}/sessions", carol), None).await.data(), 2);
+
+    for session in [&laptop, &phone] {
+        assert_eq!(as_session(&app, Method::GET, "/auth/sessions", session).await.status, 401);
+    }
+    assert!(login(&app, "laptop").await["accessToken"].is_string());
+}
+
+#[tokio::test]
+async fn changing_the_password_ends_every_session_of_the_account() {
+    let app = TestApp::spawn().await;
+    fixtures::user(&app, "carol", "carol@pass").await;
+    let laptop = login(&app, "laptop").await;
+    let phone = login(&app, "phone").await;
+
+    let change = json!({ "username": "carol", "oldPassword": "carol@pass", "newPassword": "carol@new" });
+    let response = app.send(Method::PUT, "/auth/password", laptop["accessToken"].as_str(), Some(change)).await;
+    assert_eq!(response.data(), true);
+
+    for session in [&laptop, &phone] {
+        assert_eq!(as_session(&app, Method::GET, "/auth/sessions", session).await.status, 401);
+        let refresh = json!({ "refreshToken": session["refreshToken"] });
+        assert_eq!(app.send(Method::POST, "/auth/refresh", None, Some(refresh)).await.status, 401);
+    }
+    let body = json!({ "username": "carol", "password": "carol@new", "device": "laptop" });
+    assert!(app.send(Method::POST, "/auth/login", None, Some(body)).await.data()["accessToken"].is_string());
+}
+
+#[tokio::test]
+async fn a_disabled_account_stays_disabled_through_other_updates() {
+    let app = TestApp::spawn().await;
+    let carol = fixtures::user(&app, "carol", "carol@pass").await;
+    let record = app.read("/user", carol).await;
+    assert_eq!((&record["status"], &record["type"]), (&json!(0), &json!(0)));
+    let laptop = login(&app, "laptop").await;
+
+    let disable = json!({ "recId": carol, "recSign": record["recSign"], "status": 1 });
+    assert_eq!(app.put("/user", disable).await.data(), 1);
+    assert_eq!(as_session(&app, Method::GET, "/auth/sessions", &laptop).await.status, 401);
+
+    let rec_sign = app.read("/user", carol).await["recSign"].clone();
+    assert_eq!(app.put("/user", json!({ "recId": carol, "recSign": rec_sign, "realName": "Carol" })).await.data(), 1);
+    let record = app.read("/user", carol).await;
+    assert_eq!((&record["realName"], &record["status"]), (&json!("Carol"), &json!(1)));
+    let body = json!({ "username": "carol", "password": "carol@pass" });
+    assert_ne!(app.send(Method::POST, "/auth/login", None, Some(body)).await.status, 200);
+}
+
+#[test]
+fn a_token_signed_for_another_issuer_is_refused() {
+    let rsa = Rsa::generate(2048).unwrap();
+    let ours = JwtKeys::from_rsa(rsa.clone(), "auth-center".to_string(), 60).unwrap();
+    let theirs = JwtKeys::from_rsa(rsa, "elsewhere".to_string(), 60).unwrap();
+    let exp = chrono::Local::now().timestamp() + 60;
+
+    let token = theirs.encode(&json!({ "sub": "1", "iss": "elsewhere", "exp": exp })).unwrap();
+    assert!(theirs.decode::<Value>(&token).is_ok());
+    assert!(ours.decode::<Value>(&token).is_err());
+    let token = theirs.encode(&json!({ "sub": "1", "exp": exp })).unwrap();
+    assert!(ours.decode::<Value>(&token).is_err());
+    let token = ours.encode(&json!({ "sub": "1", "iss": "auth-center", "exp": exp })).unwrap();
+    assert!(ours.decode::<Value>(&token).is_ok());
+}
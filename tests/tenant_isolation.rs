@@ -0,0 +1,269 @@
+//! Requests confined to the caller's organization, see `util::tenant`
+
+mod common;
+
+use axum::http::Method;
+use common::{fixtures, TestApp, TestResponse};
+use serde_json::{json, Value};
+
+const PERMISSIONS: [&str; 10] = [
+    "userInfo:read",
+    "userInfo:write",
+    "userInfo:delete",
+    "role:read",
+    "organization:read",
+    "user:write",
+    "user:delete",
+    "userWechatInfo:read",
+    "userWechatInfo:delete",
+    "authz:read",
+];
+
+/// ORG1 (with subsidiary SUB1) and ORG2, each with a department, a position, a role and a
+/// person; U1 of ORG1 also works for ORG2 and logs in with role STAFF granting `PERMISSIONS`
+async fn seed(app: &TestApp) -> String {
+    fixtures::organization(app, "ORG1").await;
+    app.create("/organization", json!({ "code": "SUB1", "name": "SUB1 org", "parentCode": "ORG1" })).await;
+    fixtures::organization(app, "ORG2").await;
+    for (index, code) in PERMISSIONS.iter().enumerate() {
+        app.create("/permission", json!({ "uuid": format!("p{}", index), "code": code, "name": code, "nodeType": "button" }))
+            .await;
+    }
+    app.create("/role", json!({ "code": "STAFF", "name": "Staff", "orgCode": "ORG1", "permissions": PERMISSIONS }))
+        .await;
+    app.create("/role", json!({ "code": "AUDITOR", "name": "Auditor", "orgCode": "ORG2" })).await;
+    for org_code in ["ORG1", "ORG2"] {
+        fixtures::department(app, &format!("D-{}", org_code), org_code).await;
+        fixtures::position(app, &format!("P-{}", org_code), &format!("D-{}", org_code), org_code).await;
+    }
+    fixtures::user_info(app, "U1", "ORG1", "D-ORG1", "P-ORG1").await;
+    fixtures::user_info(app, "U2", "ORG2", "D-ORG2", "P-ORG2").await;
+    app.create(
+        "/userInfo",
+        json!({ "userCode": "U1", "username": "u1-org2", "realName": "U1 name", "userType": "staff", "orgCode": "ORG2" }),
+    )
+    .await;
+    app.create("/userRoleRef", json!({ "userCode": "U1", "roleCode": "STAFF" })).await;
+    fixtures::user(app, "U1", "u1@pass").await;
+    app.login("U1", "u1@pass").await
+}
+
+fn values(records: &Value, key: &str) -> Vec<String> {
+    let mut values: Vec<String> = records
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|record| record[key].as_str().unwrap().to_string())
+        .collect();
+    values.sort();
+    values
+}
+
+async fn get_as(app: &TestApp, token: &str, org_code: Option<&str>, uri: &str) -> TestResponse {
+    app.send_in_org(Method::GET, uri, Some(token), org_code, None).await
+}
+
+#[tokio::test]
+async fn reads_only_return_the_rows_of_the_callers_organization() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+
+    let people = get_as(&app, &token, None, "/userInfo/list").await;
+    assert_eq!(values(people.data(), "orgCode"), ["ORG1"]);
+    let page = get_as(&app, &token, None, "/userInfo/page?orgCode=ORG2").await;
+    assert_eq!(page.data()["page_info"]["total"], 0);
+    assert_eq!(values(get_as(&app, &token, None, "/role/list").await.data(), "code"), ["STAFF"]);
+    assert_eq!(values(get_as(&app, &token, None, "/role/tree").await.data(), "code"), ["STAFF"]);
+    assert_eq!(values(get_as(&app, &token, None, "/organization/tree").await.data(), "code"), ["ORG1"]);
+
+    let u2 = app.get("/userInfo/list?userCode=U2").await.data()[0]["id"].as_i64().unwrap();
+    assert_eq!(get_as(&app, &token, None, &format!("/userInfo/{}", u2)).await.data(), &Value::Null);
+}
+
+#[tokio::test]
+async fn writes_stay_inside_the_callers_organization() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+    let person = |user_code: &str, org_code: Option<&str>| {
+        json!({ "userCode": user_code, "realName": "New", "userType": "staff", "orgCode": org_code })
+    };
+
+    let response = app.send(Method::POST, "/userInfo", Some(&token), Some(person("U3", None))).await;
+    let u3 = response.data().as_i64().unwrap();
+    assert_eq!(app.read("/userInfo", u3).await["orgCode"], "ORG1");
+
+    let response = app.send(Method::POST, "/userInfo", Some(&token), Some(person("U4", Some("ORG2")))).await;
+    assert_eq!(response.status, 403);
+
+    let u2 = app.get("/userInfo/list?userCode=U2").await.data()[0].clone();
+    let update = json!({ "recId": u2["id"], "recSign": u2["recSign"], "realName": "Hijacked" });
+    let response = app.send(Method::PUT, "/userInfo", Some(&token), Some(update)).await;
+    assert_eq!(response.status, 404);
+    let response = app
+        .send(Method::PUT, "/userInfo/delByIds", Some(&token), Some(json!({ "recIds": [u2["id"]] })))
+        .await;
+    assert_eq!(response.data(), 0);
+    assert_eq!(app.read("/userInfo", u2["id"].as_i64().unwrap()).await["realName"], "U2 name");
+}
+
+#[tokio::test]
+async fn the_org_code_header_selects_another_membership() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+
+    let people = get_as(&app, &token, Some("ORG2"), "/userInfo/list").await;
+    assert_eq!(values(people.data(), "userCode"), ["U1", "U2"]);
+    assert_eq!(values(get_as(&app, &token, Some("ORG2"), "/role/list").await.data(), "code"), ["AUDITOR"]);
+
+    let response = get_as(&app, &token, Some("SUB1"), "/userInfo/list").await;
+    assert_eq!(response.status, 403);
+    assert_eq!(response.body["error"], "forbidden");
+}
+
+#[tokio::test]
+async fn administrators_see_every_organization_unless_they_pick_one() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    assert_eq!(values(app.get("/userInfo/list").await.data(), "orgCode"), ["ORG1", "ORG2", "ORG2"]);
+    let people = app.send_in_org(Method::GET, "/userInfo/list", Some(&app.token), Some("ORG1"), None).await;
+    assert_eq!(values(people.data(), "userCode"), ["U1"]);
+    let tree = app
+        .send_in_org(Method::GET, "/organization/ORG2/departments/tree", Some(&app.token), Some("ORG1"), None)
+        .await;
+    assert_eq!(tree.status, 403);
+}
+
+#[tokio::test]
+async fn a_role_bound_inside_one_organization_does_not_authorize_another() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+    for org_code in ["ORG1", "ORG2"] {
+        fixtures::department(&app, "D-ALL", org_code).await;
+        fixtures::position(&app, "P-ALL", "D-ALL", org_code).await;
+    }
+    let bindings = [
+        ("W1", "/userRoleRef", json!({ "userCode": "W1", "roleCode": "STAFF", "orgCode": "ORG1" })),
+        ("W2", "/departmentRoleRef", json!({ "departmentCode": "D-ALL", "roleCode": "STAFF", "orgCode": "ORG1" })),
+        ("W3", "/positionRoleRef", json!({ "positionCode": "P-ALL", "roleCode": "STAFF", "orgCode": "ORG1" })),
+    ];
+    for (user_code, uri, binding) in bindings {
+        let username = user_code.to_lowercase();
+        for org_code in ["ORG1", "ORG2"] {
+            // the same department and position codes exist in both organizations
+            app.create(
+                "/userInfo",
+                json!({
+                    "userCode": user_code,
+                    "username": format!("{}-{}", username, org_code),
+                    "realName": format!("{} name", user_code),
+                    "userType": "staff",
+                    "orgCode": org_code,
+                    "departmentCode": "D-ALL",
+                    "positionCode": "P-ALL",
+                }),
+            )
+            .await;
+        }
+        app.create(uri, binding).await;
+        fixtures::user(&app, &format!("{}-ORG1", username), "w@pass").await;
+        let token = app.login(&format!("{}-ORG1", username), "w@pass").await;
+        let person = |org_code: &str| {
+            json!({ "userCode": format!("{}-{}", user_code, org_code), "realName": "New", "userType": "staff" })
+        };
+
+        let response = app.send_in_org(Method::POST, "/userInfo", Some(&token), Some("ORG1"), Some(person("ORG1"))).await;
+        assert!(response.data().as_i64().is_some(), "{} refused in ORG1: {}", uri, response.body);
+        let response = app.send_in_org(Method::POST, "/userInfo", Some(&token), Some("ORG2"), Some(person("ORG2"))).await;
+        assert_eq!(response.status, 403, "{} allowed in ORG2", uri);
+        assert_eq!(response.body["message"], "缺少权限 userInfo:write！");
+    }
+}
+
+#[tokio::test]
+async fn accounts_of_another_organization_keep_their_sessions() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+    let u2 = fixtures::user(&app, "U2", "u2@pass").await;
+    let u2_token = app.login("U2", "u2@pass").await;
+
+    let rec_sign = app.read("/user", u2).await["recSign"].clone();
+    let disable = json!({ "recId": u2, "recSign": rec_sign, "status": 1 });
+    assert_eq!(app.send(Method::PUT, "/user", Some(&token), Some(disable)).await.data(), 0);
+    let response = app.send(Method::PUT, "/user/delByIds", Some(&token), Some(json!({ "recIds": [u2] }))).await;
+    assert_eq!(response.data(), 0);
+    let response = app.send(Method::DELETE, "/user", Some(&token), Some(json!({ "recIds": [u2] }))).await;
+    assert_eq!(response.data(), 0);
+
+    assert_eq!(app.send(Method::GET, "/auth/sessions", Some(&u2_token), None).await.data().as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn permission_checks_stay_inside_the_callers_organization() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+    let check = |user_code: &str, org_code: Option<&str>| {
+        json!({ "userCode": user_code, "permissionCode": "userInfo:read", "orgCode": org_code })
+    };
+
+    let response = app.send(Method::POST, "/authz/check", Some(&token), Some(check("U1", None))).await;
+    assert_eq!(response.data()["allowed"], true);
+    let response = app.send(Method::POST, "/authz/check", Some(&token), Some(check("U1", Some("ORG2")))).await;
+    assert_eq!(response.status, 403);
+    let response = app.send(Method::POST, "/authz/check", Some(&token), Some(check("U2", None))).await;
+    assert_eq!(response.data()["allowed"], false);
+
+    let response = app.send(Method::GET, "/authz/users/U2/data-scope?orgCode=ORG2", Some(&token), None).await;
+    assert_eq!(response.status, 403);
+}
+
+#[tokio::test]
+async fn wechat_profiles_follow_the_people_of_the_organization() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+    for (index, org_code) in ["ORG1", "ORG2"].iter().enumerate() {
+        let union_id = format!("union-{}", org_code);
+        app.create(
+            "/userInfo",
+            json!({ "userCode": format!("WX-{}", org_code), "realName": "Wx", "userType": "staff", "orgCode": org_code, "wxUnionId": union_id }),
+        )
+        .await;
+        app.post("/userWechatInfo", json!({ "recId": index + 1, "unionId": union_id, "nickname": org_code }))
+            .await
+            .data();
+    }
+
+    let profiles = get_as(&app, &token, None, "/userWechatInfo/list").await;
+    assert_eq!(values(profiles.data(), "nickname"), ["ORG1"]);
+    assert_eq!(get_as(&app, &token, None, "/userWechatInfo/2").await.data(), &Value::Null);
+    let response = app.send(Method::PUT, "/userWechatInfo/delByIds", Some(&token), Some(json!({ "recIds": [2] }))).await;
+    assert_eq!(response.data(), 0);
+    assert_eq!(values(app.get("/userWechatInfo/list").await.data(), "nickname"), ["ORG1", "ORG2"]);
+}
+
+#[tokio::test]
+async fn an_api_node_of_an_organization_only_guards_its_own_requests() {
+    let app = TestApp::spawn().await;
+    let token = seed(&app).await;
+    app.create(
+        "/permission",
+        json!({ "uuid": "api-1", "code": "people:export", "name": "Export", "nodeType": "api", "linkUrl": "GET /userInfo/list", "orgCode": "ORG2" }),
+    )
+    .await;
+
+    assert_eq!(get_as(&app, &token, None, "/userInfo/list").await.status, 200);
+    let response = get_as(&app, &token, Some("ORG2"), "/userInfo/list").await;
+    assert_eq!(response.status, 403);
+    assert_eq!(response.body["message"], "缺少权限 people:export！");
+}
+
+#[tokio::test]
+async fn an_organization_code_is_matched_literally() {
+    let app = TestApp::spawn().await;
+    for code in ["T_1", "TX1"] {
+        fixtures::organization(&app, code).await;
+    }
+
+    let organizations = app.send_in_org(Method::GET, "/organization/list", Some(&app.token), Some("T_1"), None).await;
+    assert_eq!(values(organizations.data(), "code"), ["T_1"]);
+}
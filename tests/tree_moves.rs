@@ -0,0 +1,213 @@
+//! `POST /{organization|department|role}/:code/move`, re-parenting a node with its whole subtree
+
+mod common;
+
+use common::{fixtures, TestApp, TestResponse};
+use serde_json::{json, Value};
+
+async fn organization(app: &TestApp, code: &str, parent_code: Option<&str>) -> i64 {
+    app.create("/organization", json!({ "code": code, "name": format!("{} org", code), "parentCode": parent_code }))
+        .await
+}
+
+async fn department(app: &TestApp, code: &str, org_code: &str, parent_code: Option<&str>) -> i64 {
+    app.create(
+        "/department",
+        json!({ "code": code, "name": format!("{} dept", code), "orgCode": org_code, "parentCode": parent_code }),
+    )
+    .await
+}
+
+/// Moves `code` below `parent_code` with the current `recSign` of the node
+async fn move_node(app: &TestApp, base: &str, id: i64, code: &str, parent_code: Option<&str>) -> TestResponse {
+    let rec_sign = app.read(base, id).await["recSign"].clone();
+    app.post(&format!("{}/{}/move", base, code), json!({ "parentCode": parent_code, "recSign": rec_sign }))
+        .await
+}
+
+fn path(record: &Value) -> &str {
+    record["path"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn moving_an_organization_rewrites_the_paths_of_its_subtree() {
+    let app = TestApp::spawn().await;
+    organization(&app, "HQ", None).await;
+    let sub1 = organization(&app, "SUB1", Some("HQ")).await;
+    let branch = organization(&app, "BR1", Some("SUB1")).await;
+    let sub2 = organization(&app, "SUB2", Some("HQ")).await;
+
+    let response = move_node(&app, "/organization", sub1, "SUB1", Some("SUB2")).await;
+    assert_eq!(response.data(), 2);
+
+    let moved = app.read("/organization", sub1).await;
+    assert_eq!(moved["parentCode"], "SUB2");
+    assert_eq!(path(&moved), "/HQ/SUB2/SUB1/");
+    assert_eq!(path(&app.read("/organization", branch).await), "/HQ/SUB2/SUB1/BR1/");
+    assert_eq!(path(&app.read("/organization", sub2).await), "/HQ/SUB2/");
+    let descendants = app.get("/organization/SUB2/descendants").await;
+    assert_eq!(descendants.data().as_array().unwrap().len(), 2);
+
+    let response = move_node(&app, "/organization", sub1, "SUB1", None).await;
+    assert_eq!(response.data(), 2);
+    assert_eq!(app.read("/organization", sub1).await["parentCode"], Value::Null);
+    assert_eq!(path(&app.read("/organization", branch).await), "/SUB1/BR1/");
+}
+
+#[tokio::test]
+async fn a_node_cannot_move_below_itself_or_its_descendants() {
+    let app = TestApp::spawn().await;
+    let hq = organization(&app, "HQ", None).await;
+    organization(&app, "SUB1", Some("HQ")).await;
+    organization(&app, "BR1", Some("SUB1")).await;
+
+    for parent_code in ["HQ", "BR1"] {
+        let response = move_node(&app, "/organization", hq, "HQ", Some(parent_code)).await;
+        assert_eq!(response.status, 400, "moving below {}", parent_code);
+        assert_eq!(response.body["fields"][0]["field"], "parentCode");
+    }
+    assert_eq!(path(&app.read("/organization", hq).await), "/HQ/");
+
+    let response = move_node(&app, "/organization", hq, "HQ", Some("NOPE")).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(app.post("/organization/NOPE/move", json!({ "recSign": "x" })).await.status, 404);
+}
+
+#[tokio::test]
+async fn departments_move_within_their_organization_only() {
+    let app = TestApp::spawn().await;
+    fixtures::organization(&app, "ORG1").await;
+    fixtures::organization(&app, "ORG2").await;
+    let rd = department(&app, "RD", "ORG1", None).await;
+    let backend = department(&app, "BACKEND", "ORG1", Some("RD")).await;
+    department(&app, "TECH", "ORG1", None).await;
+    department(&app, "SALES", "ORG2", None).await;
+
+    assert_eq!(path(&app.read("/department", backend).await), "/RD/BACKEND/");
+    let response = app
+        .post("/department", json!({ "code": "EAST", "name": "East", "orgCode": "ORG2", "parentCode": "RD" }))
+        .await;
+    assert_eq!(response.status, 400);
+
+    let response = move_node(&app, "/department", rd, "RD", Some("SALES")).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["fields"][0]["field"], "parentCode");
+
+    assert_eq!(move_node(&app, "/department", rd, "RD", Some("TECH")).await.data(), 2);
+    assert_eq!(path(&app.read("/department", backend).await), "/TECH/RD/BACKEND/");
+}
+
+#[tokio::test]
+async fn a_department_finds_its_parent_among_the_departments_of_its_organization() {
+    let app = TestApp::spawn().await;
+    fixtures::organization(&app, "ORG1").await;
+    fixtures::organization(&app, "ORG2").await;
+    // the same code in another organization, created first
+    department(&app, "RD", "ORG2", None).await;
+    department(&app, "RD", "ORG1", None).await;
+
+    let backend = department(&app, "BACKEND", "ORG1", Some("RD")).await;
+    assert_eq!(path(&app.read("/department", backend).await), "/RD/BACKEND/");
+    let qa = department(&app, "QA", "ORG1", None).await;
+    assert_eq!(move_node(&app, "/department", qa, "QA", Some("RD")).await.data(), 1);
+    assert_eq!(path(&app.read("/department", qa).await), "/RD/QA/");
+}
+
+#[tokio::test]
+async fn roles_move_with_their_subtree() {
+    let app = TestApp::spawn().await;
+    app.create("/role", json!({ "code": "ADMIN", "name": "Admin" })).await;
+    let ops = app.create("/role", json!({ "code": "OPS", "name": "Ops" })).await;
+    let oncall = app.create("/role", json!({ "code": "ONCALL", "name": "On call", "parentCode": "OPS" })).await;
+
+    assert_eq!(move_node(&app, "/role", ops, "OPS", Some("ADMIN")).await.data(), 2);
+    assert_eq!(path(&app.read("/role", oncall).await), "/ADMIN/OPS/ONCALL/");
+}
+
+#[tokio::test]
+async fn a_move_with_a_stale_rec_sign_is_rejected() {
+    let app = TestApp::spawn().await;
+    organization(&app, "HQ", None).await;
+    let sub1 = organization(&app, "SUB1", None).await;
+    let rec_sign = app.read("/organization", sub1).await["recSign"].clone();
+    app.put("/organization", json!({ "recId": sub1, "recSign": rec_sign, "name": "renamed" })).await.data();
+
+    let response = app.post("/organization/SUB1/move", json!({ "parentCode": "HQ", "recSign": rec_sign })).await;
+    assert_eq!(response.status, 409);
+    assert_eq!(path(&app.read("/organization", sub1).await), "/SUB1/");
+
+    let response = app.post("/organization/SUB1/move", json!({ "parentCode": "HQ" })).await;
+    assert_eq!(response.status, 400);
+}
+
+#[tokio::test]
+async fn an_update_leaves_the_hierarchy_to_the_move_route() {
+    let app = TestApp::spawn().await;
+    organization(&app, "HQ", None).await;
+    let sub1 = organization(&app, "SUB1", None).await;
+    let branch = organization(&app, "BR1", Some("SUB1")).await;
+    let rec_sign = app.read("/organization", sub1).await["recSign"].clone();
+
+    let response = app.put("/organization", json!({ "recId": sub1, "recSign": rec_sign, "parentCode": "HQ" })).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["fields"][0]["field"], "parentCode");
+    let response = app.put("/organization", json!({ "recId": sub1, "recSign": rec_sign, "code": "SUB9" })).await;
+    assert_eq!(response.body["fields"][0]["field"], "code");
+
+    let rec_sign = app.read("/organization", branch).await["recSign"].clone();
+    let update = json!({ "recId": branch, "recSign": rec_sign, "code": "BR9", "path": "/forged/" });
+    assert_eq!(app.put("/organization", update).await.data(), 1);
+    assert_eq!(path(&app.read("/organization", branch).await), "/SUB1/BR9/");
+}
+
+#[tokio::test]
+async fn a_move_leaves_subtrees_whose_paths_only_match_as_a_pattern_alone() {
+    let app = TestApp::spawn().await;
+    organization(&app, "HQ", None).await;
+    let a_b = organization(&app, "A_B", None).await;
+    let a_b_child = organization(&app, "C", Some("A_B")).await;
+    organization(&app, "AXB", None).await;
+    let axb_child = organization(&app, "D", Some("AXB")).await;
+
+    let response = move_node(&app, "/organization", a_b, "A_B", Some("HQ")).await;
+    assert_eq!(response.data(), 2);
+    assert_eq!(path(&app.read("/organization", a_b_child).await), "/HQ/A_B/C/");
+    assert_eq!(path(&app.read("/organization", axb_child).await), "/AXB/D/");
+}
+
+#[tokio::test]
+async fn a_role_update_leaves_the_hierarchy_to_the_move_route() {
+    let app = TestApp::spawn().await;
+    app.create("/role", json!({ "code": "ADMIN", "name": "Admin" })).await;
+    let ops = app.create("/role", json!({ "code": "OPS", "name": "Ops" })).await;
+    let oncall = app.create("/role", json!({ "code": "ONCALL", "name": "On call", "parentCode": "OPS" })).await;
+    let rec_sign = app.read("/role", ops).await["recSign"].clone();
+
+    let response = app.put("/role", json!({ "recId": ops, "recSign": rec_sign, "parentCode": "ADMIN" })).await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body["fields"][0]["field"], "parentCode");
+    let response = app.put("/role", json!({ "recId": ops, "recSign": rec_sign, "code": "OPS9" })).await;
+    assert_eq!(response.body["fields"][0]["field"], "code");
+    assert_eq!(path(&app.read("/role", ops).await), "/OPS/");
+
+    let rec_sign = app.read("/role", oncall).await["recSign"].clone();
+    let response = app.put("/role", json!({ "recId": oncall, "recSign": rec_sign, "code": "ADMIN" })).await;
+    assert_eq!(response.status, 409);
+    let update = json!({ "recId": oncall, "recSign": rec_sign, "code": "PAGER", "parentCode": "OPS" });
+    assert_eq!(app.put("/role", update).await.data(), 1);
+    assert_eq!(path(&app.read("/role", oncall).await), "/OPS/PAGER/");
+}
+
+#[tokio::test]
+async fn moving_a_role_leaves_roles_whose_paths_only_match_as_a_pattern_alone() {
+    let app = TestApp::spawn().await;
+    app.create("/role", json!({ "code": "ADMIN", "name": "Admin" })).await;
+    let a_b = app.create("/role", json!({ "code": "A_B", "name": "A_B" })).await;
+    let a_b_child = app.create("/role", json!({ "code": "C", "name": "C", "parentCode": "A_B" })).await;
+    app.create("/role", json!({ "code": "AXB", "name": "AXB" })).await;
+    let axb_child = app.create("/role", json!({ "code": "D", "name": "D", "parentCode": "AXB" })).await;
+
+    assert_eq!(move_node(&app, "/role", a_b, "A_B", Some("ADMIN")).await.data(), 2);
+    assert_eq!(path(&app.read("/role", a_b_child).await), "/ADMIN/A_B/C/");
+    assert_eq!(path(&app.read("/role", axb_child).await), "/AXB/D/");
+}
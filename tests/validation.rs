@@ -0,0 +1,120 @@
+//! The `Validate` rules of request bodies, checked before a body reaches a service
+
+mod common;
+
+use axum::http::Method;
+use common::{fixtures, TestApp};
+use serde_json::{json, Value};
+
+/// `(field, message)` of every violation of a 400 validation response
+fn violations(response: &common::TestResponse) -> Vec<(String, String)> {
+    assert_eq!(response.status, 400, "unexpected response {}", response.body);
+    assert_eq!(response.body["error"], "validation");
+    response.body["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| (field["field"].as_str().unwrap().to_string(), field["message"].as_str().unwrap().to_string()))
+        .collect()
+}
+
+fn fields(response: &common::TestResponse) -> Vec<String> {
+    violations(response).into_iter().map(|(field, _)| field).collect()
+}
+
+#[tokio::test]
+async fn a_create_reports_every_violation_at_once() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .post(
+            "/userInfo",
+            json!({
+                "userCode": "U/1",
+                "realName": " ",
+                "userType": "robot",
+                "cellphone": "12345",
+                "email": "alice@",
+                "effectiveDate": "2026-01-01",
+                "invalidDate": "2025-12-31",
+            }),
+        )
+        .await;
+    assert_eq!(
+        violations(&response),
+        [
+            ("userCode".to_string(), "userCode 只能包含字母、数字、下划线、中划线和点！".to_string()),
+            ("realName".to_string(), "真实姓名不能为空！".to_string()),
+            ("cellphone".to_string(), "cellphone 不是有效的手机号码！".to_string()),
+            ("userType".to_string(), "userType 必须是 staff、external、system 之一！".to_string()),
+            ("email".to_string(), "email 不是有效的邮箱地址！".to_string()),
+            ("invalidDate".to_string(), "失效日期不能早于生效日期！".to_string()),
+        ]
+    );
+    assert!(app.get("/userInfo/list").await.data().as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn required_fields_are_named_when_missing() {
+    let app = TestApp::spawn().await;
+
+    assert_eq!(fields(&app.post("/department", json!({})).await), ["code", "name", "orgCode"]);
+    assert_eq!(fields(&app.post("/userRoleRef", json!({ "orgCode": "ORG1" })).await), ["userCode", "roleCode"]);
+    assert_eq!(fields(&app.post("/systemConfig", json!({ "configKey": "k" })).await), ["status"]);
+}
+
+#[tokio::test]
+async fn an_update_only_checks_the_fields_it_carries() {
+    let app = TestApp::spawn().await;
+    let id = fixtures::organization(&app, "ORG1").await;
+    let rec_sign = app.read("/organization", id).await["recSign"].clone();
+
+    let response = app.put("/organization", json!({ "recId": id, "recSign": rec_sign, "email": "not-an-email" })).await;
+    assert_eq!(fields(&response), ["email"]);
+    let response = app.put("/organization", json!({ "recId": id, "recSign": rec_sign, "name": "" })).await;
+    assert_eq!(violations(&response), [("name".to_string(), "组织名称不能为空！".to_string())]);
+
+    let update = json!({ "recId": id, "recSign": rec_sign, "email": "office@example.com", "cellphone": "+8613800138000" });
+    assert_eq!(app.put("/organization", update).await.data(), 1);
+    assert_eq!(app.read("/organization", id).await["email"], "office@example.com");
+}
+
+#[tokio::test]
+async fn lengths_enums_and_cross_field_rules_apply_to_every_resource() {
+    let app = TestApp::spawn().await;
+
+    let long_name: String = "x".repeat(256);
+    let response = app.post("/role", json!({ "code": "R1", "name": long_name })).await;
+    assert_eq!(violations(&response), [("name".to_string(), "name 长度不能超过 255 个字符！".to_string())]);
+
+    let response = app.post("/permission", json!({ "uuid": "p1", "name": "Menu", "nodeType": "page" })).await;
+    assert_eq!(fields(&response), ["nodeType"]);
+
+    let response = app.post("/group", json!({ "code": "G1", "parentCode": "G1", "name": "Loop", "type": "team", "application": "auth" })).await;
+    assert_eq!(fields(&response), ["parentCode"]);
+
+    let response = app.post("/user", json!({ "userName": "carol", "password": "123" })).await;
+    assert_eq!(fields(&response), ["password"]);
+
+    let response = app.post("/userWechatInfo", json!({ "unionId": "union-1" })).await;
+    assert_eq!(fields(&response), ["recId"]);
+}
+
+#[tokio::test]
+async fn bodies_outside_crud_are_validated_too() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .send(Method::POST, "/auth/login", None, Some(json!({ "username": "", "password": "" })))
+        .await;
+    assert_eq!(fields(&response), ["username", "password"]);
+
+    let response = app
+        .put("/auth/password", json!({ "username": "admin", "oldPassword": "admin@123", "newPassword": "short" }))
+        .await;
+    assert_eq!(violations(&response), [("newPassword".to_string(), "newPassword 长度不能少于 6 个字符！".to_string())]);
+
+    let checks: Vec<Value> = vec![json!({ "permissionCode": "role:read" }), json!({ "permissionCode": " " })];
+    let response = app.post("/authz/check-batch", json!({ "userCode": "U1", "checks": checks })).await;
+    assert_eq!(fields(&response), ["checks[1].permissionCode"]);
+}